@@ -0,0 +1,185 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::{Icon, icons::ld_icons::{LdArrowLeft, LdDownload, LdPackage}};
+
+use crate::routes::Route;
+use crate::scripts::instances::{self, Instance};
+use crate::scripts::modrinth::{self, ModrinthClient, ProjectType, SearchHit, SearchQuery};
+
+const PAGE_SIZE: u32 = 20;
+
+#[component]
+pub fn BrowseMods(id: ReadOnlySignal<String>) -> Element {
+    let mut query = use_signal(String::new);
+    let mut project_type = use_signal(|| ProjectType::Mod);
+    let mut offset = use_signal(|| 0u32);
+
+    let instance = use_resource(move || async move {
+        instances::load_instance(&id()).await.map_err(|e| e.to_string())
+    });
+
+    let results = use_resource(move || async move {
+        let Some(Ok(instance)) = instance() else {
+            return Err("Instance not loaded".to_string());
+        };
+        let search = SearchQuery {
+            query: query(),
+            project_type: project_type(),
+            game_version: Some(instance.game_version.clone()),
+            loaders: match project_type() {
+                ProjectType::Mod => modrinth::loader_names(instance.loader).iter().map(|l| l.to_string()).collect(),
+                _ => Vec::new(),
+            },
+            offset: offset(),
+            limit: PAGE_SIZE,
+        };
+        let client = ModrinthClient::from_settings().await.map_err(|e| e.to_string())?;
+        client.search(&search).await.map_err(|e| e.to_string())
+    });
+
+    rsx! {
+        div { class: "flex-1 flex flex-col gap-4 p-6",
+            Link {
+                class: "flex items-center gap-1 text-[var(--grey)] hover:text-[var(--foreground)]",
+                to: Route::InstanceMods { id: id() },
+                Icon { icon: LdArrowLeft, class: "w-4 h-4" }
+                "Mods"
+            }
+            div { class: "flex items-center gap-2",
+                h2 { class: "flex-1", "Browse Modrinth" }
+                input {
+                    class: "bg-[var(--background-dark)] p-2 rounded",
+                    placeholder: "Search...",
+                    value: "{query}",
+                    oninput: move |e| {
+                        query.set(e.value());
+                        offset.set(0);
+                    },
+                }
+                select {
+                    class: "bg-[var(--background-dark)] p-2 rounded",
+                    onchange: move |evt| {
+                        project_type
+                            .set(
+                                match evt.value().as_str() {
+                                    "ResourcePack" => ProjectType::ResourcePack,
+                                    "Shader" => ProjectType::Shader,
+                                    _ => ProjectType::Mod,
+                                },
+                            );
+                        offset.set(0);
+                    },
+                    option { value: "Mod", "Mods" }
+                    option { value: "ResourcePack", "Resource packs" }
+                    option { value: "Shader", "Shaders" }
+                }
+            }
+
+            match (instance(), results()) {
+                (Some(Ok(instance)), Some(Ok(response))) => {
+                    let page = response.offset / PAGE_SIZE + 1;
+                    let pages = response.total_hits.div_ceil(PAGE_SIZE).max(1);
+                    rsx! {
+                        p { class: "text-[var(--grey)]",
+                            "{response.total_hits} results for {instance.version_label()}"
+                        }
+                        div { class: "flex flex-col gap-2",
+                            for hit in response.hits {
+                                SearchResult {
+                                    key: "{hit.project_id}",
+                                    hit,
+                                    instance: instance.clone(),
+                                    project_type: project_type(),
+                                }
+                            }
+                        }
+                        div { class: "flex items-center justify-center gap-2",
+                            button {
+                                class: "bg-[var(--background-dark)] p-2 px-3 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                                disabled: offset() == 0,
+                                onclick: move |_| offset.set(offset().saturating_sub(PAGE_SIZE)),
+                                "Previous"
+                            }
+                            p { "{page} / {pages}" }
+                            button {
+                                class: "bg-[var(--background-dark)] p-2 px-3 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                                disabled: page >= pages,
+                                onclick: move |_| offset.set(offset() + PAGE_SIZE),
+                                "Next"
+                            }
+                        }
+                    }
+                }
+                (Some(Err(e)), _) | (_, Some(Err(e))) => rsx! {
+                    p { class: "text-[var(--red)]", "{e}" }
+                },
+                _ => rsx! {
+                    p { class: "text-[var(--grey)]", "Loading..." }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn SearchResult(hit: SearchHit, instance: Instance, project_type: ProjectType) -> Element {
+    let mut status = use_signal(|| None::<Result<String, String>>);
+    let mut installing = use_signal(|| false);
+
+    rsx! {
+        div { class: "flex items-center gap-3 p-3 rounded-lg bg-[var(--background-dark)]",
+            div { class: "w-12 h-12 shrink-0 rounded bg-[var(--background-darker)] flex items-center justify-center overflow-hidden",
+                if let Some(icon) = &hit.icon_url {
+                    img { class: "w-full h-full", src: "{icon}" }
+                } else {
+                    Icon { icon: LdPackage, class: "w-6 h-6 text-[var(--grey)]" }
+                }
+            }
+            div { class: "flex-1 min-w-0",
+                p { class: "truncate",
+                    span { class: "font-bold", "{hit.title}" }
+                    span { class: "text-[var(--grey)]", " by {hit.author}" }
+                }
+                p { class: "text-xs truncate", "{hit.description}" }
+                p { class: "text-[var(--grey)] text-xs", "{hit.downloads} downloads" }
+                match status() {
+                    Some(Ok(msg)) => rsx! {
+                        p { class: "text-[var(--green)] text-xs", "{msg}" }
+                    },
+                    Some(Err(msg)) => rsx! {
+                        p { class: "text-[var(--red)] text-xs", "{msg}" }
+                    },
+                    None => rsx! {},
+                }
+            }
+            button {
+                class: "flex items-center gap-1 bg-[var(--green)] text-[var(--background-darker)] p-2 px-3 rounded hover:bg-[var(--green-dark)] cursor-pointer",
+                disabled: installing(),
+                onclick: move |_| {
+                    let instance = instance.clone();
+                    let project_id = hit.project_id.clone();
+                    async move {
+                        installing.set(true);
+                        status.set(None);
+                        let result = async {
+                            let client = ModrinthClient::from_settings().await?;
+                            modrinth::install_project(&client, &instance, &project_id, project_type).await
+                        };
+                        match result.await {
+                            Ok(files) if files.is_empty() => {
+                                status.set(Some(Ok("Already installed".to_string())));
+                            }
+                            Ok(files) => {
+                                let names: Vec<String> = files.iter().map(|f| f.file_name.clone()).collect();
+                                status.set(Some(Ok(format!("Installed {}", names.join(", ")))));
+                            }
+                            Err(e) => status.set(Some(Err(e.to_string()))),
+                        }
+                        installing.set(false);
+                    }
+                },
+                Icon { icon: LdDownload, class: "w-4 h-4" }
+                if installing() { "Installing..." } else { "Install" }
+            }
+        }
+    }
+}
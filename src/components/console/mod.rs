@@ -0,0 +1,2 @@
+pub mod page;
+pub mod view;
\ No newline at end of file
@@ -0,0 +1,75 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::{Icon, icons::ld_icons::LdArrowLeft};
+
+use crate::components::console::view::{ConsoleLine, ConsoleView};
+use crate::routes::Route;
+use crate::scripts::process;
+use crate::state::{CONSOLE_LOG, GAME_LOGS, GAMES};
+
+/// The launcher's own log
+#[component]
+pub fn LauncherConsole() -> Element {
+    let lines: Vec<ConsoleLine> = CONSOLE_LOG.read().iter().map(ConsoleLine::from).collect();
+
+    rsx! {
+        div { class: "flex-1 min-h-0 flex flex-col gap-4 p-6",
+            h2 { "Launcher log" }
+            ConsoleView { lines }
+        }
+    }
+}
+
+/// Output of the instance's latest game run
+#[component]
+pub fn InstanceConsole(id: ReadOnlySignal<String>) -> Element {
+    let run = process::latest_run(&GAMES.read(), &id()).map(|g| g.run);
+    let lines = run.map(run_lines).unwrap_or_default();
+
+    rsx! {
+        div { class: "flex-1 min-h-0 flex flex-col gap-4 p-6",
+            Link {
+                class: "flex items-center gap-1 text-[var(--grey)] hover:text-[var(--foreground)]",
+                to: Route::InstanceDetails { id: id() },
+                Icon { icon: LdArrowLeft, class: "w-4 h-4" }
+                "Instance"
+            }
+            h2 { "Game log" }
+            ConsoleView { lines }
+        }
+    }
+}
+
+/// Output of one run, for instances running more than once
+#[component]
+pub fn RunConsole(run: u64) -> Element {
+    let game = GAMES.read().get(&run).cloned();
+    let lines = run_lines(run);
+
+    rsx! {
+        div { class: "flex-1 min-h-0 flex flex-col gap-4 p-6",
+            Link {
+                class: "flex items-center gap-1 text-[var(--grey)] hover:text-[var(--foreground)]",
+                to: Route::Library {},
+                Icon { icon: LdArrowLeft, class: "w-4 h-4" }
+                "Library"
+            }
+            match game {
+                Some(game) => rsx! {
+                    h2 { "Game log: {game.instance_name} #{run}" }
+                },
+                None => rsx! {
+                    h2 { "Game log #{run}" }
+                },
+            }
+            ConsoleView { lines }
+        }
+    }
+}
+
+fn run_lines(run: u64) -> Vec<ConsoleLine> {
+    GAME_LOGS
+        .read()
+        .get(&run)
+        .map(|entries| entries.iter().map(ConsoleLine::from).collect())
+        .unwrap_or_default()
+}
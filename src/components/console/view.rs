@@ -0,0 +1,161 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::{Icon, icons::ld_icons::LdCopy};
+use log::Level;
+use std::rc::Rc;
+
+use crate::logger::LogEntry;
+use crate::scripts::process::GameLogEntry;
+
+/// Lines rendered at once, older matches are only reachable through search or copy
+const MAX_VISIBLE: usize = 2000;
+
+const LEVELS: [Level; 5] = [Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace];
+
+/// A log record of either the launcher or a game, as the console shows it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsoleLine {
+    pub level: Level,
+    /// Thread and logger for game events
+    pub source: Option<String>,
+    pub message: String,
+    pub throwable: Option<String>,
+}
+
+impl ConsoleLine {
+    fn matches(&self, search: &str) -> bool {
+        search.is_empty()
+            || self.message.to_lowercase().contains(search)
+            || self.source.as_ref().is_some_and(|s| s.to_lowercase().contains(search))
+            || self.throwable.as_ref().is_some_and(|t| t.to_lowercase().contains(search))
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = match &self.source {
+            Some(source) => format!("[{}] [{}] {}", self.level, source, self.message),
+            None => format!("[{}] {}", self.level, self.message),
+        };
+        if let Some(throwable) = &self.throwable {
+            text.push('\n');
+            text.push_str(throwable);
+        }
+        text
+    }
+}
+
+impl From<&LogEntry> for ConsoleLine {
+    fn from(entry: &LogEntry) -> Self {
+        ConsoleLine {
+            level: entry.level,
+            source: None,
+            message: entry.message.clone(),
+            throwable: None,
+        }
+    }
+}
+
+impl From<&GameLogEntry> for ConsoleLine {
+    fn from(entry: &GameLogEntry) -> Self {
+        let source = match (&entry.thread, &entry.logger) {
+            (Some(thread), Some(logger)) => Some(format!("{}/{}", thread, logger)),
+            (Some(source), None) | (None, Some(source)) => Some(source.clone()),
+            (None, None) => None,
+        };
+        ConsoleLine {
+            level: entry.level,
+            source,
+            message: entry.message.clone(),
+            throwable: entry.throwable.clone(),
+        }
+    }
+}
+
+fn level_color(level: Level) -> &'static str {
+    match level {
+        Level::Error => "text-[var(--red)]",
+        Level::Warn => "text-[var(--yellow)]",
+        Level::Info => "text-[var(--foreground)]",
+        Level::Debug | Level::Trace => "text-[var(--grey)]",
+    }
+}
+
+/// Log lines with level filters, search and copy
+#[component]
+pub fn ConsoleView(lines: Vec<ConsoleLine>) -> Element {
+    let mut levels = use_signal(|| vec![Level::Error, Level::Warn, Level::Info]);
+    let mut search = use_signal(String::new);
+
+    let query = search().to_lowercase();
+    let shown: Rc<Vec<ConsoleLine>> = Rc::new(
+        lines
+            .into_iter()
+            .filter(|line| levels.read().contains(&line.level) && line.matches(&query))
+            .collect(),
+    );
+    let hidden = shown.len().saturating_sub(MAX_VISIBLE);
+
+    rsx! {
+        div { class: "flex items-center gap-2",
+            input {
+                class: "flex-1 bg-[var(--background-dark)] p-2 rounded",
+                placeholder: "Search...",
+                value: "{search}",
+                oninput: move |e| search.set(e.value()),
+            }
+            for level in LEVELS {
+                label { class: "flex items-center gap-1 cursor-pointer {level_color(level)}",
+                    input {
+                        r#type: "checkbox",
+                        checked: levels.read().contains(&level),
+                        onchange: move |e| {
+                            let mut levels = levels.write();
+                            levels.retain(|l| *l != level);
+                            if e.checked() {
+                                levels.push(level);
+                            }
+                        },
+                    }
+                    "{level}"
+                }
+            }
+            button {
+                class: "flex items-center gap-1 bg-[var(--background-dark)] p-2 px-3 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                onclick: {
+                    let shown = shown.clone();
+                    move |_| {
+                        // Only built on click, the log can be long
+                        let copy_text = shown.iter().map(ConsoleLine::to_text).collect::<Vec<_>>().join("\n");
+                        // Serialized as a JSON string so any text is a valid JS literal
+                        match serde_json::to_string(&copy_text) {
+                            Ok(text) => {
+                                let _ = document::eval(&format!("navigator.clipboard.writeText({})", text));
+                            }
+                            Err(e) => log::error!("Failed to copy log: {}", e),
+                        }
+                    }
+                },
+                Icon { icon: LdCopy, class: "w-4 h-4" }
+                "Copy"
+            }
+        }
+
+        div { class: "flex-1 min-h-0 overflow-y-auto bg-[var(--background-darker)] rounded p-2 font-mono text-xs select-text",
+            if hidden > 0 {
+                p { class: "text-[var(--grey)]", "{hidden} older lines hidden" }
+            }
+            if shown.is_empty() {
+                p { class: "text-[var(--grey)]", "Nothing to show" }
+            }
+            for line in shown.iter().skip(hidden) {
+                div { class: "whitespace-pre-wrap break-all {level_color(line.level)}",
+                    if let Some(source) = &line.source {
+                        span { class: "text-[var(--grey)]", "[{source}] " }
+                    }
+                    "{line.message}"
+                    if let Some(throwable) = &line.throwable {
+                        pre { class: "text-[var(--red)]", "{throwable}" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1 @@
+pub mod panel;
\ No newline at end of file
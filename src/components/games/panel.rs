@@ -0,0 +1,90 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::{
+    Icon,
+    icons::ld_icons::{LdFolderOpen, LdSkull, LdSquare, LdTerminal},
+};
+
+use crate::routes::Route;
+use crate::scripts::instances;
+use crate::scripts::process::{self, GameProcess, GameState, StopRequest};
+use crate::state::GAMES;
+
+/// Every game started from the launcher that hasn't exited yet
+#[component]
+pub fn RunningGames() -> Element {
+    let mut games: Vec<GameProcess> = GAMES
+        .read()
+        .values()
+        .filter(|g| g.state.is_active())
+        .cloned()
+        .collect();
+    games.sort_by_key(|g| g.run);
+
+    if games.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "flex flex-col gap-2 p-3 rounded-lg bg-[var(--background-dark)]",
+            h3 { "Running games" }
+            for game in games {
+                RunningGame { key: "{game.run}", game }
+            }
+        }
+    }
+}
+
+#[component]
+fn RunningGame(game: GameProcess) -> Element {
+    let button_class = "flex items-center gap-1 bg-[var(--background-darker)] p-1 px-2 rounded hover:bg-[var(--background-light)] cursor-pointer text-sm";
+    let status = match (&game.state, game.pid) {
+        (GameState::Starting, _) => "Starting".to_string(),
+        (_, Some(pid)) => format!("Running, PID {}", pid),
+        (_, None) => "Running".to_string(),
+    };
+    let started = instances::ago_label(game.started_at);
+    let run = game.run;
+
+    rsx! {
+        div { class: "flex items-center gap-3",
+            div { class: "flex-1 flex flex-col",
+                p { class: "font-bold", "{game.instance_name} #{run}" }
+                p { class: "text-[var(--grey)] text-sm", "{status} · started {started}" }
+            }
+            Link {
+                class: button_class,
+                to: Route::RunConsole { run },
+                Icon { icon: LdTerminal, class: "w-4 h-4" }
+                "Log"
+            }
+            button {
+                class: button_class,
+                onclick: {
+                    let dir = game.game_dir.clone();
+                    move |_| {
+                        let path = std::fs::canonicalize(&dir).unwrap_or(dir.clone());
+                        if let Err(e) = webbrowser::open(&path.to_string_lossy()) {
+                            log::error!("Failed to open the game folder: {}", e);
+                        }
+                    }
+                },
+                Icon { icon: LdFolderOpen, class: "w-4 h-4" }
+                "Folder"
+            }
+            button {
+                class: button_class,
+                disabled: game.pid.is_none(),
+                onclick: move |_| process::request_stop(run, StopRequest::Stop),
+                Icon { icon: LdSquare, class: "w-4 h-4" }
+                "Stop"
+            }
+            button {
+                class: "flex items-center gap-1 bg-[var(--red)] text-[var(--background-darker)] p-1 px-2 rounded hover:bg-[var(--red-dark)] cursor-pointer text-sm",
+                disabled: game.pid.is_none(),
+                onclick: move |_| process::request_stop(run, StopRequest::Kill),
+                Icon { icon: LdSkull, class: "w-4 h-4" }
+                "Kill"
+            }
+        }
+    }
+}
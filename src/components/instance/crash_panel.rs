@@ -0,0 +1,112 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::{
+    Icon,
+    icons::ld_icons::{LdCopy, LdFileText, LdX},
+};
+
+use crate::scripts::crash::CrashAnalysis;
+use crate::state::CRASHES;
+
+/// What we could tell about the latest crash of an instance
+#[component]
+pub fn CrashPanel(instance_id: String, analysis: CrashAnalysis) -> Element {
+    let button_class = "flex items-center gap-1 bg-[var(--background-dark)] p-1 px-2 rounded hover:bg-[var(--background-light)] cursor-pointer";
+    let description = analysis.report.as_ref().and_then(|r| r.description.clone());
+    let suspected_mods = analysis
+        .report
+        .as_ref()
+        .map(|r| r.suspected_mods.join(", "))
+        .unwrap_or_default();
+    let stack_trace = analysis
+        .report
+        .as_ref()
+        .map(|r| r.stack_trace.join("\n"))
+        .unwrap_or_default();
+
+    rsx! {
+        div { class: "flex flex-col gap-3 p-3 rounded-lg bg-[var(--background-darker)] border border-[var(--red)]",
+            div { class: "flex items-center gap-2",
+                h3 { class: "flex-1 text-[var(--red)]",
+                    "The game crashed"
+                    if let Some(code) = analysis.exit_code {
+                        " (exit code {code})"
+                    }
+                }
+                if let Some(report) = analysis.report.clone() {
+                    button {
+                        class: button_class,
+                        onclick: {
+                            let path = report.path.clone();
+                            move |_| {
+                                let path = std::fs::canonicalize(&path).unwrap_or(path.clone());
+                                if let Err(e) = webbrowser::open(&path.to_string_lossy()) {
+                                    log::error!("Failed to open crash report: {}", e);
+                                }
+                            }
+                        },
+                        Icon { icon: LdFileText, class: "w-4 h-4" }
+                        "Open report"
+                    }
+                    button {
+                        class: button_class,
+                        onclick: move |_| {
+                            // Serialized as a JSON string so any text is a valid JS literal
+                            match serde_json::to_string(&report.content) {
+                                Ok(text) => {
+                                    let _ = document::eval(&format!("navigator.clipboard.writeText({})", text));
+                                }
+                                Err(e) => log::error!("Failed to copy crash report: {}", e),
+                            }
+                        },
+                        Icon { icon: LdCopy, class: "w-4 h-4" }
+                        "Copy report"
+                    }
+                }
+                button {
+                    class: button_class,
+                    onclick: move |_| {
+                        CRASHES.write().remove(&instance_id);
+                    },
+                    Icon { icon: LdX, class: "w-4 h-4" }
+                }
+            }
+
+            if let Some(description) = description {
+                p { "{description}" }
+            }
+
+            if analysis.diagnoses.is_empty() {
+                p { class: "text-[var(--grey)]",
+                    "No known cause found. Check the game log or the crash report."
+                }
+            }
+            for diagnosis in analysis.diagnoses.iter() {
+                div { class: "flex flex-col gap-1",
+                    p { class: "font-bold", "{diagnosis.title}" }
+                    p { class: "text-[var(--grey)]", "{diagnosis.explanation}" }
+                    ul { class: "list-disc pl-5",
+                        for fix in diagnosis.fixes.iter() {
+                            li { "{fix}" }
+                        }
+                    }
+                    p { class: "font-mono text-xs text-[var(--grey)] break-all", "{diagnosis.evidence}" }
+                }
+            }
+
+            if !suspected_mods.is_empty() {
+                p {
+                    span { class: "text-[var(--grey)]", "Suspected mods: " }
+                    "{suspected_mods}"
+                }
+            }
+            if !stack_trace.is_empty() {
+                details {
+                    summary { class: "cursor-pointer text-[var(--grey)]", "Stack trace" }
+                    pre { class: "font-mono text-xs whitespace-pre-wrap break-all select-text",
+                        "{stack_trace}"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+use crate::scripts::game::DATA_DIR;
+use crate::scripts::instances::Instance;
+use crate::scripts::modpacks::{self, DEFAULT_INCLUDES, ExportOptions};
+use crate::scripts::modrinth::ModrinthClient;
+
+#[component]
+pub fn ExportModal(show_modal: Signal<bool>, instance: Instance) -> Element {
+    let mut name = use_signal(|| instance.name.clone());
+    let mut version = use_signal(|| "1.0.0".to_string());
+    let mut includes = use_signal(Vec::<String>::new);
+    let mut busy = use_signal(|| false);
+    let mut status = use_signal(|| None::<Result<String, String>>);
+
+    // Folders of the game directory that can go into overrides/
+    let entries = use_resource({
+        let instance = instance.clone();
+        move || {
+            let instance = instance.clone();
+            async move {
+                let entries = modpacks::exportable_entries(&instance).await.unwrap_or_else(|e| {
+                    log::error!("Failed to list instance files: {}", e);
+                    Vec::new()
+                });
+                includes.set(
+                    entries
+                        .iter()
+                        .filter(|e| DEFAULT_INCLUDES.contains(&e.as_str()))
+                        .cloned()
+                        .collect(),
+                );
+                entries
+            }
+        }
+    });
+
+    let input_class = "bg-[var(--background-dark)] p-2 rounded w-full";
+
+    rsx! {
+        // Background
+        div {
+            class: "absolute inset-0 z-40 bg-[var(--background-darker)]/50 flex items-center justify-center",
+            onclick: move |_| {
+                if !busy() {
+                    show_modal.set(false);
+                }
+            },
+            // Modal
+            div {
+                class: "bg-[var(--background)] p-5 rounded-lg w-1/3 flex flex-col gap-3",
+                onclick: move |e| e.stop_propagation(),
+                h2 { "Export Modpack" }
+                input {
+                    class: input_class,
+                    placeholder: "Pack name",
+                    value: "{name}",
+                    oninput: move |e| name.set(e.value()),
+                }
+                input {
+                    class: input_class,
+                    placeholder: "Pack version",
+                    value: "{version}",
+                    oninput: move |e| version.set(e.value()),
+                }
+                p { class: "text-[var(--grey)]", "Include in overrides:" }
+                div { class: "flex flex-col gap-1 max-h-48 overflow-y-auto",
+                    for entry in entries().unwrap_or_default() {
+                        label { key: "{entry}", class: "flex items-center gap-2 cursor-pointer",
+                            input {
+                                r#type: "checkbox",
+                                checked: includes().contains(&entry),
+                                onchange: {
+                                    let entry = entry.clone();
+                                    move |e: FormEvent| {
+                                        if e.checked() {
+                                            includes.write().push(entry.clone());
+                                        } else {
+                                            includes.write().retain(|i| *i != entry);
+                                        }
+                                    }
+                                },
+                            }
+                            "{entry}"
+                        }
+                    }
+                }
+                match status() {
+                    Some(Ok(msg)) => rsx! {
+                        p { class: "text-[var(--green)] break-all", "{msg}" }
+                    },
+                    Some(Err(msg)) => rsx! {
+                        p { class: "text-[var(--red)]", "{msg}" }
+                    },
+                    None => rsx! {},
+                }
+                button {
+                    class: "border border-[var(--background-darker)] bg-[var(--background-dark)] p-2 px-4 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                    disabled: busy(),
+                    onclick: {
+                        let instance = instance.clone();
+                        move |_| {
+                            let instance = instance.clone();
+                            async move {
+                                busy.set(true);
+                                status.set(None);
+
+                                let options = ExportOptions {
+                                    name: name().trim().to_string(),
+                                    version: version().trim().to_string(),
+                                    summary: None,
+                                    includes: includes(),
+                                };
+                                let out_path = PathBuf::from(DATA_DIR)
+                                    .join("exports")
+                                    .join(format!("{}-{}.mrpack", options.name, options.version));
+
+                                let result = match ModrinthClient::from_settings().await {
+                                    Ok(client) => {
+                                        modpacks::export_mrpack(&client, &instance, &options, &out_path).await
+                                    }
+                                    Err(e) => Err(e),
+                                };
+                                match result {
+                                    Ok(()) => status.set(Some(Ok(format!("Saved to {}", out_path.display())))),
+                                    Err(e) => status.set(Some(Err(e.to_string()))),
+                                }
+                                busy.set(false);
+                            }
+                        }
+                    },
+                    if busy() { "Exporting..." } else { "Export" }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use dioxus::prelude::*;
+
+use crate::scripts::instances::history::{self, HistorySummary};
+use crate::scripts::instances::{self, Instance, SessionEnd};
+use crate::scripts::process;
+use crate::state::GAMES;
+
+/// Sessions shown in the recent list
+const RECENT_SESSIONS: usize = 10;
+
+/// Playtime totals and the latest sessions of an instance
+#[component]
+pub fn HistoryPanel(instance: Instance) -> Element {
+    // Only changes when a game starts or stops, not on every log line
+    let id = instance.id.clone();
+    let game_state = use_memo(move || process::latest_run(&GAMES.read(), &id).map(|g| g.state));
+
+    let history = use_resource(move || {
+        let instance = instance.clone();
+        async move {
+            game_state.read();
+            history::load_history(&instance).await.map_err(|e| e.to_string())
+        }
+    });
+
+    let history = match history() {
+        Some(Ok(history)) => history,
+        Some(Err(e)) => {
+            return rsx! {
+                p { class: "text-[var(--red)]", "Failed to load the play history: {e}" }
+            };
+        }
+        None => return rsx! {},
+    };
+    if history.is_empty() {
+        return rsx! {};
+    }
+
+    let summary: HistorySummary = history::summarize(&history);
+    let HistorySummary { sessions, crashes, .. } = summary;
+    let total = instances::duration_label(summary.total_secs);
+    let average = instances::duration_label(summary.average_secs());
+    let longest = instances::duration_label(summary.longest_secs);
+
+    rsx! {
+        div { class: "flex flex-col gap-2 p-3 rounded-lg bg-[var(--background-dark)]",
+            h3 { "Play history" }
+            div { class: "flex gap-6 text-[var(--grey)]",
+                p { "{sessions} sessions" }
+                p { "Total: {total}" }
+                p { "Average: {average}" }
+                p { "Longest: {longest}" }
+                if crashes > 0 {
+                    p { class: "text-[var(--red)]", "{crashes} crashed" }
+                }
+            }
+            div { class: "flex flex-col",
+                for session in history.iter().rev().take(RECENT_SESSIONS) {
+                    div {
+                        key: "{session.started_at}",
+                        class: "flex gap-4 text-sm py-1 border-t border-[var(--background-light)]",
+                        p { class: "w-32", "{instances::ago_label(session.ended_at)}" }
+                        p { class: "w-20", "{instances::duration_label(session.duration_secs())}" }
+                        match session.end {
+                            SessionEnd::Exited => rsx! {
+                                p { class: "text-[var(--grey)]", "Exited" }
+                            },
+                            SessionEnd::Stopped => rsx! {
+                                p { class: "text-[var(--grey)]", "Stopped" }
+                            },
+                            SessionEnd::Crashed => rsx! {
+                                p { class: "text-[var(--red)]",
+                                    "Crashed"
+                                    if let Some(code) = session.exit_code {
+                                        " (exit code {code})"
+                                    }
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
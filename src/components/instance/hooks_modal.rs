@@ -0,0 +1,83 @@
+use dioxus::prelude::*;
+
+use crate::scripts::instances::{self, Instance};
+
+/// Commands run before launch and after exit, with the variables they get
+#[component]
+pub fn HooksModal(show_modal: Signal<bool>, instance: Instance, on_saved: EventHandler<()>) -> Element {
+    let mut pre_launch = use_signal(|| instance.hooks.pre_launch.clone().unwrap_or_default());
+    let mut post_exit = use_signal(|| instance.hooks.post_exit.clone().unwrap_or_default());
+    let mut status = use_signal(|| None::<String>);
+
+    let input_class = "bg-[var(--background-dark)] p-2 rounded w-full font-mono text-sm";
+
+    rsx! {
+        // Background
+        div {
+            class: "absolute inset-0 z-40 bg-[var(--background-darker)]/50 flex items-center justify-center",
+            onclick: move |_| show_modal.set(false),
+            // Modal
+            div {
+                class: "bg-[var(--background)] p-5 rounded-lg w-1/2 flex flex-col gap-2",
+                onclick: move |e| e.stop_propagation(),
+                h2 { "Hooks" }
+                p { class: "text-[var(--grey)] text-sm",
+                    "Run through the system shell in the game directory with INST_ID, INST_NAME, INST_DIR, INST_MC_DIR, INST_MC_VER, INST_LOADER, INST_LOADER_VER and INST_JAVA set."
+                }
+                label { class: "text-[var(--grey)]", "Pre-launch (a non-zero exit cancels the launch)" }
+                input {
+                    class: input_class,
+                    placeholder: "e.g. git -C config pull",
+                    value: "{pre_launch}",
+                    oninput: move |e| pre_launch.set(e.value()),
+                }
+                label { class: "text-[var(--grey)]", "Post-exit (EXIT_CODE holds the game's exit code)" }
+                input {
+                    class: input_class,
+                    placeholder: "e.g. rsync -a saves/ /mnt/nas/backups/$INST_ID/",
+                    value: "{post_exit}",
+                    oninput: move |e| post_exit.set(e.value()),
+                }
+                div { class: "flex gap-2 justify-end items-center",
+                    if let Some(msg) = status() {
+                        p { class: "text-[var(--red)]", "{msg}" }
+                    }
+                    button {
+                        class: "bg-[var(--background-dark)] p-2 px-3 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                        onclick: move |_| show_modal.set(false),
+                        "Cancel"
+                    }
+                    button {
+                        class: "bg-[var(--green)] text-[var(--background-darker)] p-2 px-3 rounded hover:bg-[var(--green-dark)] cursor-pointer",
+                        onclick: {
+                            let instance = instance.clone();
+                            move |_| {
+                                let id = instance.id.clone();
+                                async move {
+                                    let command = |value: String| {
+                                        (!value.trim().is_empty()).then(|| value.trim().to_string())
+                                    };
+                                    let pre_launch = command(pre_launch());
+                                    let post_exit = command(post_exit());
+                                    let saved = instances::update_instance(&id, |updated| {
+                                        updated.hooks.pre_launch = pre_launch;
+                                        updated.hooks.post_exit = post_exit;
+                                    })
+                                    .await;
+                                    match saved {
+                                        Ok(_) => {
+                                            on_saved.call(());
+                                            show_modal.set(false);
+                                        }
+                                        Err(e) => status.set(Some(format!("Failed to save: {}", e))),
+                                    }
+                                }
+                            }
+                        },
+                        "Save"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+use crate::components::settings::java::{EnvEditor, JvmArgsEditor, MemorySlider};
+use crate::scripts::game::java::split_command;
+use crate::scripts::instances::{self, Instance, JavaSettings};
+use crate::scripts::settings::{self, JavaDefaults};
+
+/// Java overrides of one instance, unset values fall back to the launcher settings
+#[component]
+pub fn JavaSettingsModal(show_modal: Signal<bool>, instance: Instance, on_saved: EventHandler<()>) -> Element {
+    let mut draft = use_signal(|| instance.java.clone());
+    let mut status = use_signal(|| None::<String>);
+    // Bumped to remount the text editors when the draft is replaced
+    let mut revision = use_signal(|| 0);
+
+    let defaults = use_resource(|| async move {
+        settings::load_settings()
+            .await
+            .map(|s| s.java)
+            .unwrap_or_else(|e| {
+                log::error!("Failed to load settings: {}", e);
+                JavaDefaults::default()
+            })
+    });
+    let defaults = defaults().unwrap_or_default();
+
+    let input_class = "bg-[var(--background-dark)] p-2 rounded w-full";
+    let path = draft().path.map(|p| p.display().to_string()).unwrap_or_default();
+    let default_path = defaults
+        .path
+        .as_ref()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "bundled Java 21".to_string());
+    let wrapper = draft().wrapper;
+    let wrapper_error = wrapper.as_deref().and_then(|w| split_command(w).err()).map(|e| e.to_string());
+    let default_wrapper = defaults.wrapper.clone().unwrap_or_else(|| "none".to_string());
+    let (default_min, default_max) = (defaults.min_memory_mb, defaults.max_memory_mb);
+    let default_args = defaults.jvm_args.len();
+
+    rsx! {
+        // Background
+        div {
+            class: "absolute inset-0 z-40 bg-[var(--background-darker)]/50 flex items-center justify-center",
+            onclick: move |_| show_modal.set(false),
+            // Modal
+            div {
+                class: "bg-[var(--background)] p-5 rounded-lg w-1/2 max-h-[90%] overflow-y-auto flex flex-col gap-2",
+                onclick: move |e| e.stop_propagation(),
+                h2 { "Java settings" }
+
+                label { class: "text-[var(--grey)]", "Java executable (empty for {default_path})" }
+                input {
+                    class: input_class,
+                    value: "{path}",
+                    oninput: move |e| {
+                        let value = e.value();
+                        draft.write().path = (!value.trim().is_empty()).then(|| PathBuf::from(value.trim()));
+                    },
+                }
+
+                label { class: "flex items-center gap-2 cursor-pointer",
+                    input {
+                        r#type: "checkbox",
+                        checked: draft().max_memory_mb.is_some(),
+                        onchange: move |e| {
+                            let mut java = draft.write();
+                            if e.checked() {
+                                java.min_memory_mb = Some(default_min);
+                                java.max_memory_mb = Some(default_max);
+                            } else {
+                                java.min_memory_mb = None;
+                                java.max_memory_mb = None;
+                            }
+                        },
+                    }
+                    "Override memory"
+                }
+                match (draft().min_memory_mb, draft().max_memory_mb) {
+                    (Some(min), Some(max)) => rsx! {
+                        MemorySlider {
+                            label: "Minimum memory",
+                            value: min,
+                            on_change: move |mb| draft.write().min_memory_mb = Some(mb),
+                        }
+                        MemorySlider {
+                            label: "Maximum memory",
+                            value: max,
+                            on_change: move |mb| draft.write().max_memory_mb = Some(mb),
+                        }
+                    },
+                    _ => rsx! {
+                        p { class: "text-[var(--grey)]",
+                            "Using {default_min} - {default_max} MB from the launcher settings"
+                        }
+                    },
+                }
+
+                if default_args > 0 {
+                    p { class: "text-[var(--grey)] text-sm",
+                        "Added after the launcher's {default_args} arguments"
+                    }
+                }
+                JvmArgsEditor {
+                    key: "{revision}",
+                    args: draft().jvm_args,
+                    on_change: move |args| draft.write().jvm_args = args,
+                }
+                EnvEditor {
+                    key: "{revision}",
+                    env: draft().env,
+                    on_change: move |env| draft.write().env = env,
+                }
+
+                label { class: "flex items-center gap-2 cursor-pointer",
+                    input {
+                        r#type: "checkbox",
+                        checked: wrapper.is_some(),
+                        onchange: move |e| {
+                            draft.write().wrapper = e.checked().then(String::new);
+                        },
+                    }
+                    "Override wrapper command (launcher: {default_wrapper})"
+                }
+                if let Some(wrapper) = wrapper {
+                    input {
+                        class: input_class,
+                        placeholder: "Empty to run without a wrapper",
+                        value: "{wrapper}",
+                        oninput: move |e| draft.write().wrapper = Some(e.value()),
+                    }
+                }
+                if let Some(error) = wrapper_error {
+                    p { class: "text-[var(--red)] text-sm", "{error}, quote paths with spaces like '/path/to/my wrapper'" }
+                }
+
+                div { class: "flex gap-2 justify-end items-center",
+                    if let Some(msg) = status() {
+                        p { class: "text-[var(--red)]", "{msg}" }
+                    }
+                    button {
+                        class: "bg-[var(--background-dark)] p-2 px-3 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                        onclick: move |_| {
+                            draft.set(JavaSettings::default());
+                            revision += 1;
+                        },
+                        "Use launcher settings"
+                    }
+                    button {
+                        class: "bg-[var(--green)] text-[var(--background-darker)] p-2 px-3 rounded hover:bg-[var(--green-dark)] cursor-pointer",
+                        onclick: {
+                            let instance = instance.clone();
+                            move |_| {
+                                let id = instance.id.clone();
+                                async move {
+                                    let java = draft();
+                                    match instances::update_instance(&id, |updated| updated.java = java).await {
+                                        Ok(_) => {
+                                            on_saved.call(());
+                                            show_modal.set(false);
+                                        }
+                                        Err(e) => status.set(Some(format!("Failed to save: {}", e))),
+                                    }
+                                }
+                            }
+                        },
+                        "Save"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use dioxus::prelude::*;
+
+use crate::scripts::game::launch_script;
+use crate::scripts::instances::Instance;
+use crate::state::AUTH;
+
+/// Install the instance and write its launch command out instead of starting it
+#[component]
+pub fn LaunchScriptModal(show_modal: Signal<bool>, instance: Instance) -> Element {
+    let mut redact_token = use_signal(|| true);
+    let mut busy = use_signal(|| false);
+    let mut status = use_signal(|| None::<Result<String, String>>);
+
+    rsx! {
+        // Background
+        div {
+            class: "absolute inset-0 z-40 bg-[var(--background-darker)]/50 flex items-center justify-center",
+            onclick: move |_| {
+                if !busy() {
+                    show_modal.set(false);
+                }
+            },
+            // Modal
+            div {
+                class: "bg-[var(--background)] p-5 rounded-lg w-1/3 flex flex-col gap-3",
+                onclick: move |e| e.stop_propagation(),
+                h2 { "Launch script" }
+                p { class: "text-[var(--grey)]",
+                    "Installs everything a launch needs, then writes the launch command as a script and as launch.json without starting the game."
+                }
+                label { class: "flex items-center gap-2 cursor-pointer",
+                    input {
+                        r#type: "checkbox",
+                        checked: redact_token(),
+                        onchange: move |e| redact_token.set(e.checked()),
+                    }
+                    "Redact the access token (the script reads ACCESS_TOKEN instead)"
+                }
+                match status() {
+                    Some(Ok(msg)) => rsx! {
+                        p { class: "text-[var(--green)] break-all", "{msg}" }
+                    },
+                    Some(Err(msg)) => rsx! {
+                        p { class: "text-[var(--red)]", "{msg}" }
+                    },
+                    None => rsx! {},
+                }
+                div { class: "flex gap-2 justify-end",
+                    button {
+                        class: "bg-[var(--background-dark)] p-2 px-3 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                        disabled: busy(),
+                        onclick: move |_| show_modal.set(false),
+                        "Close"
+                    }
+                    button {
+                        class: "bg-[var(--green)] text-[var(--background-darker)] p-2 px-3 rounded hover:bg-[var(--green-dark)] cursor-pointer",
+                        disabled: busy(),
+                        onclick: {
+                            let instance = instance.clone();
+                            move |_| {
+                                let instance = instance.clone();
+                                async move {
+                                    let auth = AUTH();
+                                    let Some(user) = auth.user else {
+                                        status.set(Some(Err("Log in first, the launch command contains the account".to_string())));
+                                        return;
+                                    };
+
+                                    busy.set(true);
+                                    status.set(None);
+                                    let result = launch_script::export_launch_script(
+                                        &instance,
+                                        user,
+                                        auth.session,
+                                        redact_token(),
+                                    )
+                                    .await;
+                                    match result {
+                                        Ok(dir) => status.set(Some(Ok(format!("Saved to {}", dir.display())))),
+                                        Err(e) => status.set(Some(Err(e.to_string()))),
+                                    }
+                                    busy.set(false);
+                                }
+                            }
+                        },
+                        if busy() { "Exporting..." } else { "Export" }
+                    }
+                }
+            }
+        }
+    }
+}
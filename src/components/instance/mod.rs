@@ -0,0 +1,10 @@
+pub mod crash_panel;
+pub mod export_modal;
+pub mod history_panel;
+pub mod hooks_modal;
+pub mod java_modal;
+pub mod launch_script_modal;
+pub mod mod_problems_panel;
+pub mod page;
+pub mod quick_play_modal;
+pub mod script;
\ No newline at end of file
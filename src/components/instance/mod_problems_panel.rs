@@ -0,0 +1,59 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::{
+    Icon,
+    icons::ld_icons::{LdPlay, LdPuzzle, LdX},
+};
+
+use crate::components::instance::script::{BlockedLaunch, play_anyway};
+use crate::routes::Route;
+use crate::state::BLOCKED_LAUNCHES;
+
+/// Mod problems that stopped a launch, with the choice to launch anyway
+#[component]
+pub fn ModProblemsPanel(blocked: BlockedLaunch) -> Element {
+    let button_class = "flex items-center gap-1 bg-[var(--background-dark)] p-1 px-2 rounded hover:bg-[var(--background-light)] cursor-pointer";
+    let instance_id = blocked.instance.id.clone();
+    let count = blocked.problems.len();
+
+    rsx! {
+        div { class: "flex flex-col gap-3 p-3 rounded-lg bg-[var(--background-darker)] border border-[var(--yellow)]",
+            div { class: "flex items-center gap-2",
+                h3 { class: "flex-1 text-[var(--yellow)]",
+                    "{blocked.instance.name} wasn't started: {count} mod problems"
+                }
+                button {
+                    class: button_class,
+                    title: "The game will likely crash while loading",
+                    onclick: {
+                        let blocked = blocked.clone();
+                        move |_| {
+                            spawn_forever(play_anyway(blocked.clone()));
+                        }
+                    },
+                    Icon { icon: LdPlay, class: "w-4 h-4" }
+                    "Launch anyway"
+                }
+                Link {
+                    class: button_class,
+                    to: Route::InstanceMods {
+                        id: instance_id.clone(),
+                    },
+                    Icon { icon: LdPuzzle, class: "w-4 h-4" }
+                    "Mods"
+                }
+                button {
+                    class: button_class,
+                    onclick: move |_| {
+                        BLOCKED_LAUNCHES.write().remove(&instance_id);
+                    },
+                    Icon { icon: LdX, class: "w-4 h-4" }
+                }
+            }
+            ul { class: "list-disc pl-5",
+                for problem in blocked.problems.iter() {
+                    li { "{problem}" }
+                }
+            }
+        }
+    }
+}
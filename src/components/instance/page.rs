@@ -0,0 +1,362 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::{
+    Icon,
+    icons::ld_icons::{
+        LdArrowLeft, LdCoffee, LdCopy, LdFileCode, LdImage, LdPackage, LdPencil, LdPlay, LdPuzzle,
+        LdSkull, LdSquare, LdTerminal, LdTrash2, LdWorkflow, LdZap,
+    },
+};
+
+use crate::components::instance::crash_panel::CrashPanel;
+use crate::components::instance::export_modal::ExportModal;
+use crate::components::instance::history_panel::HistoryPanel;
+use crate::components::instance::hooks_modal::HooksModal;
+use crate::components::instance::java_modal::JavaSettingsModal;
+use crate::components::instance::launch_script_modal::LaunchScriptModal;
+use crate::components::instance::mod_problems_panel::ModProblemsPanel;
+use crate::components::instance::quick_play_modal::QuickPlayModal;
+use crate::components::instance::script::{play, play_another};
+use crate::routes::Route;
+use crate::scripts::instances::{self, Instance};
+use crate::scripts::process::{self, GameState, StopRequest};
+use crate::state::{BLOCKED_LAUNCHES, CRASHES, GAMES};
+
+#[component]
+pub fn InstanceDetails(id: ReadOnlySignal<String>) -> Element {
+    // Reload once a run ends, it adds to the playtime
+    let game_state = use_memo(move || process::latest_run(&GAMES.read(), &id()).map(|g| g.state));
+    let mut instance = use_resource(move || async move {
+        game_state.read();
+        instances::load_instance(&id()).await.map_err(|e| e.to_string())
+    });
+
+    rsx! {
+        div { class: "flex-1 flex flex-col gap-4 p-6",
+            Link {
+                class: "flex items-center gap-1 text-[var(--grey)] hover:text-[var(--foreground)]",
+                to: Route::Library {},
+                Icon { icon: LdArrowLeft, class: "w-4 h-4" }
+                "Library"
+            }
+            match instance() {
+                Some(Ok(current)) => rsx! {
+                    InstanceHeader { instance: current, on_change: move |_| instance.restart() }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-[var(--red)]", "Failed to load instance: {e}" }
+                },
+                None => rsx! {
+                    p { class: "text-[var(--grey)]", "Loading..." }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn InstanceHeader(instance: Instance, on_change: EventHandler<()>) -> Element {
+    let navigator = use_navigator();
+    let mut renaming = use_signal(|| None::<String>);
+    let mut confirm_delete = use_signal(|| false);
+    let mut show_export = use_signal(|| false);
+    let mut show_java = use_signal(|| false);
+    let mut show_hooks = use_signal(|| false);
+    let mut show_launch_script = use_signal(|| false);
+    let mut show_quick_play = use_signal(|| false);
+    let game = process::latest_run(&GAMES.read(), &instance.id);
+    let crash = CRASHES.read().get(&instance.id).cloned();
+    let blocked = BLOCKED_LAUNCHES.read().get(&instance.id).cloned();
+    let running_count = GAMES
+        .read()
+        .values()
+        .filter(|g| g.instance_id == instance.id && g.state.is_active())
+        .count();
+    let running = running_count > 0;
+
+    let button_class = "flex items-center gap-1 bg-[var(--background-dark)] p-2 px-3 rounded hover:bg-[var(--background-light)] cursor-pointer";
+
+    rsx! {
+        if show_export() {
+            ExportModal { show_modal: show_export, instance: instance.clone() }
+        }
+        if show_java() {
+            JavaSettingsModal {
+                show_modal: show_java,
+                instance: instance.clone(),
+                on_saved: move |_| on_change.call(()),
+            }
+        }
+        if show_launch_script() {
+            LaunchScriptModal { show_modal: show_launch_script, instance: instance.clone() }
+        }
+        if show_quick_play() {
+            QuickPlayModal { show_modal: show_quick_play, instance: instance.clone() }
+        }
+        if show_hooks() {
+            HooksModal {
+                show_modal: show_hooks,
+                instance: instance.clone(),
+                on_saved: move |_| on_change.call(()),
+            }
+        }
+        if confirm_delete() {
+            // Background
+            div {
+                class: "absolute inset-0 z-40 bg-[var(--background-darker)]/50 flex items-center justify-center",
+                onclick: move |_| confirm_delete.set(false),
+                // Modal
+                div {
+                    class: "bg-[var(--background)] p-5 rounded-lg w-1/3 flex flex-col gap-3",
+                    onclick: move |e| e.stop_propagation(),
+                    h3 { "Delete {instance.name}?" }
+                    p { "This removes the instance and its game directory, including worlds and mods. This can't be undone." }
+                    div { class: "flex gap-2 justify-end",
+                        button {
+                            class: button_class,
+                            onclick: move |_| confirm_delete.set(false),
+                            "Cancel"
+                        }
+                        button {
+                            class: "bg-[var(--red)] text-[var(--background-darker)] p-2 px-3 rounded hover:bg-[var(--red-dark)] cursor-pointer",
+                            onclick: {
+                                let instance = instance.clone();
+                                move |_| {
+                                    let instance = instance.clone();
+                                    async move {
+                                        match instances::delete_instance(&instance).await {
+                                            Ok(_) => {
+                                                navigator.push(Route::Library {});
+                                            }
+                                            Err(e) => log::error!("Failed to delete instance: {}", e),
+                                        }
+                                    }
+                                }
+                            },
+                            "Delete"
+                        }
+                    }
+                }
+            }
+        }
+
+        div { class: "flex items-center gap-3",
+            if let Some(new_name) = renaming() {
+                input {
+                    class: "bg-[var(--background-dark)] p-2 rounded text-xl font-bold",
+                    value: "{new_name}",
+                    autofocus: true,
+                    oninput: move |e| renaming.set(Some(e.value())),
+                    onkeydown: {
+                        let instance = instance.clone();
+                        move |e: KeyboardEvent| {
+                            let instance = instance.clone();
+                            async move {
+                                match e.key() {
+                                    Key::Enter => {
+                                        let new_name = renaming().unwrap_or_default();
+                                        if !new_name.trim().is_empty() {
+                                            if let Err(e) = instances::rename_instance(&instance, &new_name).await {
+                                                log::error!("Failed to rename instance: {}", e);
+                                            }
+                                            on_change.call(());
+                                        }
+                                        renaming.set(None);
+                                    }
+                                    Key::Escape => renaming.set(None),
+                                    _ => {}
+                                }
+                            }
+                        }
+                    },
+                }
+            } else {
+                h2 { "{instance.name}" }
+            }
+            p { class: "text-[var(--grey)]", "{instance.version_label()}" }
+        }
+
+        div { class: "flex gap-6 text-[var(--grey)]",
+            p { "Last played: {instance.last_played_label()}" }
+            p { "Playtime: {instance.playtime_label()}" }
+            if let Some(game) = game {
+                match game.state {
+                    GameState::Starting => rsx! {
+                        p { class: "text-[var(--yellow)]", "Starting..." }
+                    },
+                    GameState::Running => rsx! {
+                        p { class: "text-[var(--green)]",
+                            "Running"
+                            if let Some(pid) = game.pid {
+                                " (PID {pid})"
+                            }
+                        }
+                    },
+                    GameState::Exited(code) => rsx! {
+                        p {
+                            "Exited"
+                            if let Some(code) = code {
+                                " with code {code}"
+                            }
+                        }
+                    },
+                    GameState::Crashed(code) => rsx! {
+                        p { class: "text-[var(--red)]",
+                            "Crashed"
+                            if let Some(code) = code {
+                                " with code {code}"
+                            }
+                        }
+                    },
+                }
+            }
+            if running_count > 1 {
+                p { class: "text-[var(--green)]", "{running_count} clients running" }
+            }
+        }
+
+        div { class: "flex gap-2",
+            if running {
+                button {
+                    class: "flex items-center gap-1 bg-[var(--red)] text-[var(--background-darker)] p-2 px-4 rounded hover:bg-[var(--red-dark)] cursor-pointer",
+                    onclick: {
+                        let id = instance.id.clone();
+                        move |_| process::request_stop_instance(&id, StopRequest::Stop)
+                    },
+                    Icon { icon: LdSquare, class: "w-4 h-4" }
+                    "Stop"
+                }
+                button {
+                    class: button_class,
+                    onclick: {
+                        let id = instance.id.clone();
+                        move |_| process::request_stop_instance(&id, StopRequest::Kill)
+                    },
+                    Icon { icon: LdSkull, class: "w-4 h-4" }
+                    "Kill"
+                }
+                button {
+                    class: button_class,
+                    title: "Start another client of this instance, e.g. to test multiplayer",
+                    onclick: {
+                        let instance = instance.clone();
+                        move |_| {
+                            spawn_forever(play_another(instance.clone()));
+                        }
+                    },
+                    Icon { icon: LdPlay, class: "w-4 h-4" }
+                    "Run another"
+                }
+            } else {
+                button {
+                    class: "flex items-center gap-1 bg-[var(--green)] text-[var(--background-darker)] p-2 px-4 rounded hover:bg-[var(--green-dark)] cursor-pointer",
+                    onclick: {
+                        let instance = instance.clone();
+                        move |_| {
+                            spawn_forever(play(instance.clone(), None));
+                        }
+                    },
+                    Icon { icon: LdPlay, class: "w-4 h-4" }
+                    "Play"
+                }
+                button {
+                    class: button_class,
+                    onclick: move |_| show_quick_play.set(true),
+                    Icon { icon: LdZap, class: "w-4 h-4" }
+                    "Quick Play"
+                }
+            }
+            Link {
+                class: button_class,
+                to: Route::InstanceMods {
+                    id: instance.id.clone(),
+                },
+                Icon { icon: LdPuzzle, class: "w-4 h-4" }
+                "Mods"
+            }
+            Link {
+                class: button_class,
+                to: Route::InstanceConsole {
+                    id: instance.id.clone(),
+                },
+                Icon { icon: LdTerminal, class: "w-4 h-4" }
+                "Console"
+            }
+            Link {
+                class: button_class,
+                to: Route::InstancePacks {
+                    id: instance.id.clone(),
+                },
+                Icon { icon: LdImage, class: "w-4 h-4" }
+                "Packs"
+            }
+            button {
+                class: button_class,
+                onclick: move |_| show_java.set(true),
+                Icon { icon: LdCoffee, class: "w-4 h-4" }
+                "Java"
+            }
+            button {
+                class: button_class,
+                onclick: move |_| show_hooks.set(true),
+                Icon { icon: LdWorkflow, class: "w-4 h-4" }
+                "Hooks"
+            }
+            button {
+                class: button_class,
+                onclick: move |_| show_launch_script.set(true),
+                Icon { icon: LdFileCode, class: "w-4 h-4" }
+                "Launch script"
+            }
+            button {
+                class: button_class,
+                onclick: {
+                    let name = instance.name.clone();
+                    move |_| renaming.set(Some(name.clone()))
+                },
+                Icon { icon: LdPencil, class: "w-4 h-4" }
+                "Rename"
+            }
+            button {
+                class: button_class,
+                onclick: {
+                    let instance = instance.clone();
+                    move |_| {
+                        let instance = instance.clone();
+                        async move {
+                            let copy_name = format!("{} (copy)", instance.name);
+                            match instances::duplicate_instance(&instance, &copy_name).await {
+                                Ok(copy) => {
+                                    navigator.push(Route::InstanceDetails { id: copy.id });
+                                }
+                                Err(e) => log::error!("Failed to duplicate instance: {}", e),
+                            }
+                        }
+                    }
+                },
+                Icon { icon: LdCopy, class: "w-4 h-4" }
+                "Duplicate"
+            }
+            button {
+                class: button_class,
+                onclick: move |_| show_export.set(true),
+                Icon { icon: LdPackage, class: "w-4 h-4" }
+                "Export"
+            }
+            button {
+                class: button_class,
+                onclick: move |_| confirm_delete.set(true),
+                Icon { icon: LdTrash2, class: "w-4 h-4" }
+                "Delete"
+            }
+        }
+
+        if let Some(blocked) = blocked {
+            ModProblemsPanel { blocked }
+        }
+        if let Some(analysis) = crash {
+            CrashPanel { instance_id: instance.id.clone(), analysis }
+        }
+
+        HistoryPanel { instance: instance.clone() }
+    }
+}
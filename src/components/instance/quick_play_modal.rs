@@ -0,0 +1,219 @@
+use dioxus::prelude::*;
+
+use crate::components::instance::script::play;
+use crate::scripts::game::QuickPlay;
+use crate::scripts::instances::Instance;
+use crate::scripts::quickplay::{self, shortcut};
+
+/// Start the instance straight into a saved server or world, or make a desktop shortcut that does
+#[component]
+pub fn QuickPlayModal(show_modal: Signal<bool>, instance: Instance) -> Element {
+    let mut address = use_signal(String::new);
+    let mut realm = use_signal(String::new);
+    let mut status = use_signal(|| None::<Result<String, String>>);
+
+    let servers = use_resource({
+        let instance = instance.clone();
+        move || {
+            let instance = instance.clone();
+            async move { quickplay::list_servers(&instance).await.map_err(|e| e.to_string()) }
+        }
+    });
+    let worlds = use_resource({
+        let instance = instance.clone();
+        move || {
+            let instance = instance.clone();
+            async move { quickplay::list_worlds(&instance).await.map_err(|e| e.to_string()) }
+        }
+    });
+
+    let start = {
+        let instance = instance.clone();
+        move |target: QuickPlay| {
+            spawn_forever(play(instance.clone(), Some(target)));
+            show_modal.set(false);
+        }
+    };
+    let create_shortcut = {
+        let instance = instance.clone();
+        move |target: Option<QuickPlay>| {
+            let instance = instance.clone();
+            spawn(async move {
+                match shortcut::create_shortcut(&instance, target).await {
+                    Ok(path) => status.set(Some(Ok(format!("Shortcut saved to {}", path.display())))),
+                    Err(e) => status.set(Some(Err(format!("Failed to create shortcut: {}", e)))),
+                }
+            });
+        }
+    };
+
+    let button_class = "bg-[var(--background-dark)] p-1 px-2 rounded hover:bg-[var(--background-light)] cursor-pointer text-sm";
+    let play_class = "bg-[var(--green)] text-[var(--background-darker)] p-1 px-2 rounded hover:bg-[var(--green-dark)] cursor-pointer text-sm";
+
+    rsx! {
+        // Background
+        div {
+            class: "absolute inset-0 z-40 bg-[var(--background-darker)]/50 flex items-center justify-center",
+            onclick: move |_| show_modal.set(false),
+            // Modal
+            div {
+                class: "bg-[var(--background)] p-5 rounded-lg w-1/2 max-h-[90%] overflow-y-auto flex flex-col gap-3",
+                onclick: move |e| e.stop_propagation(),
+                div { class: "flex items-center gap-2",
+                    h2 { class: "flex-1", "Quick Play" }
+                    button {
+                        class: button_class,
+                        onclick: {
+                            let create_shortcut = create_shortcut.clone();
+                            move |_| create_shortcut(None)
+                        },
+                        "Desktop shortcut"
+                    }
+                }
+                p { class: "text-[var(--grey)]",
+                    "Skips the title screen. Minecraft before 1.20 can only join servers this way."
+                }
+
+                h3 { "Servers" }
+                div { class: "flex gap-2",
+                    input {
+                        class: "flex-1 bg-[var(--background-dark)] p-2 rounded",
+                        placeholder: "host or host:port",
+                        value: "{address}",
+                        oninput: move |e| address.set(e.value()),
+                    }
+                    button {
+                        class: play_class,
+                        disabled: address().trim().is_empty(),
+                        onclick: {
+                            let mut start = start.clone();
+                            move |_| start(QuickPlay::Multiplayer(address().trim().to_string()))
+                        },
+                        "Join"
+                    }
+                }
+                match servers() {
+                    Some(Ok(servers)) if servers.is_empty() => rsx! {
+                        p { class: "text-[var(--grey)]", "No servers in the multiplayer list yet." }
+                    },
+                    Some(Ok(servers)) => rsx! {
+                        for server in servers {
+                            div { key: "{server.address}", class: "flex items-center gap-2",
+                                div { class: "flex-1 flex flex-col",
+                                    p { "{server.name}" }
+                                    p { class: "text-[var(--grey)] text-sm", "{server.address}" }
+                                }
+                                button {
+                                    class: button_class,
+                                    onclick: {
+                                        let create_shortcut = create_shortcut.clone();
+                                        let address = server.address.clone();
+                                        move |_| create_shortcut(Some(QuickPlay::Multiplayer(address.clone())))
+                                    },
+                                    "Shortcut"
+                                }
+                                button {
+                                    class: play_class,
+                                    onclick: {
+                                        let mut start = start.clone();
+                                        let address = server.address.clone();
+                                        move |_| start(QuickPlay::Multiplayer(address.clone()))
+                                    },
+                                    "Join"
+                                }
+                            }
+                        }
+                    },
+                    Some(Err(e)) => rsx! {
+                        p { class: "text-[var(--red)]", "Failed to read the server list: {e}" }
+                    },
+                    None => rsx! {
+                        p { class: "text-[var(--grey)]", "Loading..." }
+                    },
+                }
+
+                h3 { "Worlds" }
+                match worlds() {
+                    Some(Ok(worlds)) if worlds.is_empty() => rsx! {
+                        p { class: "text-[var(--grey)]", "No worlds yet." }
+                    },
+                    Some(Ok(worlds)) => rsx! {
+                        for world in worlds {
+                            div { key: "{world.folder}", class: "flex items-center gap-2",
+                                div { class: "flex-1 flex flex-col",
+                                    p { "{world.name}" }
+                                    p { class: "text-[var(--grey)] text-sm", "{world.folder}" }
+                                }
+                                button {
+                                    class: button_class,
+                                    onclick: {
+                                        let create_shortcut = create_shortcut.clone();
+                                        let folder = world.folder.clone();
+                                        move |_| create_shortcut(Some(QuickPlay::Singleplayer(folder.clone())))
+                                    },
+                                    "Shortcut"
+                                }
+                                button {
+                                    class: play_class,
+                                    onclick: {
+                                        let mut start = start.clone();
+                                        let folder = world.folder.clone();
+                                        move |_| start(QuickPlay::Singleplayer(folder.clone()))
+                                    },
+                                    "Play"
+                                }
+                            }
+                        }
+                    },
+                    Some(Err(e)) => rsx! {
+                        p { class: "text-[var(--red)]", "Failed to read the worlds: {e}" }
+                    },
+                    None => rsx! {
+                        p { class: "text-[var(--grey)]", "Loading..." }
+                    },
+                }
+
+                h3 { "Realms" }
+                p { class: "text-[var(--grey)] text-sm",
+                    "Realms only let in Mojang accounts, an Ely.by account can't join them."
+                }
+                div { class: "flex gap-2",
+                    input {
+                        class: "flex-1 bg-[var(--background-dark)] p-2 rounded",
+                        placeholder: "Realm id",
+                        value: "{realm}",
+                        oninput: move |e| realm.set(e.value()),
+                    }
+                    button {
+                        class: button_class,
+                        disabled: realm().trim().is_empty(),
+                        onclick: {
+                            let create_shortcut = create_shortcut.clone();
+                            move |_| create_shortcut(Some(QuickPlay::Realms(realm().trim().to_string())))
+                        },
+                        "Shortcut"
+                    }
+                    button {
+                        class: play_class,
+                        disabled: realm().trim().is_empty(),
+                        onclick: {
+                            let mut start = start.clone();
+                            move |_| start(QuickPlay::Realms(realm().trim().to_string()))
+                        },
+                        "Join"
+                    }
+                }
+
+                match status() {
+                    Some(Ok(msg)) => rsx! {
+                        p { class: "text-[var(--green)] break-all", "{msg}" }
+                    },
+                    Some(Err(msg)) => rsx! {
+                        p { class: "text-[var(--red)]", "{msg}" }
+                    },
+                    None => rsx! {},
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::components::login::{script, yggdrasil};
+use crate::scripts::game::{ModProblems, QuickPlay};
+use crate::scripts::instances::Instance;
+use crate::scripts::process;
+use crate::state::{AUTH, BLOCKED_LAUNCHES, LAUNCH_AFTER_LOGIN};
+
+/// A launch the mod check stopped, kept so the user can launch anyway
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockedLaunch {
+    pub instance: Instance,
+    pub problems: Vec<String>,
+    pub quick_play: Option<QuickPlay>,
+}
+
+/// Refresh the session if needed, then install, launch and supervise `instance`.
+/// Does nothing if the instance is already running, so a double click doesn't start it twice.
+pub async fn play(instance: Instance, quick_play: Option<QuickPlay>) {
+    if process::is_active(&instance.id) {
+        log::warn!("{} is already running", instance.name);
+        return;
+    }
+    run(instance, quick_play, false).await;
+}
+
+/// Start one more run of `instance`, next to the ones already going (e.g. a second client for testing)
+pub async fn play_another(instance: Instance) {
+    run(instance, None, false).await;
+}
+
+/// Retry a launch the mod check stopped, skipping the check
+pub async fn play_anyway(blocked: BlockedLaunch) {
+    BLOCKED_LAUNCHES.write().remove(&blocked.instance.id);
+    run(blocked.instance, blocked.quick_play, true).await;
+}
+
+async fn run(instance: Instance, quick_play: Option<QuickPlay>, ignore_mod_problems: bool) {
+    BLOCKED_LAUNCHES.write().remove(&instance.id);
+    let run = process::mark_starting(&instance);
+
+    // Both kinds of login get a chance to refresh an expired access token
+    if let Some(session) = AUTH().session {
+        match yggdrasil::ensure_valid(&session).await {
+            Ok(session) => {
+                let mut auth = AUTH.write();
+                auth.token = Some(session.access_token.clone());
+                auth.user = Some(session.user_info());
+                auth.session = Some(session);
+            }
+            Err(e) => log::warn!("Failed to refresh session: {}", e),
+        }
+    } else if AUTH().user.is_some() {
+        match script::restore_oauth().await {
+            Ok(Some(user)) => {
+                let mut auth = AUTH.write();
+                auth.token = Some(user.access_token.clone());
+                auth.user = Some(user);
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("Failed to refresh login: {}", e),
+        }
+    }
+
+    let auth = AUTH();
+    let Some(user) = auth.user else {
+        // Shows the login prompt, the launch continues once logged in
+        log::error!("Log in before playing {}", instance.name);
+        process::mark_failed(run);
+        *LAUNCH_AFTER_LOGIN.write() = Some((instance, quick_play));
+        return;
+    };
+
+    let launched = crate::scripts::game::launch(
+        instance.clone(),
+        user,
+        auth.session,
+        quick_play.clone(),
+        ignore_mod_problems,
+    )
+    .await;
+    match launched {
+        Ok(launched) => {
+            log::info!("Game launched successfully");
+            process::supervise(&instance, run, launched).await;
+        }
+        Err(e) if e.is::<ModProblems>() => {
+            log::error!("Game launch stopped: {}", e);
+            process::mark_failed(run);
+            let problems = e.downcast::<ModProblems>().map(|p| p.0).unwrap_or_default();
+            BLOCKED_LAUNCHES.write().insert(
+                instance.id.clone(),
+                BlockedLaunch {
+                    instance,
+                    problems,
+                    quick_play,
+                },
+            );
+        }
+        Err(e) => {
+            log::error!("Game launch failed: {:?}", e);
+            process::mark_failed(run);
+        }
+    }
+}
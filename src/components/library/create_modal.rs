@@ -0,0 +1,133 @@
+use dioxus::prelude::*;
+
+use crate::scripts::game::{MC_VERSION, NEOFORGE_VERSION, VersionType, install};
+use crate::scripts::instances;
+
+#[component]
+pub fn CreateInstanceModal(show_modal: Signal<bool>, on_created: EventHandler<()>) -> Element {
+    let mut name = use_signal(String::new);
+    let mut game_version = use_signal(|| MC_VERSION.to_string());
+    let mut loader = use_signal(|| VersionType::Vanilla);
+    let mut loader_version = use_signal(|| NEOFORGE_VERSION.to_string());
+    let mut error = use_signal(|| None::<String>);
+    let mut show_snapshots = use_signal(|| false);
+
+    // Only versions the launch command can be built for (1.13 and newer)
+    let versions = use_resource(|| async { install::launchable_versions().await.map_err(|e| e.to_string()) });
+
+    let input_class = "bg-[var(--background-dark)] p-2 rounded w-full";
+
+    rsx! {
+        // Background
+        div {
+            class: "absolute inset-0 z-40 bg-[var(--background-darker)]/50 flex items-center justify-center",
+            onclick: move |_| show_modal.set(false),
+            // Modal
+            div {
+                class: "bg-[var(--background)] p-5 rounded-lg w-1/3 flex flex-col gap-3",
+                onclick: move |e| e.stop_propagation(),
+                h2 { "New Instance" }
+                input {
+                    class: input_class,
+                    placeholder: "Name",
+                    value: "{name}",
+                    oninput: move |e| name.set(e.value()),
+                }
+                match versions() {
+                    Some(Ok(versions)) => rsx! {
+                        select {
+                            class: input_class,
+                            onchange: move |evt| game_version.set(evt.value()),
+                            for version in versions.into_iter().filter(|v| v.kind == "release" || show_snapshots()) {
+                                option {
+                                    key: "{version.id}",
+                                    value: "{version.id}",
+                                    selected: version.id == game_version(),
+                                    "{version.id}"
+                                }
+                            }
+                        }
+                        label { class: "flex items-center gap-2 cursor-pointer",
+                            input {
+                                r#type: "checkbox",
+                                checked: show_snapshots(),
+                                onchange: move |e| {
+                                    show_snapshots.set(e.checked());
+                                    // The picked snapshot would be hidden but still selected
+                                    if !e.checked() {
+                                        game_version.set(MC_VERSION.to_string());
+                                    }
+                                },
+                            }
+                            "Show snapshots"
+                        }
+                    },
+                    Some(Err(e)) => rsx! {
+                        p { class: "text-[var(--red)]", "Failed to load Minecraft versions: {e}" }
+                    },
+                    None => rsx! {
+                        p { class: "text-[var(--grey)]", "Loading versions..." }
+                    },
+                }
+                select {
+                    class: input_class,
+                    onchange: move |evt| {
+                        match evt.value().as_str() {
+                            "NeoForge" => loader.set(VersionType::NeoForge),
+                            "Fabric" => loader.set(VersionType::Fabric),
+                            "Quilt" => loader.set(VersionType::Quilt),
+                            _ => loader.set(VersionType::Vanilla),
+                        }
+                    },
+                    option { value: "Vanilla", "Vanilla" }
+                    option { value: "NeoForge", "NeoForge" }
+                    option { value: "Fabric", "Fabric" }
+                    option { value: "Quilt", "Quilt" }
+                }
+                if loader() != VersionType::Vanilla {
+                    input {
+                        class: input_class,
+                        placeholder: "Loader version",
+                        value: "{loader_version}",
+                        oninput: move |e| loader_version.set(e.value()),
+                    }
+                }
+                if let Some(err) = error() {
+                    p { class: "text-[var(--red)]", "{err}" }
+                }
+                button {
+                    class: "border border-[var(--background-darker)] bg-[var(--background-dark)] p-2 px-4 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                    onclick: move |_| async move {
+                        if name().trim().is_empty() {
+                            error.set(Some("Name can't be empty".to_string()));
+                            return;
+                        }
+                        if !matches!(versions(), Some(Ok(_))) {
+                            error.set(Some("Pick a Minecraft version".to_string()));
+                            return;
+                        }
+                        let loader_version = match loader() {
+                            VersionType::Vanilla => None,
+                            _ => Some(loader_version().trim().to_string()),
+                        };
+                        match instances::create_instance(
+                                name().trim(),
+                                game_version().trim(),
+                                loader(),
+                                loader_version,
+                            )
+                            .await
+                        {
+                            Ok(_) => {
+                                show_modal.set(false);
+                                on_created.call(());
+                            }
+                            Err(e) => error.set(Some(e.to_string())),
+                        }
+                    },
+                    "Create"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+use crate::scripts::game::DATA_DIR;
+use crate::scripts::curseforge::CurseForgeClient;
+use crate::scripts::modpacks::{self, ManualDownload};
+use crate::scripts::modrinth::ModrinthClient;
+
+#[component]
+pub fn ImportModpackModal(show_modal: Signal<bool>, on_imported: EventHandler<()>) -> Element {
+    // Picked .mrpack or CurseForge zip, copied into the downloads folder
+    let mut pack_path = use_signal(|| None::<PathBuf>);
+    let mut version_id = use_signal(String::new);
+    let mut busy = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    // Files CurseForge wouldn't let us download, shown once the import finished
+    let mut manual_downloads = use_signal(Vec::<ManualDownload>::new);
+
+    let input_class = "bg-[var(--background-dark)] p-2 rounded w-full";
+
+    rsx! {
+        // Background
+        div {
+            class: "absolute inset-0 z-40 bg-[var(--background-darker)]/50 flex items-center justify-center",
+            onclick: move |_| {
+                if !busy() {
+                    show_modal.set(false);
+                }
+            },
+            // Modal
+            div {
+                class: "bg-[var(--background)] p-5 rounded-lg w-1/3 flex flex-col gap-3",
+                onclick: move |e| e.stop_propagation(),
+                h2 { "Import Modpack" }
+                if !manual_downloads().is_empty() {
+                    ManualDownloads { downloads: manual_downloads() }
+                    button {
+                        class: "border border-[var(--background-darker)] bg-[var(--background-dark)] p-2 px-4 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                        onclick: move |_| show_modal.set(false),
+                        "Done"
+                    }
+                } else {
+                    p { class: "text-[var(--grey)]", "Pick a Modrinth .mrpack or CurseForge .zip file..." }
+                    input {
+                        class: "w-full",
+                        r#type: "file",
+                        accept: ".mrpack,.zip",
+                        disabled: busy(),
+                        onchange: move |evt| async move {
+                            error.set(None);
+                            let Some(file) = evt.files().into_iter().next() else {
+                                return;
+                            };
+                            let bytes = match file.read_bytes().await {
+                                Ok(bytes) => bytes,
+                                Err(e) => {
+                                    error.set(Some(e.to_string()));
+                                    return;
+                                }
+                            };
+                            let dir = PathBuf::from(DATA_DIR).join("downloads");
+                            let path = dir.join(file.name());
+                            let written = async {
+                                tokio::fs::create_dir_all(&dir).await?;
+                                tokio::fs::write(&path, &bytes).await
+                            };
+                            match written.await {
+                                Ok(()) => pack_path.set(Some(path)),
+                                Err(e) => error.set(Some(e.to_string())),
+                            }
+                        },
+                    }
+                    p { class: "text-[var(--grey)]", "...or enter a Modrinth version ID" }
+                    input {
+                        class: input_class,
+                        placeholder: "Version ID",
+                        value: "{version_id}",
+                        disabled: busy(),
+                        oninput: move |e| version_id.set(e.value()),
+                    }
+                    if let Some(err) = error() {
+                        p { class: "text-[var(--red)]", "{err}" }
+                    }
+                    button {
+                        class: "border border-[var(--background-darker)] bg-[var(--background-dark)] p-2 px-4 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                        disabled: busy(),
+                        onclick: move |_| async move {
+                            error.set(None);
+                            busy.set(true);
+
+                            let result = if let Some(path) = pack_path() {
+                                let result = if path.extension().is_some_and(|e| e == "zip") {
+                                    import_curseforge(&path, manual_downloads).await
+                                } else {
+                                    modpacks::import_mrpack(&path, None).await.map(|_| ())
+                                };
+                                let _ = tokio::fs::remove_file(&path).await;
+                                pack_path.set(None);
+                                result
+                            } else if !version_id().trim().is_empty() {
+                                match ModrinthClient::from_settings().await {
+                                    Ok(client) => {
+                                        modpacks::import_modrinth_version(&client, version_id().trim())
+                                            .await
+                                            .map(|_| ())
+                                    }
+                                    Err(e) => Err(e),
+                                }
+                            } else {
+                                Err(anyhow::anyhow!("Pick a modpack file or enter a version ID"))
+                            };
+
+                            busy.set(false);
+                            match result {
+                                Ok(()) => {
+                                    on_imported.call(());
+                                    if manual_downloads().is_empty() {
+                                        show_modal.set(false);
+                                    }
+                                }
+                                Err(e) => error.set(Some(e.to_string())),
+                            }
+                        },
+                        if busy() { "Installing..." } else { "Import" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn import_curseforge(
+    path: &std::path::Path,
+    mut manual_downloads: Signal<Vec<ManualDownload>>,
+) -> anyhow::Result<()> {
+    let client = CurseForgeClient::from_settings().await?;
+    let import = modpacks::import_curseforge_zip(&client, path).await?;
+    manual_downloads.set(import.manual_downloads);
+    Ok(())
+}
+
+#[component]
+fn ManualDownloads(downloads: Vec<ManualDownload>) -> Element {
+    rsx! {
+        p { "These files can't be downloaded automatically. Download each one from CurseForge and put it in the folder shown:" }
+        div { class: "flex flex-col gap-1 max-h-64 overflow-y-auto",
+            for download in downloads {
+                div { key: "{download.url}", class: "flex items-center gap-2",
+                    div { class: "flex-1 truncate",
+                        p { class: "font-bold truncate", "{download.project_name}" }
+                        p { class: "text-xs truncate", title: "{download.file_name}", "{download.file_name}" }
+                        p {
+                            class: "text-[var(--grey)] text-xs truncate",
+                            title: "{download.target.display()}",
+                            "{download.target.display()}"
+                        }
+                    }
+                    button {
+                        class: "bg-[var(--background-dark)] p-1 px-3 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                        onclick: {
+                            let url = download.url.clone();
+                            move |_| {
+                                if let Err(e) = webbrowser::open(&url) {
+                                    log::error!("Failed to open {}: {}", url, e);
+                                }
+                            }
+                        },
+                        "Open"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod create_modal;
+pub mod import_modal;
+pub mod page;
\ No newline at end of file
@@ -0,0 +1,199 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::{Icon, icons::ld_icons::{LdBox, LdDownload, LdFeather, LdHammer, LdLayers, LdPlay, LdPlus, LdSquare}};
+
+use crate::components::games::panel::RunningGames;
+use crate::components::instance::mod_problems_panel::ModProblemsPanel;
+use crate::components::instance::script::play;
+use crate::components::library::create_modal::CreateInstanceModal;
+use crate::components::library::import_modal::ImportModpackModal;
+use crate::routes::Route;
+use crate::scripts::game::VersionType;
+use crate::scripts::instances::{self, Instance};
+use crate::scripts::process::{self, StopRequest};
+use crate::state::{BLOCKED_LAUNCHES, GAMES};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortBy {
+    Name,
+    LastPlayed,
+    Playtime,
+    Version,
+}
+
+#[component]
+pub fn Library() -> Element {
+    let mut show_create = use_signal(|| false);
+    let mut show_import = use_signal(|| false);
+    let mut filter = use_signal(String::new);
+    let mut sort_by = use_signal(|| SortBy::LastPlayed);
+
+    // Reload when a game stops, its playtime and last played time changed
+    let active_games = use_memo(|| GAMES.read().values().filter(|g| g.state.is_active()).count());
+    let mut instances = use_resource(move || async move {
+        active_games.read();
+        instances::list_instances().await.unwrap_or_else(|e| {
+            log::error!("Failed to load instances: {}", e);
+            Vec::new()
+        })
+    });
+
+    let shown = use_memo(move || {
+        let query = filter().to_lowercase();
+        let mut list: Vec<Instance> = instances()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|i| {
+                query.is_empty()
+                    || i.name.to_lowercase().contains(&query)
+                    || i.version_label().to_lowercase().contains(&query)
+            })
+            .collect();
+
+        match sort_by() {
+            SortBy::Name => list.sort_by_key(|i| i.name.to_lowercase()),
+            SortBy::LastPlayed => list.sort_by(|a, b| b.last_played.cmp(&a.last_played)),
+            SortBy::Playtime => list.sort_by(|a, b| b.playtime_secs.cmp(&a.playtime_secs)),
+            SortBy::Version => list.sort_by(|a, b| {
+                instances::compare_game_versions(&b.game_version, &a.game_version)
+            }),
+        }
+        list
+    });
+
+    rsx! {
+        if show_create() {
+            CreateInstanceModal {
+                show_modal: show_create,
+                on_created: move |_| instances.restart(),
+            }
+        }
+        if show_import() {
+            ImportModpackModal {
+                show_modal: show_import,
+                on_imported: move |_| instances.restart(),
+            }
+        }
+
+        div { class: "flex-1 flex flex-col gap-4 p-6",
+            div { class: "flex items-center gap-2",
+                h2 { class: "flex-1", "Library" }
+                input {
+                    class: "bg-[var(--background-dark)] p-2 rounded",
+                    placeholder: "Search...",
+                    value: "{filter}",
+                    oninput: move |e| filter.set(e.value()),
+                }
+                select {
+                    class: "bg-[var(--background-dark)] p-2 rounded",
+                    onchange: move |evt| {
+                        sort_by
+                            .set(
+                                match evt.value().as_str() {
+                                    "Name" => SortBy::Name,
+                                    "Playtime" => SortBy::Playtime,
+                                    "Version" => SortBy::Version,
+                                    _ => SortBy::LastPlayed,
+                                },
+                            )
+                    },
+                    option { value: "LastPlayed", "Last played" }
+                    option { value: "Name", "Name" }
+                    option { value: "Playtime", "Playtime" }
+                    option { value: "Version", "Version" }
+                }
+                button {
+                    class: "flex items-center gap-1 bg-[var(--background-dark)] p-2 px-3 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                    onclick: move |_| show_create.set(true),
+                    Icon { icon: LdPlus, class: "w-4 h-4" }
+                    "New"
+                }
+                button {
+                    class: "flex items-center gap-1 bg-[var(--background-dark)] p-2 px-3 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                    onclick: move |_| show_import.set(true),
+                    Icon { icon: LdDownload, class: "w-4 h-4" }
+                    "Import"
+                }
+            }
+
+            RunningGames {}
+            for blocked in BLOCKED_LAUNCHES.read().values().cloned() {
+                ModProblemsPanel { key: "{blocked.instance.id}", blocked }
+            }
+
+            div { class: "grid grid-cols-[repeat(auto-fill,minmax(12rem,1fr))] gap-3",
+                for instance in shown() {
+                    InstanceCard { key: "{instance.id}", instance }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn InstanceCard(instance: Instance) -> Element {
+    let navigator = use_navigator();
+    let id = instance.id.clone();
+    let running = GAMES
+        .read()
+        .values()
+        .any(|g| g.instance_id == instance.id && g.state.is_active());
+
+    rsx! {
+        div {
+            class: "group relative flex flex-col gap-1 p-3 rounded-lg bg-[var(--background-dark)] hover:bg-[var(--background-light)] cursor-pointer",
+            onclick: move |_| {
+                navigator
+                    .push(Route::InstanceDetails {
+                        id: id.clone(),
+                    });
+            },
+            div { class: "w-12 h-12 rounded bg-[var(--background-darker)] flex items-center justify-center",
+                match instance.loader {
+                    VersionType::Vanilla => rsx! {
+                        Icon { icon: LdBox, class: "w-6 h-6 text-[var(--green)]" }
+                    },
+                    VersionType::NeoForge => rsx! {
+                        Icon { icon: LdHammer, class: "w-6 h-6 text-[var(--orange)]" }
+                    },
+                    VersionType::Fabric => rsx! {
+                        Icon { icon: LdFeather, class: "w-6 h-6 text-[var(--yellow)]" }
+                    },
+                    VersionType::Quilt => rsx! {
+                        Icon { icon: LdLayers, class: "w-6 h-6 text-[var(--red)]" }
+                    },
+                }
+            }
+            p { class: "font-bold truncate", "{instance.name}" }
+            p { class: "text-[var(--grey)]", "{instance.version_label()}" }
+            p { class: "text-[var(--grey)] text-xs",
+                "{instance.last_played_label()} · {instance.playtime_label()}"
+            }
+            if running {
+                button {
+                    class: "absolute top-3 right-3 flex w-8 h-8 rounded-full bg-[var(--red)] items-center justify-center cursor-pointer",
+                    title: "Stop",
+                    onclick: {
+                        let id = instance.id.clone();
+                        move |e: MouseEvent| {
+                            e.stop_propagation();
+                            process::request_stop_instance(&id, StopRequest::Stop);
+                        }
+                    },
+                    Icon { icon: LdSquare, class: "w-4 h-4 text-[var(--background-darker)]" }
+                }
+            } else {
+                button {
+                    class: "absolute top-3 right-3 hidden group-hover:flex w-8 h-8 rounded-full bg-[var(--green)] items-center justify-center cursor-pointer",
+                    onclick: {
+                        let instance = instance.clone();
+                        move |e: MouseEvent| {
+                            e.stop_propagation();
+                            spawn_forever(play(instance.clone(), None));
+                        }
+                    },
+                    Icon { icon: LdPlay, class: "w-4 h-4 text-[var(--background-darker)]" }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+use url::Url;
+
+/// Largest request head we accept from the browser
+const MAX_REQUEST_SIZE: usize = 16 * 1024;
+/// How long an idle (e.g. pre-connected) socket may stay open without sending a request
+const READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+const SUCCESS_PAGE: &str = "<html><body><h1>Login Successful!</h1><p>You can close this window now.</p><script>window.close()</script></body></html>";
+
+/// Bind the first free port of `ports` on localhost
+pub async fn bind(ports: &[u16]) -> anyhow::Result<(TcpListener, u16)> {
+    for &port in ports {
+        match TcpListener::bind(("localhost", port)).await {
+            Ok(listener) => return Ok((listener, port)),
+            Err(e) => log::warn!("Callback port {} unavailable: {}", port, e),
+        }
+    }
+    Err(anyhow::anyhow!("None of the callback ports {:?} are available", ports))
+}
+
+/// Serve requests until the browser hits `/callback` with our state, returning the authorization code.
+/// Other paths (favicon, probes) get a 404 and callbacks with a missing or foreign state an error page,
+/// both are ignored. Dropping the future stops the server and its connections.
+pub async fn wait_for_code(listener: &TcpListener, csrf_state: &str) -> anyhow::Result<String> {
+    let (tx, mut rx) = mpsc::unbounded_channel::<anyhow::Result<String>>();
+    // Aborted with the future when the login is cancelled
+    let mut connections = JoinSet::new();
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                let tx = tx.clone();
+                let csrf_state = csrf_state.to_string();
+                // Handle each connection separately so a silent pre-connect can't block the real request
+                connections.spawn(async move {
+                    if let Some(result) = handle_connection(stream, &csrf_state).await {
+                        let _ = tx.send(result);
+                    }
+                });
+            }
+            Some(result) = rx.recv() => return result,
+            // Reap finished connections so the set doesn't grow
+            Some(_) = connections.join_next(), if !connections.is_empty() => {}
+        }
+    }
+}
+
+/// Returns `None` for requests that aren't the OAuth callback meant for this login
+async fn handle_connection(mut stream: TcpStream, csrf_state: &str) -> Option<anyhow::Result<String>> {
+    let target = match tokio::time::timeout(READ_TIMEOUT, read_request_target(&mut stream)).await {
+        Ok(Ok(Some(target))) => target,
+        Ok(Ok(None)) | Err(_) => return None,
+        Ok(Err(e)) => {
+            log::debug!("Bad callback request: {}", e);
+            return None;
+        }
+    };
+
+    let url = match Url::parse(&format!("http://localhost{}", target)) {
+        Ok(url) => url,
+        Err(_) => {
+            let _ = respond(&mut stream, "400 Bad Request", "<h1>Bad Request</h1>").await;
+            return None;
+        }
+    };
+
+    if url.path() != "/callback" {
+        let _ = respond(&mut stream, "404 Not Found", "<h1>Not Found</h1>").await;
+        return None;
+    }
+
+    let pairs: HashMap<_, _> = url.query_pairs().into_owned().collect();
+    // A stale tab or another app hitting the port, not an answer to this login
+    if pairs.get("state").map(String::as_str) != Some(csrf_state) {
+        log::warn!("Ignoring a callback with a missing or unknown state");
+        let page = error_page("This login link is not the one ezLauncher is waiting for.");
+        let _ = respond(&mut stream, "400 Bad Request", &page).await;
+        return None;
+    }
+    let result = parse_callback(&pairs);
+
+    let page = match &result {
+        Ok(_) => SUCCESS_PAGE.to_string(),
+        Err(e) => error_page(&e.to_string()),
+    };
+    let _ = respond(&mut stream, "200 OK", &page).await;
+
+    Some(result)
+}
+
+fn parse_callback(pairs: &HashMap<String, String>) -> anyhow::Result<String> {
+    if let Some(error) = pairs.get("error") {
+        let description = pairs
+            .get("error_description")
+            .or_else(|| pairs.get("message"))
+            .map(|d| format!(": {}", d))
+            .unwrap_or_default();
+        return Err(anyhow::anyhow!("Authorization failed ({}){}", error, description));
+    }
+
+    pairs
+        .get("code")
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Missing code param"))
+}
+
+/// Read the request head and return the request target, e.g. `/callback?code=...&state=...`.
+/// Returns `None` if the peer closed the connection without sending anything.
+async fn read_request_target(stream: &mut TcpStream) -> anyhow::Result<Option<String>> {
+    let mut buffer = Vec::with_capacity(2048);
+    let mut chunk = [0; 2048];
+
+    while !buffer.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            if buffer.is_empty() {
+                return Ok(None);
+            }
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+        if buffer.len() > MAX_REQUEST_SIZE {
+            return Err(anyhow::anyhow!("Request too large"));
+        }
+    }
+
+    // Request line example: GET /callback?code=...&state=... HTTP/1.1
+    let request = String::from_utf8_lossy(&buffer);
+    let mut request_line = request.lines().next().unwrap_or("").split_whitespace();
+    match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some(target)) => Ok(Some(target.to_string())),
+        _ => Err(anyhow::anyhow!("Unsupported request: {:?}", request.lines().next())),
+    }
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, body: &str) -> anyhow::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+fn error_page(message: &str) -> String {
+    let escaped = message
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    format!(
+        "<html><body><h1>Login Failed</h1><p>{}</p><p>Return to ezLauncher and try again.</p></body></html>",
+        escaped
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn get(port: u16, target: &str) -> String {
+        let mut stream = TcpStream::connect(("localhost", port)).await.unwrap();
+        let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", target);
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn stray_callbacks_keep_the_listener_waiting() {
+        let listener = TcpListener::bind(("localhost", 0)).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let waiting = tokio::spawn(async move { wait_for_code(&listener, "expected").await });
+
+        assert!(get(port, "/favicon.ico").await.starts_with("HTTP/1.1 404"));
+        assert!(get(port, "/callback?code=abc").await.starts_with("HTTP/1.1 400"));
+        assert!(get(port, "/callback?code=abc&state=other").await.starts_with("HTTP/1.1 400"));
+        assert!(!waiting.is_finished());
+
+        assert!(get(port, "/callback?code=abc&state=expected").await.contains("Login Successful"));
+        assert_eq!(waiting.await.unwrap().unwrap(), "abc");
+    }
+
+    #[tokio::test]
+    async fn denied_login_ends_the_wait() {
+        let listener = TcpListener::bind(("localhost", 0)).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let waiting = tokio::spawn(async move { wait_for_code(&listener, "expected").await });
+
+        let response = get(port, "/callback?error=access_denied&state=expected").await;
+        assert!(response.contains("Login Failed"));
+        let err = waiting.await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("access_denied"), "{}", err);
+    }
+}
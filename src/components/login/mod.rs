@@ -1,2 +1,5 @@
+pub mod callback;
 pub mod modal;
-pub mod script;
\ No newline at end of file
+pub mod prompt;
+pub mod script;
+pub mod yggdrasil;
\ No newline at end of file
@@ -1,9 +1,39 @@
 use dioxus::prelude::*;
 
-use crate::{components::login::script, state};
+use crate::{
+    components::login::{
+        script,
+        yggdrasil::{self, Authenticated, Profile, Session},
+    },
+    scripts::{game::ELY_BY_API, settings},
+    state,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LoginMode {
+    OAuth,
+    Password,
+}
+
+async fn finish_yggdrasil_login(session: Session, mut show_modal: Signal<bool>) {
+    if let Err(e) = yggdrasil::save_session(&session).await {
+        log::error!("Failed to save session: {}", e);
+    }
+
+    log::info!("Logged in as {}", session.profile.name);
+    let mut auth = state::AUTH.write();
+    auth.token = Some(session.access_token.clone());
+    auth.user = Some(session.user_info());
+    auth.session = Some(session);
+    drop(auth);
+
+    show_modal.set(false);
+}
 
 #[component]
 pub fn LoginModal(show_modal: Signal<bool>) -> Element {
+    let mut mode = use_signal(|| LoginMode::OAuth);
+
     rsx! {
         // Background
         div {
@@ -14,48 +44,225 @@ pub fn LoginModal(show_modal: Signal<bool>) -> Element {
             },
             // Modal
             div {
-                class: "bg-[var(--background)] p-5 py-20 rounded-lg w-1/3 h-1/2 flex flex-col items-center justify-between",
+                class: "bg-[var(--background)] p-5 py-10 rounded-lg w-1/3 min-h-1/2 flex flex-col items-center justify-between gap-4",
                 onclick: move |e| e.stop_propagation(),
-                div { class: "flex flex-col gap-2 items-center",
-                    h2 { "Ely.by Log In" }
-                    p { class: "text-center",
-                        "You will be redirected to Ely.by to authorize this application and log in to your account."
+                div { class: "flex gap-2",
+                    button {
+                        class: "p-1 px-3 rounded cursor-pointer",
+                        class: if mode() == LoginMode::OAuth { "bg-[var(--background-dark)]" },
+                        onclick: move |_| mode.set(LoginMode::OAuth),
+                        "Ely.by"
+                    }
+                    button {
+                        class: "p-1 px-3 rounded cursor-pointer",
+                        class: if mode() == LoginMode::Password { "bg-[var(--background-dark)]" },
+                        onclick: move |_| mode.set(LoginMode::Password),
+                        "Password"
                     }
                 }
-                button {
-                    class: "border border-[var(--background-darker)] bg-[var(--background-dark)] text-[var(--text-dark)] p-2 px-4 rounded hover:bg-[var(--background-dark)] active:bg-[var(--background-light)] cursor-pointer",
-                    onclick: move |_| {
-                        spawn(async move {
-                            let window = dioxus_desktop::use_window();
-
-                            match script::login().await {
-                                Ok(token) => {
-                                    log::info!("Login successful! Token: {}", token);
-                                    match script::fetch_user_info(&token).await {
-                                        Ok(user_info) => {
-                                            log::info!("User Info: {:?}", user_info);
-                                            state::AUTH.write().token = Some(token);
-                                            state::AUTH.write().user = Some(user_info);
-                                        }
+                match mode() {
+                    LoginMode::OAuth => rsx! {
+                        OAuthLogin { show_modal }
+                    },
+                    LoginMode::Password => rsx! {
+                        PasswordLogin { show_modal }
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn OAuthLogin(show_modal: Signal<bool>) -> Element {
+    // Running login, dropped (and its callback port freed) on cancel or when the modal closes
+    let mut login_task = use_signal(|| None::<Task>);
+    let mut error = use_signal(|| None::<String>);
+
+    rsx! {
+        div { class: "flex flex-col gap-2 items-center",
+            h2 { "Ely.by Log In" }
+            p { class: "text-center",
+                if login_task().is_some() {
+                    "Waiting for you to finish logging in in the browser..."
+                } else {
+                    "You will be redirected to Ely.by to authorize this application and log in to your account."
+                }
+            }
+            if let Some(err) = error() {
+                p { class: "text-[var(--red)] text-center", "{err}" }
+            }
+        }
+        if let Some(task) = login_task() {
+            button {
+                class: "border border-[var(--background-darker)] bg-[var(--background-dark)] text-[var(--text-dark)] p-2 px-4 rounded hover:bg-[var(--background-dark)] active:bg-[var(--background-light)] cursor-pointer",
+                onclick: move |_| {
+                    task.cancel();
+                    login_task.set(None);
+                    log::info!("Login cancelled");
+                },
+                "Cancel"
+            }
+        } else {
+            button {
+                class: "border border-[var(--background-darker)] bg-[var(--background-dark)] text-[var(--text-dark)] p-2 px-4 rounded hover:bg-[var(--background-dark)] active:bg-[var(--background-light)] cursor-pointer",
+                onclick: move |_| {
+                    error.set(None);
+                    let task = spawn(async move {
+                        let window = dioxus_desktop::use_window();
+
+                        let ports = match settings::load_settings().await {
+                            Ok(settings) if !settings.callback_ports.is_empty() => settings.callback_ports,
+                            Ok(_) => script::DEFAULT_CALLBACK_PORTS.to_vec(),
+                            Err(e) => {
+                                log::error!("Failed to load settings: {}", e);
+                                script::DEFAULT_CALLBACK_PORTS.to_vec()
+                            }
+                        };
+                        match script::login(&ports).await {
+                            Ok(tokens) => {
+                                log::info!("Login successful!");
+                                if let Err(e) = script::save_oauth(&tokens).await {
+                                    log::error!("Failed to save the login: {}", e);
+                                }
+                                match script::fetch_user_info(&tokens.access_token).await {
+                                    Ok(user_info) => {
+                                        log::info!("User Info: {:?}", user_info);
+                                        state::AUTH.write().token = Some(tokens.access_token);
+                                        state::AUTH.write().user = Some(user_info);
+                                    }
+                                    Err(e) => {
+                                        log::error!("Failed to fetch user info: {}", e);
+                                    }
+                                }
+                                show_modal.set(false);
+                            }
+                            Err(e) => {
+                                log::error!("Login error: {}", e);
+                                error.set(Some(e.to_string()));
+                            }
+                        }
+
+                        login_task.set(None);
+                        window.set_minimized(false);
+                        window.set_focus();
+                    });
+                    login_task.set(Some(task));
+                },
+                "Log In"
+            }
+        }
+    }
+}
+
+#[component]
+fn PasswordLogin(show_modal: Signal<bool>) -> Element {
+    let mut server = use_signal(|| ELY_BY_API.to_string());
+    let mut username = use_signal(String::new);
+    let mut password = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut busy = use_signal(|| false);
+    // Set when the account owns several profiles and one has to be picked
+    let mut pending = use_signal(|| None::<Authenticated>);
+
+    let input_class = "bg-[var(--background-dark)] p-2 rounded w-full";
+
+    rsx! {
+        div { class: "flex flex-col gap-2 items-center w-full",
+            h2 { "Yggdrasil Log In" }
+
+            if let Some(auth) = pending() {
+                p { class: "text-center", "This account owns several profiles, pick one:" }
+                for profile in auth.available_profiles.clone() {
+                    button {
+                        key: "{profile.id}",
+                        class: "border border-[var(--background-darker)] bg-[var(--background-dark)] p-2 px-4 rounded w-full hover:bg-[var(--background-light)] cursor-pointer",
+                        disabled: busy(),
+                        onclick: {
+                            let auth = auth.clone();
+                            let profile: Profile = profile.clone();
+                            move |_| {
+                                let auth = auth.clone();
+                                let profile = profile.clone();
+                                spawn(async move {
+                                    busy.set(true);
+                                    match yggdrasil::select_profile(&auth, &profile).await {
+                                        Ok(session) => finish_yggdrasil_login(session, show_modal).await,
                                         Err(e) => {
-                                            log::error!("Failed to fetch user info: {}", e);
+                                            log::error!("Profile selection failed: {}", e);
+                                            error.set(Some(e.to_string()));
                                         }
                                     }
-                                    show_modal.set(false);
-                                }
-                                Err(e) => {
-                                    log::error!("Login error: {}", e);
-                                    // TODO: Show error to user
+                                    busy.set(false);
+                                });
+                            }
+                        },
+                        "{profile.name}"
+                    }
+                }
+            } else {
+                input {
+                    class: input_class,
+                    placeholder: "authlib-injector API root",
+                    value: "{server}",
+                    oninput: move |e| server.set(e.value()),
+                }
+                input {
+                    class: input_class,
+                    placeholder: "Username or email",
+                    value: "{username}",
+                    oninput: move |e| username.set(e.value()),
+                }
+                input {
+                    class: input_class,
+                    r#type: "password",
+                    placeholder: "Password",
+                    value: "{password}",
+                    oninput: move |e| password.set(e.value()),
+                }
+            }
+
+            if let Some(err) = error() {
+                p { class: "text-[var(--red)] text-center", "{err}" }
+            }
+        }
+        if pending().is_none() {
+            button {
+                class: "border border-[var(--background-darker)] bg-[var(--background-dark)] text-[var(--text-dark)] p-2 px-4 rounded hover:bg-[var(--background-dark)] active:bg-[var(--background-light)] cursor-pointer",
+                disabled: busy(),
+                onclick: move |_| {
+                    spawn(async move {
+                        busy.set(true);
+                        error.set(None);
+
+                        match yggdrasil::authenticate(&server(), &username(), &password()).await {
+                            Ok(auth) => {
+                                password.set(String::new());
+                                if let Some(session) = auth.clone().into_session() {
+                                    finish_yggdrasil_login(session, show_modal).await;
+                                } else if auth.available_profiles.is_empty() {
+                                    error.set(Some("This account has no game profiles".to_string()));
+                                } else if auth.available_profiles.len() == 1 {
+                                    let profile = auth.available_profiles[0].clone();
+                                    match yggdrasil::select_profile(&auth, &profile).await {
+                                        Ok(session) => finish_yggdrasil_login(session, show_modal).await,
+                                        Err(e) => error.set(Some(e.to_string())),
+                                    }
+                                } else {
+                                    pending.set(Some(auth));
                                 }
                             }
+                            Err(e) => {
+                                log::error!("Login error: {}", e);
+                                error.set(Some(e.to_string()));
+                            }
+                        }
 
-                            window.set_minimized(false);
-                            window.set_focus();
-                        });
-                    },
-                    "Log In"
-                }
+                        busy.set(false);
+                    });
+                },
+                if busy() { "Logging In..." } else { "Log In" }
             }
         }
     }
-}
\ No newline at end of file
+}
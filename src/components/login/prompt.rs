@@ -0,0 +1,40 @@
+use dioxus::prelude::*;
+
+use crate::components::instance::script::play;
+use crate::components::login::modal::LoginModal;
+use crate::state::{AUTH, LAUNCH_AFTER_LOGIN};
+
+/// Login modal for a launch that needs an account (e.g. from a desktop shortcut),
+/// the launch continues once logged in
+#[component]
+pub fn LaunchLoginPrompt() -> Element {
+    let mut show_modal = use_signal(|| false);
+
+    // Open when a launch starts waiting
+    use_effect(move || {
+        if LAUNCH_AFTER_LOGIN.read().is_some() && !*show_modal.peek() {
+            show_modal.set(true);
+        }
+    });
+
+    // Closing the modal either continues the launch or drops it
+    use_effect(move || {
+        if show_modal() || LAUNCH_AFTER_LOGIN.peek().is_none() {
+            return;
+        }
+        let Some((instance, quick_play)) = LAUNCH_AFTER_LOGIN.write().take() else {
+            return;
+        };
+        if AUTH.peek().user.is_some() {
+            spawn_forever(play(instance, quick_play));
+        } else {
+            log::warn!("Not logged in, {} was not started", instance.name);
+        }
+    });
+
+    rsx! {
+        if show_modal() {
+            LoginModal { show_modal }
+        }
+    }
+}
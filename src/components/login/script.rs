@@ -1,71 +1,105 @@
 use oauth2::{
-    basic::BasicClient, AuthType, AuthUrl, ClientId, ClientSecret, CsrfToken, RedirectUrl,
-    Scope, TokenResponse, TokenUrl, AuthorizationCode
+    basic::BasicClient, AuthType, AuthUrl, ClientId, ClientSecret, CsrfToken, PkceCodeChallenge,
+    RedirectUrl, RefreshToken, Scope, TokenResponse, TokenUrl, AuthorizationCode
 };
 use oauth2::reqwest::async_http_client;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::net::TcpListener;
-use url::Url;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
 
-use crate::secrets;
+use crate::components::login::callback;
+use crate::components::login::yggdrasil::{self, Session};
+use crate::scripts::game::DATA_DIR;
+use crate::scripts::instances::now_secs;
+use crate::scripts::skins::ELY_BY_SKINSYSTEM;
+use crate::{secrets, state};
 
 const AUTH_URL: &str = "https://account.ely.by/oauth2/v1";
 const TOKEN_URL: &str = "https://account.ely.by/api/oauth2/v1/token";
+const USER_INFO_URL: &str = "https://account.ely.by/api/account/v1/info";
 
-pub async fn login() -> anyhow::Result<String> {
-    let client = BasicClient::new(
+/// Callback ports tried in order unless the settings list others,
+/// `http://localhost:<port>/callback` must be allowed as redirect URI
+pub const DEFAULT_CALLBACK_PORTS: &[u16] = &[23234, 23235, 23236, 23237];
+/// How long to wait for the user to finish logging in in the browser
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+const OAUTH_FILE: &str = "oauth.json";
+/// Refresh this long before the access token expires, so it doesn't run out during a launch
+const EXPIRY_MARGIN_SECS: u64 = 5 * 60;
+
+/// Tokens of an Ely.by OAuth login, persisted so the launcher and its shortcuts stay logged in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OAuthTokens {
+    pub access_token: String,
+    /// Only issued for the `offline_access` scope
+    pub refresh_token: Option<String>,
+    /// Unix timestamp (seconds)
+    pub expires_at: Option<u64>,
+}
+
+impl OAuthTokens {
+    fn from_response(response: &impl TokenResponse<oauth2::basic::BasicTokenType>) -> Self {
+        Self {
+            access_token: response.access_token().secret().to_string(),
+            refresh_token: response.refresh_token().map(|t| t.secret().to_string()),
+            expires_at: response.expires_in().map(|d| now_secs() + d.as_secs()),
+        }
+    }
+
+    fn expires_soon(&self) -> bool {
+        self.expires_at
+            .is_some_and(|at| at <= now_secs() + EXPIRY_MARGIN_SECS)
+    }
+}
+
+fn oauth_client() -> anyhow::Result<BasicClient> {
+    Ok(BasicClient::new(
         ClientId::new(secrets::AUTH_CLIENT_ID.to_string()),
         Some(ClientSecret::new(secrets::AUTH_CLIENT_SECRET.to_string())),
         AuthUrl::new(AUTH_URL.to_string())?,
         Some(TokenUrl::new(TOKEN_URL.to_string())?),
     )
-    .set_auth_type(AuthType::RequestBody)
-    .set_redirect_uri(RedirectUrl::new(secrets::AUTH_REDIRECT_URI.to_string())?);
+    .set_auth_type(AuthType::RequestBody))
+}
+
+/// Run the OAuth flow on the first free port of `ports`.
+/// The returned future can be dropped at any time to cancel the login and free the port.
+pub async fn login(ports: &[u16]) -> anyhow::Result<OAuthTokens> {
+    // Create a listener for the callback
+    let (listener, port) = callback::bind(ports).await?;
+    let redirect_uri = format!("http://localhost:{}/callback", port);
+
+    let client = oauth_client()?.set_redirect_uri(RedirectUrl::new(redirect_uri)?);
+
+    let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
 
     // Generate the full authorization URL.
     let (auth_url, csrf_token) = client
         .authorize_url(CsrfToken::new_random)
         .add_scope(Scope::new("account_info".to_string()))
+        .add_scope(Scope::new("offline_access".to_string()))
+        .set_pkce_challenge(pkce_challenge)
         .url();
 
-    // Create a listener for the callback
-    let listener = TcpListener::bind("localhost:23234").await?;
-    
     // Open the browser
     if let Err(e) = webbrowser::open(auth_url.as_str()) {
         log::error!("Failed to open browser: {}", e);
     }
 
     // Wait for the callback
-    log::info!("Waiting for callback...");
-    let (mut stream, _) = listener.accept().await?;
-    
-    let mut buffer = [0; 2048];
-    let n = stream.read(&mut buffer).await?;
-    let request = String::from_utf8_lossy(&buffer[..n]);
-
-    // Parse the code and state from the query string
-    // Request line example: GET /callback?code=...&state=... HTTP/1.1
-    let url_part = request.lines().next().unwrap_or("").split_whitespace().nth(1).unwrap_or("");
-    let url = Url::parse(&format!("http://127.0.0.1:23234{}", url_part))?;
-    
-    let pairs: std::collections::HashMap<_, _> = url.query_pairs().into_owned().collect();
-    
-    let code = pairs.get("code").ok_or_else(|| anyhow::anyhow!("Missing code param"))?;
-    let state = pairs.get("state").ok_or_else(|| anyhow::anyhow!("Missing state param"))?;
-
-    if state != csrf_token.secret() {
-        return Err(anyhow::anyhow!("Invalid state token"));
-    }
-
-    // Respond to the browser
-    let response = "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\r\n<html><body><h1>Login Successful!</h1><p>You can close this window now.</p><script>window.close()</script></body></html>";
-    stream.write_all(response.as_bytes()).await?;
-    stream.flush().await?;
+    log::info!("Waiting for callback on port {}...", port);
+    let code = tokio::time::timeout(
+        LOGIN_TIMEOUT,
+        callback::wait_for_code(&listener, csrf_token.secret()),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("Timed out waiting for the browser login"))??;
+    drop(listener);
 
     // Exchange the code with a token.
     let token_result = client
-        .exchange_code(AuthorizationCode::new(code.clone()))
+        .exchange_code(AuthorizationCode::new(code))
+        .set_pkce_verifier(pkce_verifier)
         .request_async(async_http_client)
         .await
         .map_err(|e| {
@@ -73,7 +107,73 @@ pub async fn login() -> anyhow::Result<String> {
             anyhow::anyhow!("Token exchange failed: {:?}", e)
         })?;
 
-    Ok(token_result.access_token().secret().to_string())
+    Ok(OAuthTokens::from_response(&token_result))
+}
+
+/// Trade the refresh token for a new access token
+pub async fn refresh_oauth(tokens: &OAuthTokens) -> anyhow::Result<OAuthTokens> {
+    let refresh_token = tokens
+        .refresh_token
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("The login has expired, log in again"))?;
+    let response = oauth_client()?
+        .exchange_refresh_token(&RefreshToken::new(refresh_token))
+        .request_async(async_http_client)
+        .await
+        .map_err(|e| anyhow::anyhow!("Token refresh failed: {:?}", e))?;
+
+    let mut refreshed = OAuthTokens::from_response(&response);
+    // The refresh token stays valid when no new one is issued
+    if refreshed.refresh_token.is_none() {
+        refreshed.refresh_token = tokens.refresh_token.clone();
+    }
+    Ok(refreshed)
+}
+
+/// The saved OAuth login with a working access token, refreshed and saved again if it expired.
+/// `None` when nobody logged in with OAuth.
+pub async fn restore_oauth() -> anyhow::Result<Option<UserInfo>> {
+    let Some(mut tokens) = load_oauth().await? else {
+        return Ok(None);
+    };
+
+    if !tokens.expires_soon() {
+        match fetch_user_info(&tokens.access_token).await {
+            Ok(user) => return Ok(Some(user)),
+            Err(e) => log::info!("Saved OAuth token rejected ({}), refreshing...", e),
+        }
+    }
+    tokens = refresh_oauth(&tokens).await?;
+    save_oauth(&tokens).await?;
+    Ok(Some(fetch_user_info(&tokens.access_token).await?))
+}
+
+fn oauth_path() -> PathBuf {
+    PathBuf::from(DATA_DIR).join(OAUTH_FILE)
+}
+
+pub async fn save_oauth(tokens: &OAuthTokens) -> anyhow::Result<()> {
+    tokio::fs::create_dir_all(DATA_DIR).await?;
+    tokio::fs::write(oauth_path(), serde_json::to_string_pretty(tokens)?).await?;
+    Ok(())
+}
+
+async fn load_oauth() -> anyhow::Result<Option<OAuthTokens>> {
+    let path = oauth_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = tokio::fs::read_to_string(path).await?;
+    Ok(Some(serde_json::from_str(&content)?))
+}
+
+pub async fn delete_oauth() -> anyhow::Result<()> {
+    let path = oauth_path();
+    if path.exists() {
+        tokio::fs::remove_file(path).await?;
+    }
+    Ok(())
 }
 
 #[derive(serde::Deserialize, Debug)]
@@ -86,7 +186,7 @@ struct UserInfoResponse {
 
 #[derive(Debug, Clone)]
 pub struct UserInfo {
-    pub id: u32,
+    pub id: Option<u32>,
     pub uuid: String,
     pub username: String,
     pub email: Option<String>,
@@ -97,7 +197,7 @@ pub struct UserInfo {
 pub async fn fetch_user_info(token: &str) -> anyhow::Result<UserInfo> {
     let client = reqwest::Client::new();
     let response = client
-        .get("https://account.ely.by/api/account/v1/info")
+        .get(USER_INFO_URL)
         .header("Authorization", format!("Bearer {}", token))
         .send()
         .await?;
@@ -109,11 +209,68 @@ pub async fn fetch_user_info(token: &str) -> anyhow::Result<UserInfo> {
     let api_response = response.json::<UserInfoResponse>().await?;
     
     Ok(UserInfo {
-        id: api_response.id,
+        id: Some(api_response.id),
         uuid: api_response.uuid,
-        skin_url: format!("http://skinsystem.ely.by/skins/{}.png", api_response.username),
+        skin_url: format!("{}/skins/{}.png", ELY_BY_SKINSYSTEM, api_response.username),
         username: api_response.username,
         email: api_response.email,
         access_token: token.to_string(),
     })
-}
\ No newline at end of file
+}
+
+/// Check that the provider still accepts the current credentials
+pub async fn validate(user: &UserInfo, session: Option<&Session>) -> anyhow::Result<bool> {
+    match session {
+        Some(session) => yggdrasil::validate(session).await,
+        None => {
+            let response = reqwest::Client::new()
+                .get(USER_INFO_URL)
+                .header("Authorization", format!("Bearer {}", user.access_token))
+                .send()
+                .await?;
+            Ok(response.status().is_success())
+        }
+    }
+}
+
+/// What happened to the access token at the provider on logout
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenRevocation {
+    Revoked,
+    /// The auth server couldn't be reached or refused, the token may still be valid
+    Failed(String),
+    /// Ely.by doesn't offer OAuth token revocation, the token stays valid until it expires
+    Unsupported,
+}
+
+/// Revoke the token at the provider when possible, wipe persisted credentials and reset `AUTH`.
+/// Local state is always cleared, whatever the provider said.
+pub async fn logout() -> anyhow::Result<TokenRevocation> {
+    let auth = state::AUTH();
+
+    let revocation = match &auth.session {
+        Some(session) => match yggdrasil::invalidate(session).await {
+            Ok(()) => TokenRevocation::Revoked,
+            Err(e) => {
+                log::warn!("Failed to invalidate token at {}: {}", session.server, e);
+                TokenRevocation::Failed(e.to_string())
+            }
+        },
+        None if auth.token.is_some() => {
+            log::warn!("Ely.by OAuth tokens can't be revoked, dropping it locally");
+            TokenRevocation::Unsupported
+        }
+        // Nothing to revoke
+        None => TokenRevocation::Revoked,
+    };
+
+    yggdrasil::delete_session().await?;
+    delete_oauth().await?;
+
+    let mut auth = state::AUTH.write();
+    auth.token = None;
+    auth.user = None;
+    auth.session = None;
+
+    Ok(revocation)
+}
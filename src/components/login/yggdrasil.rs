@@ -0,0 +1,382 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::PathBuf;
+
+use crate::components::login::script::UserInfo;
+use crate::scripts::game::DATA_DIR;
+use crate::scripts::skins::ELY_BY_SKINSYSTEM;
+
+const SESSION_FILE: &str = "session.json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+}
+
+/// A logged in Yggdrasil session against an authlib-injector server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    /// authlib-injector API root, e.g. https://authserver.ely.by/api/authlib-injector
+    pub server: String,
+    pub client_token: String,
+    pub access_token: String,
+    pub profile: Profile,
+}
+
+impl Session {
+    pub fn user_info(&self) -> UserInfo {
+        UserInfo {
+            id: None,
+            uuid: self.profile.id.clone(),
+            skin_url: format!("{}/skins/{}.png", ELY_BY_SKINSYSTEM, self.profile.name),
+            username: self.profile.name.clone(),
+            email: None,
+            access_token: self.access_token.clone(),
+        }
+    }
+}
+
+/// Result of `authenticate`, before a profile has been picked
+#[derive(Debug, Clone, PartialEq)]
+pub struct Authenticated {
+    pub server: String,
+    pub client_token: String,
+    pub access_token: String,
+    pub available_profiles: Vec<Profile>,
+    pub selected_profile: Option<Profile>,
+}
+
+impl Authenticated {
+    /// Turn into a session if the server already bound a profile to the token
+    pub fn into_session(self) -> Option<Session> {
+        let profile = self.selected_profile?;
+        Some(Session {
+            server: self.server,
+            client_token: self.client_token,
+            access_token: self.access_token,
+            profile,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthenticateResponse {
+    access_token: String,
+    client_token: String,
+    #[serde(default)]
+    available_profiles: Vec<Profile>,
+    selected_profile: Option<Profile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RefreshResponse {
+    access_token: String,
+    client_token: String,
+    selected_profile: Option<Profile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorResponse {
+    error: String,
+    error_message: Option<String>,
+}
+
+fn endpoint(server: &str, path: &str) -> String {
+    format!("{}/authserver/{}", server.trim_end_matches('/'), path)
+}
+
+async fn post(server: &str, path: &str, body: serde_json::Value) -> anyhow::Result<reqwest::Response> {
+    let client = reqwest::Client::builder().user_agent("ezLauncher/0.2.0").build()?;
+    let response = client.post(endpoint(server, path)).json(&body).send().await?;
+
+    if response.status().is_success() {
+        return Ok(response);
+    }
+
+    let status = response.status();
+    match response.json::<ErrorResponse>().await {
+        Ok(err) => Err(anyhow::anyhow!(
+            "{}: {}",
+            err.error,
+            err.error_message.unwrap_or_default()
+        )),
+        Err(_) => Err(anyhow::anyhow!("Yggdrasil {} failed: {}", path, status)),
+    }
+}
+
+pub async fn authenticate(server: &str, username: &str, password: &str) -> anyhow::Result<Authenticated> {
+    let client_token = format!("{:032x}", rand::random::<u128>());
+
+    let response = post(
+        server,
+        "authenticate",
+        json!({
+            "agent": { "name": "Minecraft", "version": 1 },
+            "username": username,
+            "password": password,
+            "clientToken": client_token,
+            "requestUser": false,
+        }),
+    )
+    .await?
+    .json::<AuthenticateResponse>()
+    .await?;
+
+    if response.client_token != client_token {
+        return Err(anyhow::anyhow!("Server returned a different client token"));
+    }
+
+    Ok(Authenticated {
+        server: server.to_string(),
+        client_token: response.client_token,
+        access_token: response.access_token,
+        available_profiles: response.available_profiles,
+        selected_profile: response.selected_profile,
+    })
+}
+
+/// Bind one of the account's profiles to the token (used when an account owns several)
+pub async fn select_profile(auth: &Authenticated, profile: &Profile) -> anyhow::Result<Session> {
+    let response = post(
+        &auth.server,
+        "refresh",
+        json!({
+            "accessToken": auth.access_token,
+            "clientToken": auth.client_token,
+            "selectedProfile": profile,
+        }),
+    )
+    .await?
+    .json::<RefreshResponse>()
+    .await?;
+
+    Ok(Session {
+        server: auth.server.clone(),
+        client_token: response.client_token,
+        access_token: response.access_token,
+        profile: response.selected_profile.unwrap_or_else(|| profile.clone()),
+    })
+}
+
+pub async fn refresh(session: &Session) -> anyhow::Result<Session> {
+    let response = post(
+        &session.server,
+        "refresh",
+        json!({
+            "accessToken": session.access_token,
+            "clientToken": session.client_token,
+        }),
+    )
+    .await?
+    .json::<RefreshResponse>()
+    .await?;
+
+    Ok(Session {
+        server: session.server.clone(),
+        client_token: response.client_token,
+        access_token: response.access_token,
+        profile: response.selected_profile.unwrap_or_else(|| session.profile.clone()),
+    })
+}
+
+pub async fn validate(session: &Session) -> anyhow::Result<bool> {
+    let client = reqwest::Client::builder().user_agent("ezLauncher/0.2.0").build()?;
+    let response = client
+        .post(endpoint(&session.server, "validate"))
+        .json(&json!({
+            "accessToken": session.access_token,
+            "clientToken": session.client_token,
+        }))
+        .send()
+        .await?;
+
+    // 204 = valid, 403 = invalid or expired token
+    Ok(response.status().is_success())
+}
+
+pub async fn invalidate(session: &Session) -> anyhow::Result<()> {
+    post(
+        &session.server,
+        "invalidate",
+        json!({
+            "accessToken": session.access_token,
+            "clientToken": session.client_token,
+        }),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Validate the session and refresh it if the access token is no longer accepted
+pub async fn ensure_valid(session: &Session) -> anyhow::Result<Session> {
+    if validate(session).await? {
+        return Ok(session.clone());
+    }
+
+    log::info!("Access token expired, refreshing...");
+    let refreshed = refresh(session).await?;
+    save_session(&refreshed).await?;
+    Ok(refreshed)
+}
+
+fn session_path() -> PathBuf {
+    PathBuf::from(DATA_DIR).join(SESSION_FILE)
+}
+
+pub async fn save_session(session: &Session) -> anyhow::Result<()> {
+    tokio::fs::create_dir_all(DATA_DIR).await?;
+    tokio::fs::write(session_path(), serde_json::to_string_pretty(session)?).await?;
+    Ok(())
+}
+
+pub async fn load_session() -> anyhow::Result<Option<Session>> {
+    let path = session_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = tokio::fs::read_to_string(path).await?;
+    Ok(Some(serde_json::from_str(&content)?))
+}
+
+pub async fn delete_session() -> anyhow::Result<()> {
+    let path = session_path();
+    if path.exists() {
+        tokio::fs::remove_file(path).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scripts::test_server::{self, Response};
+
+    fn profile(id: &str, name: &str) -> serde_json::Value {
+        json!({ "id": id, "name": name })
+    }
+
+    /// authlib-injector server where `password` logs in and `fresh-token` is the only valid token
+    async fn stand_in(profiles: Vec<serde_json::Value>, selected: Option<serde_json::Value>) -> test_server::TestServer {
+        test_server::serve(move |request| {
+            let body = request.json();
+            let forbidden = Response::json(
+                403,
+                json!({
+                    "error": "ForbiddenOperationException",
+                    "errorMessage": "Invalid token.",
+                }),
+            );
+            match request.path.as_str() {
+                "/authserver/authenticate" if body["password"] == "password" => Response::json(
+                    200,
+                    json!({
+                        "accessToken": "fresh-token",
+                        "clientToken": body["clientToken"],
+                        "availableProfiles": profiles,
+                        "selectedProfile": selected,
+                    }),
+                ),
+                "/authserver/authenticate" => Response::json(
+                    403,
+                    json!({
+                        "error": "ForbiddenOperationException",
+                        "errorMessage": "Invalid credentials. Invalid username or password.",
+                    }),
+                ),
+                "/authserver/refresh" if body["accessToken"] == "fresh-token" => {
+                    let selected = if body["selectedProfile"].is_null() {
+                        selected.clone().unwrap_or_default()
+                    } else {
+                        body["selectedProfile"].clone()
+                    };
+                    Response::json(
+                        200,
+                        json!({
+                            "accessToken": "refreshed-token",
+                            "clientToken": body["clientToken"],
+                            "selectedProfile": selected,
+                        }),
+                    )
+                }
+                "/authserver/validate" if body["accessToken"] == "fresh-token" => Response::status(204),
+                _ => forbidden,
+            }
+        })
+        .await
+    }
+
+    fn session(server: &str, access_token: &str) -> Session {
+        Session {
+            server: server.to_string(),
+            client_token: "client".to_string(),
+            access_token: access_token.to_string(),
+            profile: Profile {
+                id: "1".to_string(),
+                name: "Steve".to_string(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn authenticate_with_one_profile_is_a_session() {
+        let server = stand_in(vec![profile("1", "Steve")], Some(profile("1", "Steve"))).await;
+
+        let auth = authenticate(&server.url, "steve@example.com", "password").await.unwrap();
+        assert_eq!(auth.access_token, "fresh-token");
+        let session = auth.into_session().unwrap();
+        assert_eq!(session.profile.name, "Steve");
+        assert_eq!(session.server, server.url);
+    }
+
+    #[tokio::test]
+    async fn authenticate_with_wrong_password_fails() {
+        let server = stand_in(vec![], None).await;
+
+        let err = authenticate(&server.url, "steve@example.com", "wrong").await.unwrap_err();
+        assert!(err.to_string().contains("Invalid credentials"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn multiple_profiles_need_a_selection() {
+        let server = stand_in(vec![profile("1", "Steve"), profile("2", "Alex")], None).await;
+
+        let auth = authenticate(&server.url, "steve@example.com", "password").await.unwrap();
+        assert_eq!(auth.available_profiles.len(), 2);
+        assert!(auth.clone().into_session().is_none());
+
+        let alex = auth.available_profiles[1].clone();
+        let session = select_profile(&auth, &alex).await.unwrap();
+        assert_eq!(session.profile, alex);
+        assert_eq!(session.access_token, "refreshed-token");
+
+        let refresh_request = server.requests().pop().unwrap();
+        assert_eq!(refresh_request.path, "/authserver/refresh");
+        assert_eq!(refresh_request.json()["selectedProfile"]["name"], "Alex");
+    }
+
+    #[tokio::test]
+    async fn refresh_keeps_the_profile() {
+        let server = stand_in(vec![profile("1", "Steve")], Some(profile("1", "Steve"))).await;
+
+        let refreshed = refresh(&session(&server.url, "fresh-token")).await.unwrap();
+        assert_eq!(refreshed.access_token, "refreshed-token");
+        assert_eq!(refreshed.profile.name, "Steve");
+    }
+
+    #[tokio::test]
+    async fn invalid_token_fails_validate_and_refresh() {
+        let server = stand_in(vec![profile("1", "Steve")], Some(profile("1", "Steve"))).await;
+
+        assert!(validate(&session(&server.url, "fresh-token")).await.unwrap());
+        let revoked = session(&server.url, "revoked-token");
+        assert!(!validate(&revoked).await.unwrap());
+        let err = refresh(&revoked).await.unwrap_err();
+        assert!(err.to_string().contains("ForbiddenOperationException"), "{}", err);
+    }
+}
@@ -1,6 +1,9 @@
 use dioxus::prelude::*;
+use dioxus_free_icons::{Icon, icons::ld_icons::{LdLibrary, LdSettings, LdTerminal}};
 
-use crate::components::{main_content::MainContent, resize_borders::ResizeBorders, title_bar::TitleBar};
+use crate::components::login::prompt::LaunchLoginPrompt;
+use crate::components::{resize_borders::ResizeBorders, title_bar::TitleBar};
+use crate::routes::Route;
 
 #[component]
 pub fn MainLayout() -> Element {
@@ -8,7 +11,45 @@ pub fn MainLayout() -> Element {
         div { class: "relative w-screen h-screen border border-[var(--background-dark)] flex flex-col",
             ResizeBorders {}
             TitleBar {}
-            MainContent {}
+            div { class: "flex-1 flex min-h-0",
+                NavBar {}
+                div { class: "relative flex-1 flex flex-col min-w-0 overflow-y-auto",
+                    Outlet::<Route> {}
+                }
+            }
+            LaunchLoginPrompt {}
+        }
+    }
+}
+
+#[component]
+fn NavBar() -> Element {
+    let route = use_route::<Route>();
+    let item_class = |active: bool| {
+        format!(
+            "group w-10 h-10 rounded flex items-center justify-center hover:bg-[var(--background-light)] {}",
+            if active { "bg-[var(--background-light)]" } else { "" }
+        )
+    };
+    let icon_class = "w-1/2 h-1/2 text-[var(--background-lighter)] group-hover:text-[var(--foreground)]";
+
+    rsx! {
+        nav { class: "w-14 bg-[var(--background-dark)] flex flex-col items-center gap-2 py-2",
+            Link {
+                class: item_class(matches!(route, Route::Library {} | Route::InstanceDetails { .. })),
+                to: Route::Library {},
+                Icon { icon: LdLibrary, class: icon_class }
+            }
+            Link {
+                class: item_class(matches!(route, Route::LauncherConsole {})),
+                to: Route::LauncherConsole {},
+                Icon { icon: LdTerminal, class: icon_class }
+            }
+            Link {
+                class: item_class(matches!(route, Route::Settings {})),
+                to: Route::Settings {},
+                Icon { icon: LdSettings, class: icon_class }
+            }
         }
     }
 }
\ No newline at end of file
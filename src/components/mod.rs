@@ -1,5 +1,13 @@
 pub mod main_layout;
 pub mod title_bar;
 pub mod resize_borders;
-pub mod main_content;
-pub mod login;
\ No newline at end of file
+pub mod login;
+pub mod skins;
+pub mod library;
+pub mod instance;
+pub mod settings;
+pub mod mods;
+pub mod browse;
+pub mod packs;
+pub mod console;
+pub mod games;
\ No newline at end of file
@@ -0,0 +1,26 @@
+use dioxus::prelude::*;
+
+use crate::scripts::mods::{ModIssue, Severity};
+
+/// Problems the pre-launch check would report
+#[component]
+pub fn IssuesPanel(issues: Vec<ModIssue>) -> Element {
+    if issues.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "flex flex-col gap-1 p-3 rounded-lg bg-[var(--background-darker)]",
+            for (i, issue) in issues.into_iter().enumerate() {
+                p {
+                    key: "{i}",
+                    class: match issue.severity {
+                        Severity::Error => "text-[var(--red)]",
+                        Severity::Warning => "text-[var(--yellow)]",
+                    },
+                    "{issue.message}"
+                }
+            }
+        }
+    }
+}
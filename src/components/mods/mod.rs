@@ -0,0 +1,3 @@
+pub mod issues;
+pub mod page;
+pub mod updates;
\ No newline at end of file
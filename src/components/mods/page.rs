@@ -0,0 +1,210 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::{Icon, icons::ld_icons::{LdArrowLeft, LdPackage, LdRefreshCw, LdSearch, LdTrash2, LdUndo2}};
+
+use crate::components::mods::issues::IssuesPanel;
+use crate::components::mods::updates::UpdatesPanel;
+use crate::routes::Route;
+use crate::scripts::instances::{self, Instance};
+use crate::scripts::mods::{self, DependencyKind, ModInfo, Rollback};
+
+#[component]
+pub fn InstanceMods(id: ReadOnlySignal<String>) -> Element {
+    let mut filter = use_signal(String::new);
+    let mut show_updates = use_signal(|| false);
+
+    let mut mod_list = use_resource(move || async move {
+        let instance = instances::load_instance(&id()).await.map_err(|e| e.to_string())?;
+        let mods = mods::list_mods(&instance).await.map_err(|e| e.to_string())?;
+        let rollbacks = mods::load_rollbacks(&instance).await.unwrap_or_else(|e| {
+            log::warn!("Failed to load mod rollbacks: {}", e);
+            Vec::new()
+        });
+        Ok::<_, String>((instance, mods, rollbacks))
+    });
+
+    rsx! {
+        div { class: "flex-1 flex flex-col gap-4 p-6",
+            Link {
+                class: "flex items-center gap-1 text-[var(--grey)] hover:text-[var(--foreground)]",
+                to: Route::InstanceDetails { id: id() },
+                Icon { icon: LdArrowLeft, class: "w-4 h-4" }
+                "Instance"
+            }
+            match mod_list() {
+                Some(Ok((instance, mods, rollbacks))) => {
+                    let query = filter().to_lowercase();
+                    let enabled = mods.iter().filter(|m| m.enabled).count();
+                    let issues = mods::analysis::analyze(&instance, &mods);
+                    rsx! {
+                        div { class: "flex items-center gap-2",
+                            h2 { class: "flex-1", "{instance.name} · Mods" }
+                            p { class: "text-[var(--grey)]", "{enabled}/{mods.len()} enabled" }
+                            input {
+                                class: "bg-[var(--background-dark)] p-2 rounded",
+                                placeholder: "Search...",
+                                value: "{filter}",
+                                oninput: move |e| filter.set(e.value()),
+                            }
+                            Link {
+                                class: "flex items-center gap-1 bg-[var(--background-dark)] p-2 px-3 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                                to: Route::BrowseMods { id: id() },
+                                Icon { icon: LdSearch, class: "w-4 h-4" }
+                                "Browse"
+                            }
+                            button {
+                                class: "flex items-center gap-1 bg-[var(--background-dark)] p-2 px-3 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                                onclick: move |_| show_updates.set(!show_updates()),
+                                Icon { icon: LdRefreshCw, class: "w-4 h-4" }
+                                "Updates"
+                            }
+                        }
+                        if show_updates() {
+                            UpdatesPanel {
+                                instance: instance.clone(),
+                                on_change: move |_| mod_list.restart(),
+                            }
+                        }
+                        IssuesPanel { issues }
+                        if mods.is_empty() {
+                            p { class: "text-[var(--grey)]", "No mods installed" }
+                        }
+                        div { class: "flex flex-col gap-2",
+                            for info in mods
+                                .into_iter()
+                                .filter(|m| {
+                                    query.is_empty() || m.name.to_lowercase().contains(&query)
+                                        || m.file_name.to_lowercase().contains(&query)
+                                })
+                            {
+                                ModRow {
+                                    key: "{info.file_name}",
+                                    rollback: rollbacks.iter().find(|r| r.current_file == info.file_name).cloned(),
+                                    instance: instance.clone(),
+                                    info,
+                                    on_change: move |_| mod_list.restart(),
+                                }
+                            }
+                        }
+                    }
+                }
+                Some(Err(e)) => rsx! {
+                    p { class: "text-[var(--red)]", "Failed to load mods: {e}" }
+                },
+                None => rsx! {
+                    p { class: "text-[var(--grey)]", "Loading..." }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn ModRow(
+    info: ModInfo,
+    instance: Instance,
+    rollback: Option<Rollback>,
+    on_change: EventHandler<()>,
+) -> Element {
+    let authors = info.authors.join(", ");
+    let dependencies = info
+        .dependencies
+        .iter()
+        .filter(|d| {
+            matches!(d.kind, DependencyKind::Required | DependencyKind::Optional)
+                && !mods::PLATFORM_IDS.contains(&d.id.as_str())
+        })
+        .map(|d| match d.kind {
+            DependencyKind::Optional => format!("{} (optional)", d.id),
+            _ => d.id.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    rsx! {
+        div {
+            class: "flex items-center gap-3 p-3 rounded-lg bg-[var(--background-dark)]",
+            class: if !info.enabled { "opacity-50" },
+            div { class: "w-10 h-10 shrink-0 rounded bg-[var(--background-darker)] flex items-center justify-center overflow-hidden",
+                if let Some(icon) = &info.icon {
+                    img { class: "w-full h-full [image-rendering:pixelated]", src: "{icon}" }
+                } else {
+                    Icon { icon: LdPackage, class: "w-5 h-5 text-[var(--grey)]" }
+                }
+            }
+            div { class: "flex-1 min-w-0",
+                p { class: "truncate",
+                    span { class: "font-bold", "{info.name}" }
+                    if let Some(version) = &info.version {
+                        span { class: "text-[var(--grey)]", " {version}" }
+                    }
+                }
+                if let Some(error) = &info.metadata_error {
+                    p { class: "text-[var(--yellow)] text-xs truncate", title: "{error}",
+                        "Metadata unreadable, showing the file name"
+                    }
+                }
+                if !authors.is_empty() {
+                    p { class: "text-[var(--grey)] text-xs truncate", "by {authors}" }
+                }
+                if let Some(description) = &info.description {
+                    p { class: "text-xs truncate", "{description}" }
+                }
+                if !dependencies.is_empty() {
+                    p { class: "text-[var(--grey)] text-xs truncate", "Depends on: {dependencies}" }
+                }
+            }
+            if let Some(rollback) = rollback {
+                button {
+                    class: "p-2 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                    title: "Roll back to {rollback.previous_file}",
+                    onclick: move |_| {
+                        let instance = instance.clone();
+                        let rollback = rollback.clone();
+                        async move {
+                            if let Err(e) = mods::rollback(&instance, &rollback).await {
+                                log::error!("Failed to roll back {}: {}", rollback.current_file, e);
+                            }
+                            on_change.call(());
+                        }
+                    },
+                    Icon { icon: LdUndo2, class: "w-4 h-4" }
+                }
+            }
+            input {
+                r#type: "checkbox",
+                class: "cursor-pointer",
+                title: if info.enabled { "Disable" } else { "Enable" },
+                checked: info.enabled,
+                onchange: {
+                    let info = info.clone();
+                    move |e: FormEvent| {
+                        let info = info.clone();
+                        async move {
+                            if let Err(e) = mods::set_mod_enabled(&info, e.checked()).await {
+                                log::error!("Failed to toggle {}: {}", info.file_name, e);
+                            }
+                            on_change.call(());
+                        }
+                    }
+                },
+            }
+            button {
+                class: "p-2 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                title: "Delete",
+                onclick: {
+                    let info = info.clone();
+                    move |_| {
+                        let info = info.clone();
+                        async move {
+                            if let Err(e) = mods::delete_mod(&info).await {
+                                log::error!("Failed to delete {}: {}", info.file_name, e);
+                            }
+                            on_change.call(());
+                        }
+                    }
+                },
+                Icon { icon: LdTrash2, class: "w-4 h-4 text-[var(--red)]" }
+            }
+        }
+    }
+}
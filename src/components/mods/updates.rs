@@ -0,0 +1,114 @@
+use dioxus::prelude::*;
+
+use crate::scripts::instances::Instance;
+use crate::scripts::modrinth::ModrinthClient;
+use crate::scripts::mods::{self, ModUpdate};
+
+/// Available Modrinth updates for the instance's mods
+#[component]
+pub fn UpdatesPanel(instance: Instance, on_change: EventHandler<()>) -> Element {
+    let mut busy = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    let mut updates = use_resource({
+        let instance = instance.clone();
+        move || {
+            let instance = instance.clone();
+            async move {
+                let client = ModrinthClient::from_settings().await.map_err(|e| e.to_string())?;
+                mods::check_updates(&client, &instance).await.map_err(|e| e.to_string())
+            }
+        }
+    });
+
+    // Apply the given updates one after another, then refresh both lists
+    let run_updates = {
+        let instance = instance.clone();
+        move |list: Vec<ModUpdate>| {
+            let instance = instance.clone();
+            spawn(async move {
+                busy.set(true);
+                error.set(None);
+                match ModrinthClient::from_settings().await {
+                    Ok(client) => {
+                        for update in &list {
+                            if let Err(e) = mods::apply_update(&client, &instance, update).await {
+                                log::error!("Failed to update {}: {}", update.file_name, e);
+                                error.set(Some(format!("{}: {}", update.file_name, e)));
+                                break;
+                            }
+                        }
+                    }
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+                busy.set(false);
+                updates.restart();
+                on_change.call(());
+            });
+        }
+    };
+
+    rsx! {
+        div { class: "flex flex-col gap-2 p-3 rounded-lg bg-[var(--background-darker)]",
+            match updates() {
+                Some(Ok(list)) if list.is_empty() => rsx! {
+                    p { class: "text-[var(--grey)]", "Everything is up to date" }
+                },
+                Some(Ok(list)) => rsx! {
+                    div { class: "flex items-center gap-2",
+                        p { class: "flex-1", "{list.len()} updates available" }
+                        button {
+                            class: "bg-[var(--green)] text-[var(--background-darker)] p-2 px-3 rounded hover:bg-[var(--green-dark)] cursor-pointer",
+                            disabled: busy(),
+                            onclick: {
+                                let list = list.clone();
+                                let run_updates = run_updates.clone();
+                                move |_| run_updates(list.clone())
+                            },
+                            if busy() { "Updating..." } else { "Update all" }
+                        }
+                    }
+                    for update in list {
+                        div { key: "{update.file_name}", class: "flex flex-col gap-1 p-2 rounded bg-[var(--background-dark)]",
+                            div { class: "flex items-center gap-2",
+                                p { class: "flex-1 truncate",
+                                    span { class: "font-bold", "{update.latest.name}" }
+                                    span { class: "text-[var(--grey)]",
+                                        " {update.current.as_ref().map(|c| c.version_number.as_str()).unwrap_or(&update.file_name)}"
+                                    }
+                                    " → "
+                                    span { class: "text-[var(--green)]", "{update.latest.version_number}" }
+                                }
+                                button {
+                                    class: "bg-[var(--background-light)] p-1 px-3 rounded hover:bg-[var(--background-lighter)] cursor-pointer",
+                                    disabled: busy(),
+                                    onclick: {
+                                        let update = update.clone();
+                                        let run_updates = run_updates.clone();
+                                        move |_| run_updates(vec![update.clone()])
+                                    },
+                                    "Update"
+                                }
+                            }
+                            if let Some(changelog) = update.latest.changelog.as_ref().filter(|c| !c.trim().is_empty()) {
+                                details {
+                                    summary { class: "text-[var(--grey)] text-xs cursor-pointer", "Changelog" }
+                                    p { class: "text-xs whitespace-pre-wrap", "{changelog}" }
+                                }
+                            }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-[var(--red)]", "Failed to check for updates: {e}" }
+                },
+                None => rsx! {
+                    p { class: "text-[var(--grey)]", "Checking for updates..." }
+                },
+            }
+            if let Some(err) = error() {
+                p { class: "text-[var(--red)]", "{err}" }
+            }
+        }
+    }
+}
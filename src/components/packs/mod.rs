@@ -0,0 +1 @@
+pub mod page;
\ No newline at end of file
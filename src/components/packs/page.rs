@@ -0,0 +1,303 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::{
+    Icon,
+    icons::ld_icons::{LdArrowDown, LdArrowLeft, LdArrowUp, LdImage, LdSearch, LdTrash2},
+};
+
+use crate::routes::Route;
+use crate::scripts::instances::{self, Instance};
+use crate::scripts::packs::{self, PackInfo, PackKind};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tab {
+    ResourcePacks,
+    ShaderPacks,
+    DataPacks,
+}
+
+#[component]
+pub fn InstancePacks(id: ReadOnlySignal<String>) -> Element {
+    let mut tab = use_signal(|| Tab::ResourcePacks);
+    let mut world = use_signal(|| None::<String>);
+
+    let instance = use_resource(move || async move {
+        instances::load_instance(&id()).await.map_err(|e| e.to_string())
+    });
+
+    let worlds = use_resource(move || async move {
+        match instance() {
+            Some(Ok(instance)) => packs::list_worlds(&instance).await.unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    });
+
+    let tab_class = |active: bool| {
+        if active {
+            "p-1 px-3 rounded cursor-pointer bg-[var(--background-dark)]"
+        } else {
+            "p-1 px-3 rounded cursor-pointer"
+        }
+    };
+
+    rsx! {
+        div { class: "flex-1 flex flex-col gap-4 p-6",
+            Link {
+                class: "flex items-center gap-1 text-[var(--grey)] hover:text-[var(--foreground)]",
+                to: Route::InstanceDetails { id: id() },
+                Icon { icon: LdArrowLeft, class: "w-4 h-4" }
+                "Instance"
+            }
+            div { class: "flex items-center gap-2",
+                h2 { class: "flex-1", "Packs" }
+                button {
+                    class: tab_class(tab() == Tab::ResourcePacks),
+                    onclick: move |_| tab.set(Tab::ResourcePacks),
+                    "Resource packs"
+                }
+                button {
+                    class: tab_class(tab() == Tab::ShaderPacks),
+                    onclick: move |_| tab.set(Tab::ShaderPacks),
+                    "Shaders"
+                }
+                button {
+                    class: tab_class(tab() == Tab::DataPacks),
+                    onclick: move |_| tab.set(Tab::DataPacks),
+                    "Data packs"
+                }
+                Link {
+                    class: "flex items-center gap-1 bg-[var(--background-dark)] p-2 px-3 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                    to: Route::BrowseMods { id: id() },
+                    Icon { icon: LdSearch, class: "w-4 h-4" }
+                    "Browse"
+                }
+            }
+
+            match instance() {
+                Some(Ok(instance)) => {
+                    let kind = match tab() {
+                        Tab::ResourcePacks => Some(PackKind::ResourcePack),
+                        Tab::ShaderPacks => Some(PackKind::ShaderPack),
+                        Tab::DataPacks => world().map(PackKind::DataPack),
+                    };
+                    let instance_id = instance.id.clone();
+                    rsx! {
+                        if tab() == Tab::DataPacks {
+                            select {
+                                class: "bg-[var(--background-dark)] p-2 rounded",
+                                onchange: move |evt| {
+                                    let value = evt.value();
+                                    world.set(if value.is_empty() { None } else { Some(value) });
+                                },
+                                option { value: "", "Pick a world..." }
+                                for name in worlds().unwrap_or_default() {
+                                    option { key: "{name}", value: "{name}", "{name}" }
+                                }
+                            }
+                        }
+                        if let Some(kind) = kind {
+                            // Remount per tab and world, the list's resource only reads its props once
+                            PackList { key: "{instance_id}-{kind:?}", instance, kind }
+                        }
+                    }
+                }
+                Some(Err(e)) => rsx! {
+                    p { class: "text-[var(--red)]", "Failed to load instance: {e}" }
+                },
+                None => rsx! {
+                    p { class: "text-[var(--grey)]", "Loading..." }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn PackList(instance: Instance, kind: PackKind) -> Element {
+    let mut error = use_signal(|| None::<String>);
+
+    let mut pack_list = use_resource({
+        let instance = instance.clone();
+        let kind = kind.clone();
+        move || {
+            let instance = instance.clone();
+            let kind = kind.clone();
+            async move {
+                let list = packs::list_packs(&instance, &kind).await.map_err(|e| e.to_string())?;
+                let order = match kind {
+                    PackKind::ResourcePack => packs::enabled_resource_packs(&instance)
+                        .await
+                        .map_err(|e| e.to_string())?,
+                    _ => Vec::new(),
+                };
+                Ok::<_, String>((list, order))
+            }
+        }
+    });
+
+    rsx! {
+        input {
+            class: "w-full",
+            r#type: "file",
+            accept: ".zip",
+            multiple: true,
+            onchange: {
+                let instance = instance.clone();
+                let kind = kind.clone();
+                move |evt: FormEvent| {
+                    let instance = instance.clone();
+                    let kind = kind.clone();
+                    async move {
+                        error.set(None);
+                        for file in evt.files() {
+                            let result = match file.read_bytes().await {
+                                Ok(bytes) => {
+                                    packs::install_pack_file(&instance, &kind, &file.name(), &bytes).await
+                                }
+                                Err(e) => Err(anyhow::anyhow!(e.to_string())),
+                            };
+                            if let Err(e) = result {
+                                error.set(Some(format!("{}: {}", file.name(), e)));
+                            }
+                        }
+                        pack_list.restart();
+                    }
+                }
+            },
+        }
+        if let Some(err) = error() {
+            p { class: "text-[var(--red)]", "{err}" }
+        }
+
+        match pack_list() {
+            Some(Ok((list, order))) => {
+                // Enabled resource packs first, in priority order
+                let mut list = list;
+                list.sort_by_key(|p| {
+                    order.iter().position(|o| *o == p.file_name).unwrap_or(usize::MAX)
+                });
+                rsx! {
+                    if list.is_empty() {
+                        p { class: "text-[var(--grey)]", "Nothing installed yet" }
+                    }
+                    div { class: "flex flex-col gap-2",
+                        for pack in list {
+                            PackRow {
+                                key: "{pack.file_name}",
+                                pack,
+                                instance: instance.clone(),
+                                on_change: move |_| pack_list.restart(),
+                            }
+                        }
+                    }
+                }
+            }
+            Some(Err(e)) => rsx! {
+                p { class: "text-[var(--red)]", "Failed to list packs: {e}" }
+            },
+            None => rsx! {
+                p { class: "text-[var(--grey)]", "Loading..." }
+            },
+        }
+    }
+}
+
+#[component]
+fn PackRow(pack: PackInfo, instance: Instance, on_change: EventHandler<()>) -> Element {
+    let is_resource_pack = pack.kind == PackKind::ResourcePack;
+
+    // Run a pack operation, log failures and refresh the list
+    let run = {
+        let instance = instance.clone();
+        let pack = pack.clone();
+        move |action: PackAction| {
+            let instance = instance.clone();
+            let pack = pack.clone();
+            spawn(async move {
+                let result = match action {
+                    PackAction::Toggle => {
+                        packs::set_resource_pack_enabled(&instance, &pack.file_name, !pack.enabled).await
+                    }
+                    PackAction::Move(up) => packs::move_resource_pack(&instance, &pack.file_name, up).await,
+                    PackAction::Delete => packs::delete_pack(&instance, &pack).await,
+                };
+                if let Err(e) = result {
+                    log::error!("Failed to update {}: {}", pack.file_name, e);
+                }
+                on_change.call(());
+            });
+        }
+    };
+
+    rsx! {
+        div {
+            class: "flex items-center gap-3 p-3 rounded-lg bg-[var(--background-dark)]",
+            class: if is_resource_pack && !pack.enabled { "opacity-50" },
+            div { class: "w-10 h-10 shrink-0 rounded bg-[var(--background-darker)] flex items-center justify-center overflow-hidden",
+                if let Some(icon) = &pack.icon {
+                    img { class: "w-full h-full [image-rendering:pixelated]", src: "{icon}" }
+                } else {
+                    Icon { icon: LdImage, class: "w-5 h-5 text-[var(--grey)]" }
+                }
+            }
+            div { class: "flex-1 min-w-0",
+                p { class: "font-bold truncate", "{pack.file_name}" }
+                if let Some(description) = &pack.description {
+                    p { class: "text-xs truncate", "{description}" }
+                }
+                if pack.compatible == Some(false) {
+                    p { class: "text-[var(--yellow)] text-xs",
+                        "Made for a different game version (pack format {pack.pack_format.unwrap_or_default()})"
+                    }
+                }
+            }
+            if is_resource_pack && pack.enabled {
+                button {
+                    class: "p-2 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                    title: "Higher priority",
+                    onclick: {
+                        let run = run.clone();
+                        move |_| run(PackAction::Move(true))
+                    },
+                    Icon { icon: LdArrowUp, class: "w-4 h-4" }
+                }
+                button {
+                    class: "p-2 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                    title: "Lower priority",
+                    onclick: {
+                        let run = run.clone();
+                        move |_| run(PackAction::Move(false))
+                    },
+                    Icon { icon: LdArrowDown, class: "w-4 h-4" }
+                }
+            }
+            if is_resource_pack {
+                input {
+                    r#type: "checkbox",
+                    class: "cursor-pointer",
+                    checked: pack.enabled,
+                    onchange: {
+                        let run = run.clone();
+                        move |_| run(PackAction::Toggle)
+                    },
+                }
+            }
+            button {
+                class: "p-2 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                title: "Delete",
+                onclick: {
+                    let run = run.clone();
+                    move |_| run(PackAction::Delete)
+                },
+                Icon { icon: LdTrash2, class: "w-4 h-4 text-[var(--red)]" }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum PackAction {
+    Toggle,
+    /// `true` moves the pack up
+    Move(bool),
+    Delete,
+}
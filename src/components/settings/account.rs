@@ -0,0 +1,103 @@
+use crate::components::login::modal::LoginModal;
+use crate::components::login::script::{self, TokenRevocation};
+use crate::components::skins::modal::SkinModal;
+use crate::scripts::skins;
+use crate::state::{AUTH, SKIN_REVISION};
+use dioxus::prelude::*;
+
+const STEVE_FACE: Asset = asset!("/assets/images/steve.png");
+
+#[component]
+pub fn AccountPanel() -> Element {
+    let show_login = use_signal(|| false);
+    let mut show_skin = use_signal(|| false);
+    let mut logout_notice = use_signal(|| None::<String>);
+
+    // Front view of the player's skin, rendered from the local cache so it also works offline
+    let body = use_resource(move || async move {
+        SKIN_REVISION();
+        let username = AUTH().user.map(|u| u.username)?;
+        let textures = skins::fetch_textures(&username).await.ok()?;
+        match skins::body_data_url(&textures).await {
+            Ok(url) => url,
+            Err(e) => {
+                log::error!("Failed to render skin: {}", e);
+                None
+            }
+        }
+    });
+
+    rsx! {
+        div { class: "flex flex-col items-center gap-2",
+            if show_login() {
+                LoginModal { show_modal: show_login }
+            }
+            if show_skin() {
+                SkinModal { show_modal: show_skin }
+            }
+
+            if let Some(user) = &AUTH().user {
+                match body() {
+                    Some(Some(url)) => rsx! {
+                        img {
+                            class: "h-48",
+                            style: "image-rendering: pixelated;",
+                            src: "{url}",
+                        }
+                    },
+                    _ => rsx! {
+                        img { class: "w-10 h-10", src: STEVE_FACE }
+                    },
+                }
+                p { class: "text-xl font-bold", "{user.username}" }
+                p { class: "text-sm text-gray-500", "UUID: {user.uuid}" }
+                button {
+                    class: "text-sm text-[var(--grey)] hover:text-[var(--foreground)] cursor-pointer",
+                    onclick: move |_| show_skin.set(true),
+                    "Change Skin"
+                }
+
+                button {
+                    class: "text-sm text-[var(--grey)] hover:text-[var(--foreground)] cursor-pointer",
+                    onclick: move |_| {
+                        spawn(async move {
+                            match script::logout().await {
+                                Ok(revocation) => {
+                                    log::info!("Logged out");
+                                    logout_notice.set(match revocation {
+                                        TokenRevocation::Revoked => None,
+                                        TokenRevocation::Failed(e) => Some(format!(
+                                            "Logged out here, but the auth server couldn't revoke the token ({}). It stays valid until it expires.",
+                                            e
+                                        )),
+                                        TokenRevocation::Unsupported => Some(
+                                            "Logged out here. Ely.by can't revoke the login token, it stays valid until it expires."
+                                                .to_string(),
+                                        ),
+                                    });
+                                }
+                                Err(e) => log::error!("Logout failed: {}", e),
+                            }
+                        });
+                    },
+                    "Log Out"
+                }
+            } else {
+                img { class: "w-10 h-10", src: STEVE_FACE }
+                p { "Not Logged In" }
+                if let Some(notice) = logout_notice() {
+                    p { class: "text-sm text-[var(--yellow)] text-center max-w-xs", "{notice}" }
+                }
+                button {
+                    class: "bg-[var(--background-dark)] text-[var(--text-dark)] p-2 rounded hover:bg-[var(--background-dark)] active:bg-[var(--background-light)] cursor-pointer",
+                    onclick: move |_| {
+                        let mut show_login = show_login.clone();
+                        logout_notice.set(None);
+                        show_login.set(true);
+                    },
+                    "Log In"
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
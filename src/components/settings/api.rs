@@ -0,0 +1,114 @@
+use dioxus::prelude::*;
+
+use crate::scripts::settings::{self, LauncherSettings};
+
+/// Modrinth and CurseForge API base URLs, and the ports the login callback listens on
+#[component]
+pub fn ApiPanel() -> Element {
+    let mut draft = use_signal(LauncherSettings::default);
+    let mut ports = use_signal(String::new);
+    let mut status = use_signal(|| None::<Result<String, String>>);
+
+    use_future(move || async move {
+        match settings::load_settings().await {
+            Ok(loaded) => {
+                ports.set(ports_text(&loaded.callback_ports));
+                draft.set(loaded);
+            }
+            Err(e) => log::error!("Failed to load settings: {}", e),
+        }
+    });
+
+    let input_class = "bg-[var(--background-dark)] p-2 rounded w-full";
+
+    rsx! {
+        div { class: "flex flex-col gap-2 max-w-xl",
+            label { class: "text-[var(--grey)]", "Modrinth API" }
+            input {
+                class: input_class,
+                value: "{draft().modrinth_api}",
+                oninput: move |e| draft.write().modrinth_api = e.value(),
+            }
+            label { class: "text-[var(--grey)]", "CurseForge API" }
+            input {
+                class: input_class,
+                value: "{draft().curseforge_api}",
+                oninput: move |e| draft.write().curseforge_api = e.value(),
+            }
+            label { class: "text-[var(--grey)]",
+                "Login callback ports (each needs http://localhost:<port>/callback allowed in the OAuth app)"
+            }
+            input {
+                class: input_class,
+                placeholder: "23234, 23235",
+                value: "{ports}",
+                oninput: move |e| ports.set(e.value()),
+            }
+            div { class: "flex items-center gap-2",
+                button {
+                    class: "bg-[var(--background-dark)] p-2 px-4 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                    onclick: move |_| async move {
+                        // Only touch the API fields, other panels save the rest
+                        let result = async {
+                            let callback_ports = parse_ports(&ports())?;
+                            let mut saved = settings::load_settings().await?;
+                            saved.modrinth_api = draft().modrinth_api.trim().to_string();
+                            saved.curseforge_api = draft().curseforge_api.trim().to_string();
+                            saved.callback_ports = callback_ports;
+                            settings::save_settings(&saved).await
+                        };
+                        match result.await {
+                            Ok(()) => status.set(Some(Ok("Saved".to_string()))),
+                            Err(e) => status.set(Some(Err(e.to_string()))),
+                        }
+                    },
+                    "Save"
+                }
+                button {
+                    class: "bg-[var(--background-dark)] p-2 px-4 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                    onclick: move |_| {
+                        let defaults = LauncherSettings::default();
+                        draft.write().modrinth_api = defaults.modrinth_api;
+                        draft.write().curseforge_api = defaults.curseforge_api;
+                        ports.set(ports_text(&defaults.callback_ports));
+                    },
+                    "Reset to defaults"
+                }
+                match status() {
+                    Some(Ok(msg)) => rsx! {
+                        p { class: "text-[var(--green)]", "{msg}" }
+                    },
+                    Some(Err(msg)) => rsx! {
+                        p { class: "text-[var(--red)]", "{msg}" }
+                    },
+                    None => rsx! {},
+                }
+            }
+        }
+    }
+}
+
+fn ports_text(ports: &[u16]) -> String {
+    ports
+        .iter()
+        .map(u16::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn parse_ports(text: &str) -> anyhow::Result<Vec<u16>> {
+    let ports = text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            p.parse::<u16>()
+                .ok()
+                .filter(|port| *port > 0)
+                .ok_or_else(|| anyhow::anyhow!("Invalid port: {}", p))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    if ports.is_empty() {
+        return Err(anyhow::anyhow!("Enter at least one callback port"));
+    }
+    Ok(ports)
+}
@@ -0,0 +1,208 @@
+use dioxus::prelude::*;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::scripts::game::java::{JVM_PRESETS, split_command, total_memory_mb};
+use crate::scripts::settings::{self, JavaDefaults};
+
+/// Left to the OS and the launcher when warning about memory settings
+const RESERVED_MEMORY_MB: u64 = 2048;
+
+/// Launcher-wide Java defaults, instances can override them
+#[component]
+pub fn JavaPanel() -> Element {
+    let mut draft = use_signal(JavaDefaults::default);
+    let mut loaded = use_signal(|| false);
+    let mut status = use_signal(|| None::<Result<String, String>>);
+
+    use_future(move || async move {
+        match settings::load_settings().await {
+            Ok(settings) => draft.set(settings.java),
+            Err(e) => log::error!("Failed to load settings: {}", e),
+        }
+        loaded.set(true);
+    });
+
+    // The editors keep their own text, only mount them once the saved values are in
+    if !loaded() {
+        return rsx! {
+            p { class: "text-[var(--grey)]", "Loading..." }
+        };
+    }
+
+    let input_class = "bg-[var(--background-dark)] p-2 rounded w-full";
+    let path = draft().path.map(|p| p.display().to_string()).unwrap_or_default();
+    let wrapper = draft().wrapper.unwrap_or_default();
+    let wrapper_error = split_command(&wrapper).err().map(|e| e.to_string());
+
+    rsx! {
+        div { class: "flex flex-col gap-2 max-w-xl",
+            label { class: "text-[var(--grey)]", "Java executable (empty for the bundled Java 21)" }
+            input {
+                class: input_class,
+                value: "{path}",
+                oninput: move |e| {
+                    let value = e.value();
+                    draft.write().path = (!value.trim().is_empty()).then(|| PathBuf::from(value.trim()));
+                },
+            }
+            MemorySlider {
+                label: "Minimum memory",
+                value: draft().min_memory_mb,
+                on_change: move |mb| draft.write().min_memory_mb = mb,
+            }
+            MemorySlider {
+                label: "Maximum memory",
+                value: draft().max_memory_mb,
+                on_change: move |mb| draft.write().max_memory_mb = mb,
+            }
+            JvmArgsEditor {
+                args: draft().jvm_args,
+                on_change: move |args| draft.write().jvm_args = args,
+            }
+            EnvEditor {
+                env: draft().env,
+                on_change: move |env| draft.write().env = env,
+            }
+            label { class: "text-[var(--grey)]", "Wrapper command" }
+            input {
+                class: input_class,
+                placeholder: "e.g. gamemoderun, mangohud, prime-run",
+                value: "{wrapper}",
+                oninput: move |e| {
+                    let value = e.value();
+                    draft.write().wrapper = (!value.trim().is_empty()).then(|| value.trim().to_string());
+                },
+            }
+            if let Some(error) = wrapper_error {
+                p { class: "text-[var(--red)] text-sm", "{error}, quote paths with spaces like '/path/to/my wrapper'" }
+            }
+            div { class: "flex items-center gap-2",
+                button {
+                    class: "bg-[var(--background-dark)] p-2 px-4 rounded hover:bg-[var(--background-light)] cursor-pointer",
+                    onclick: move |_| async move {
+                        // Only touch the Java fields, other panels save the rest
+                        let result = async {
+                            let mut saved = settings::load_settings().await?;
+                            saved.java = draft();
+                            settings::save_settings(&saved).await
+                        };
+                        match result.await {
+                            Ok(()) => status.set(Some(Ok("Saved".to_string()))),
+                            Err(e) => status.set(Some(Err(e.to_string()))),
+                        }
+                    },
+                    "Save"
+                }
+                match status() {
+                    Some(Ok(msg)) => rsx! {
+                        p { class: "text-[var(--green)]", "{msg}" }
+                    },
+                    Some(Err(msg)) => rsx! {
+                        p { class: "text-[var(--red)]", "{msg}" }
+                    },
+                    None => rsx! {},
+                }
+            }
+        }
+    }
+}
+
+/// Memory in MB, bounded by the RAM of this machine
+#[component]
+pub fn MemorySlider(label: String, value: u32, on_change: EventHandler<u32>) -> Element {
+    let total_mb = use_hook(total_memory_mb);
+    let max = total_mb.max(value as u64);
+
+    rsx! {
+        label { class: "text-[var(--grey)]", "{label}: {value} MB" }
+        input {
+            r#type: "range",
+            min: "512",
+            max: "{max}",
+            step: "256",
+            value: "{value}",
+            oninput: move |e| {
+                if let Ok(mb) = e.value().parse() {
+                    on_change.call(mb);
+                }
+            },
+        }
+        if value as u64 > total_mb.saturating_sub(RESERVED_MEMORY_MB) {
+            p { class: "text-[var(--yellow)] text-sm",
+                "This leaves less than 2 GB of the {total_mb} MB of RAM for the system."
+            }
+        }
+    }
+}
+
+/// Extra JVM arguments separated by whitespace, with presets to add
+#[component]
+pub fn JvmArgsEditor(args: Vec<String>, on_change: EventHandler<Vec<String>>) -> Element {
+    let mut text = use_signal(|| args.join("\n"));
+
+    let mut set_text = move |value: String| {
+        on_change.call(value.split_whitespace().map(str::to_string).collect());
+        text.set(value);
+    };
+
+    rsx! {
+        div { class: "flex items-center gap-2",
+            label { class: "flex-1 text-[var(--grey)]", "JVM arguments" }
+            for (name, preset) in JVM_PRESETS.iter() {
+                button {
+                    class: "bg-[var(--background-dark)] p-1 px-2 rounded hover:bg-[var(--background-light)] cursor-pointer text-sm",
+                    onclick: move |_| {
+                        // Presets are added, arguments already there aren't repeated
+                        let mut value = text();
+                        for arg in preset.iter() {
+                            if !value.split_whitespace().any(|a| a == *arg) {
+                                if !value.is_empty() && !value.ends_with('\n') {
+                                    value.push('\n');
+                                }
+                                value.push_str(arg);
+                            }
+                        }
+                        set_text(value);
+                    },
+                    "+ {name}"
+                }
+            }
+        }
+        textarea {
+            class: "bg-[var(--background-dark)] p-2 rounded w-full h-32 font-mono text-sm",
+            value: "{text}",
+            oninput: move |e| set_text(e.value()),
+        }
+    }
+}
+
+/// Environment variables as `KEY=value` lines
+#[component]
+pub fn EnvEditor(env: BTreeMap<String, String>, on_change: EventHandler<BTreeMap<String, String>>) -> Element {
+    let mut text = use_signal(|| {
+        env.iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+
+    rsx! {
+        label { class: "text-[var(--grey)]", "Environment variables (KEY=value per line)" }
+        textarea {
+            class: "bg-[var(--background-dark)] p-2 rounded w-full h-20 font-mono text-sm",
+            value: "{text}",
+            oninput: move |e| {
+                let value = e.value();
+                let env = value
+                    .lines()
+                    .filter_map(|line| line.split_once('='))
+                    .filter(|(key, _)| !key.trim().is_empty())
+                    .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+                    .collect();
+                on_change.call(env);
+                text.set(value);
+            },
+        }
+    }
+}
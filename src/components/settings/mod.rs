@@ -0,0 +1,4 @@
+pub mod account;
+pub mod api;
+pub mod java;
+pub mod page;
\ No newline at end of file
@@ -0,0 +1,26 @@
+use dioxus::prelude::*;
+
+use crate::components::settings::account::AccountPanel;
+use crate::components::settings::api::ApiPanel;
+use crate::components::settings::java::JavaPanel;
+
+#[component]
+pub fn Settings() -> Element {
+    rsx! {
+        div { class: "flex-1 flex flex-col gap-6 p-6",
+            h2 { "Settings" }
+            section { class: "flex flex-col gap-2",
+                h3 { "Account" }
+                AccountPanel {}
+            }
+            section { class: "flex flex-col gap-2",
+                h3 { "Java" }
+                JavaPanel {}
+            }
+            section { class: "flex flex-col gap-2",
+                h3 { "APIs" }
+                ApiPanel {}
+            }
+        }
+    }
+}
\ No newline at end of file
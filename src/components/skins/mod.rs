@@ -0,0 +1 @@
+pub mod modal;
\ No newline at end of file
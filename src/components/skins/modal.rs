@@ -0,0 +1,186 @@
+use dioxus::prelude::*;
+
+use crate::{
+    scripts::{
+        game::ELY_BY_API,
+        skins::{self, SkinModel},
+    },
+    state,
+};
+
+#[component]
+pub fn SkinModal(show_modal: Signal<bool>) -> Element {
+    let mut model = use_signal(SkinModel::default);
+    let mut skin_data = use_signal(|| None::<Vec<u8>>);
+    let mut status = use_signal(|| None::<Result<String, String>>);
+    let mut busy = use_signal(|| false);
+
+    let api_root = state::AUTH()
+        .session
+        .map(|s| s.server)
+        .unwrap_or_else(|| ELY_BY_API.to_string());
+    let can_upload = skins::supports_upload(&api_root);
+
+    let mut textures = use_resource(move || async move {
+        let username = state::AUTH().user.map(|u| u.username)?;
+        match skins::fetch_textures(&username).await {
+            Ok(textures) => Some(textures),
+            Err(e) => {
+                log::error!("Failed to fetch textures: {}", e);
+                None
+            }
+        }
+    });
+
+    // Start on the current skin's model so re-uploading doesn't switch slim to classic
+    use_effect(move || {
+        if let Some(Some(current)) = textures() {
+            model.set(current.model);
+        }
+    });
+
+    let button_class = "border border-[var(--background-darker)] bg-[var(--background-dark)] text-[var(--text-dark)] p-2 px-4 rounded hover:bg-[var(--background-dark)] active:bg-[var(--background-light)] cursor-pointer";
+
+    rsx! {
+        // Background
+        div {
+            class: "absolute w-full h-full bg-[var(--background-darker)]/50 flex items-center justify-center",
+            onclick: move |_| show_modal.set(false),
+            // Modal
+            div {
+                class: "bg-[var(--background)] p-5 py-10 rounded-lg w-1/3 min-h-1/2 flex flex-col items-center justify-between gap-4",
+                onclick: move |e| e.stop_propagation(),
+                div { class: "flex flex-col gap-2 items-center",
+                    h2 { "Skin" }
+                    match textures() {
+                        Some(Some(current)) => rsx! {
+                            p { "Model: {current.model:?}" }
+                            p {
+                                if current.cape.is_some() {
+                                    "Cape: equipped"
+                                } else {
+                                    "Cape: none"
+                                }
+                            }
+                        },
+                        Some(None) => rsx! {
+                            p { class: "text-[var(--grey)]", "Couldn't load current skin" }
+                        },
+                        None => rsx! {
+                            p { class: "text-[var(--grey)]", "Loading..." }
+                        },
+                    }
+                }
+
+                if can_upload {
+                    div { class: "flex flex-col gap-2 items-center w-full",
+                        input {
+                            class: "w-full",
+                            r#type: "file",
+                            accept: ".png,image/png",
+                            onchange: move |evt| async move {
+                                status.set(None);
+                                let Some(file) = evt.files().into_iter().next() else {
+                                    return;
+                                };
+                                match file.read_bytes().await {
+                                    Ok(bytes) => {
+                                        let bytes = bytes.to_vec();
+                                        // Catch wrong dimensions before the user hits upload
+                                        if let Err(e) = skins::validate_skin(&bytes, model()) {
+                                            status.set(Some(Err(e.to_string())));
+                                        }
+                                        skin_data.set(Some(bytes));
+                                    }
+                                    Err(e) => status.set(Some(Err(e.to_string()))),
+                                }
+                            },
+                        }
+                        select {
+                            class: "bg-[var(--background-dark)] text-[var(--text-dark)] p-2 rounded w-full",
+                            onchange: move |evt| {
+                                match evt.value().as_str() {
+                                    "Slim" => model.set(SkinModel::Slim),
+                                    _ => model.set(SkinModel::Classic),
+                                }
+                            },
+                            option {
+                                value: "Classic",
+                                selected: model() == SkinModel::Classic,
+                                "Classic (4px arms)"
+                            }
+                            option {
+                                value: "Slim",
+                                selected: model() == SkinModel::Slim,
+                                "Slim (3px arms)"
+                            }
+                        }
+                        match status() {
+                            Some(Ok(msg)) => rsx! {
+                                p { class: "text-[var(--green)] text-center", "{msg}" }
+                            },
+                            Some(Err(msg)) => rsx! {
+                                p { class: "text-[var(--red)] text-center", "{msg}" }
+                            },
+                            None => rsx! {},
+                        }
+                    }
+
+                    button {
+                        class: button_class,
+                        disabled: busy() || skin_data().is_none(),
+                        onclick: move |_| {
+                            let api_root = api_root.clone();
+                            async move {
+                                let auth = state::AUTH();
+                                let (Some(user), Some(data)) = (auth.user, skin_data()) else {
+                                    return;
+                                };
+                                busy.set(true);
+                                match skins::upload_skin(&api_root, &user.uuid, &user.access_token, data, model())
+                                    .await
+                                {
+                                    Ok(_) => {
+                                        log::info!("Skin uploaded");
+                                        status.set(Some(Ok("Skin uploaded".to_string())));
+                                        textures.restart();
+                                        *state::SKIN_REVISION.write() += 1;
+                                    }
+                                    Err(e) => {
+                                        log::error!("Skin upload failed: {}", e);
+                                        status.set(Some(Err(e.to_string())));
+                                    }
+                                }
+                                busy.set(false);
+                            }
+                        },
+                        if busy() { "Uploading..." } else { "Upload" }
+                    }
+                } else {
+                    p { class: "text-center",
+                        "Ely.by skins are changed on the Ely.by website, the launcher picks the new one up afterwards."
+                    }
+                    div { class: "flex gap-2",
+                        button {
+                            class: button_class,
+                            onclick: move |_| {
+                                if let Err(e) = webbrowser::open(skins::ELY_BY_SKINS_PAGE) {
+                                    log::error!("Failed to open browser: {}", e);
+                                }
+                            },
+                            "Open Ely.by skins"
+                        }
+                        button {
+                            class: button_class,
+                            onclick: move |_| {
+                                textures.restart();
+                                *state::SKIN_REVISION.write() += 1;
+                            },
+                            "Refresh"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
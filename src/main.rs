@@ -8,13 +8,18 @@ mod consts;
 mod css;
 mod fonts;
 mod logger;
+mod routes;
 mod scripts;
 mod secrets;
 mod state;
 
-use crate::components::main_layout::MainLayout;
+use crate::components::instance::script::play;
+use crate::components::login::{script, yggdrasil};
 use crate::css::LoadCSS;
 use crate::fonts::LoadFonts;
+use crate::routes::Route;
+use crate::scripts::instances;
+use crate::scripts::quickplay::LaunchArgs;
 use crate::scripts::window_size_center::set_window_size_and_center;
 
 fn main() {
@@ -36,10 +41,47 @@ fn main() {
 fn App() -> Element {
     set_window_size_and_center();
 
+    // Restore the persisted Yggdrasil session or OAuth login, refreshing it if needed
+    use_future(|| async {
+        match yggdrasil::load_session().await {
+            Ok(Some(session)) => match yggdrasil::ensure_valid(&session).await {
+                Ok(session) => {
+                    log::info!("Restored session for {}", session.profile.name);
+                    let mut auth = state::AUTH.write();
+                    auth.token = Some(session.access_token.clone());
+                    auth.user = Some(session.user_info());
+                    auth.session = Some(session);
+                }
+                Err(e) => log::error!("Saved session is no longer valid: {}", e),
+            },
+            Ok(None) => match script::restore_oauth().await {
+                Ok(Some(user)) => {
+                    log::info!("Restored login for {}", user.username);
+                    let mut auth = state::AUTH.write();
+                    auth.token = Some(user.access_token.clone());
+                    auth.user = Some(user);
+                }
+                Ok(None) => {}
+                Err(e) => log::error!("Saved login is no longer valid: {}", e),
+            },
+            Err(e) => log::error!("Failed to load saved session: {}", e),
+        }
+
+        // Started from a shortcut: `--launch <id> [--server <address> | --world <folder> | --realm <id>]`
+        if let Some(args) = LaunchArgs::parse(std::env::args().skip(1)) {
+            match instances::load_instance(&args.instance_id).await {
+                Ok(instance) => {
+                    spawn_forever(play(instance, args.quick_play));
+                }
+                Err(e) => log::error!("Failed to load instance {}: {}", args.instance_id, e),
+            }
+        }
+    });
+
     rsx! {
         document::Link { rel: "icon", href: consts::FAVICON }
         LoadCSS {}
         LoadFonts {}
-        MainLayout {}
+        Router::<Route> {}
     }
 }
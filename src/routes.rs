@@ -0,0 +1,36 @@
+use dioxus::prelude::*;
+
+use crate::components::{
+    browse::page::BrowseMods,
+    console::page::{InstanceConsole, LauncherConsole, RunConsole},
+    instance::page::InstanceDetails,
+    library::page::Library,
+    main_layout::MainLayout,
+    mods::page::InstanceMods,
+    packs::page::InstancePacks,
+    settings::page::Settings,
+};
+
+#[derive(Debug, Clone, PartialEq, Routable)]
+#[rustfmt::skip]
+pub enum Route {
+    #[layout(MainLayout)]
+        #[route("/")]
+        Library {},
+        #[route("/instance/:id")]
+        InstanceDetails { id: String },
+        #[route("/instance/:id/mods")]
+        InstanceMods { id: String },
+        #[route("/instance/:id/packs")]
+        InstancePacks { id: String },
+        #[route("/instance/:id/browse")]
+        BrowseMods { id: String },
+        #[route("/instance/:id/console")]
+        InstanceConsole { id: String },
+        #[route("/games/:run/console")]
+        RunConsole { run: u64 },
+        #[route("/console")]
+        LauncherConsole {},
+        #[route("/settings")]
+        Settings {},
+}
\ No newline at end of file
@@ -0,0 +1,250 @@
+pub mod rules;
+pub mod types;
+
+use crate::scripts::instances::Instance;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+pub use types::{CrashAnalysis, CrashReport, Diagnosis, ReportKind};
+
+/// Find the report the run left behind (if any) and match it and the log against known causes
+pub async fn analyze_crash(
+    instance: &Instance,
+    started_at: u64,
+    exit_code: Option<i32>,
+    log_tail: &str,
+) -> CrashAnalysis {
+    let report = match find_report(&instance.game_dir(), started_at).await {
+        Ok(Some((path, kind))) => match read_report(&path, kind).await {
+            Ok(report) => Some(report),
+            Err(e) => {
+                log::warn!("Failed to read crash report {}: {}", path.display(), e);
+                None
+            }
+        },
+        Ok(None) => None,
+        Err(e) => {
+            log::warn!("Failed to look for crash reports: {}", e);
+            None
+        }
+    };
+
+    // The report first, the log often has the actual cause when the game died before writing one
+    let mut diagnoses = report
+        .as_ref()
+        .map(|r| rules::diagnose(&r.content))
+        .unwrap_or_default();
+    for diagnosis in rules::diagnose(log_tail) {
+        if !diagnoses.iter().any(|d| d.title == diagnosis.title) {
+            diagnoses.push(diagnosis);
+        }
+    }
+
+    CrashAnalysis {
+        exit_code,
+        report,
+        diagnoses,
+    }
+}
+
+/// Newest crash report or JVM error log written since `since` (unix seconds)
+async fn find_report(game_dir: &Path, since: u64) -> Result<Option<(PathBuf, ReportKind)>> {
+    let since = UNIX_EPOCH + Duration::from_secs(since);
+    let mut candidates = Vec::new();
+
+    let crash_dir = game_dir.join("crash-reports");
+    if crash_dir.exists() {
+        let mut entries = tokio::fs::read_dir(&crash_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_name().to_string_lossy().ends_with(".txt") {
+                candidates.push((
+                    entry.path(),
+                    ReportKind::Minecraft,
+                    entry.metadata().await?.modified()?,
+                ));
+            }
+        }
+    }
+
+    // The JVM writes hs_err files to its working directory, the game dir
+    let mut entries = tokio::fs::read_dir(game_dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with("hs_err_pid") && name.ends_with(".log") {
+            candidates.push((
+                entry.path(),
+                ReportKind::JvmFatal,
+                entry.metadata().await?.modified()?,
+            ));
+        }
+    }
+
+    Ok(candidates
+        .into_iter()
+        .filter(|(_, _, modified)| *modified >= since)
+        .max_by_key(|(_, _, modified)| *modified)
+        .map(|(path, kind, _)| (path, kind)))
+}
+
+pub async fn read_report(path: &Path, kind: ReportKind) -> Result<CrashReport> {
+    let bytes = tokio::fs::read(path).await?;
+    let content = String::from_utf8_lossy(&bytes).to_string();
+
+    let (description, stack_trace, suspected_mods) = match kind {
+        ReportKind::Minecraft => parse_minecraft_report(&content),
+        ReportKind::JvmFatal => parse_hs_err(&content),
+    };
+
+    Ok(CrashReport {
+        path: path.to_path_buf(),
+        kind,
+        description,
+        stack_trace,
+        suspected_mods,
+        content,
+    })
+}
+
+/// Description, the first stack trace and the suspected mods of a `crash-*.txt`
+fn parse_minecraft_report(content: &str) -> (Option<String>, Vec<String>, Vec<String>) {
+    let mut lines = content.lines().peekable();
+    let mut description = None;
+    let mut stack_trace = Vec::new();
+    let mut suspected_mods = Vec::new();
+
+    while let Some(line) = lines.next() {
+        if description.is_none() {
+            if let Some(text) = line.strip_prefix("Description:") {
+                description = Some(text.trim().to_string());
+
+                // Blank line, then the exception and its frames up to the next blank line
+                while lines.peek().is_some_and(|l| l.trim().is_empty()) {
+                    lines.next();
+                }
+                while let Some(frame) = lines.next_if(|l| !l.trim().is_empty()) {
+                    stack_trace.push(frame.trim_end().to_string());
+                }
+                continue;
+            }
+        }
+
+        // Forge: `Suspected Mods: NONE` or the mods on the following indented lines,
+        // Fabric: `Suspected mods:` followed by the same
+        let trimmed = line.trim();
+        if suspected_mods.is_empty() && trimmed.to_lowercase().starts_with("suspected mod") {
+            let inline = trimmed
+                .split_once(':')
+                .map(|(_, rest)| rest.trim())
+                .unwrap_or_default();
+            if !inline.is_empty() {
+                if !inline.eq_ignore_ascii_case("none") {
+                    suspected_mods = inline.split(',').map(|m| m.trim().to_string()).collect();
+                }
+            } else {
+                while let Some(entry) =
+                    lines.next_if(|l| l.starts_with([' ', '\t']) && !l.trim().is_empty())
+                {
+                    suspected_mods.push(entry.trim().to_string());
+                }
+            }
+        }
+    }
+
+    (description, stack_trace, suspected_mods)
+}
+
+/// The error line and the problematic frame of an `hs_err_pid*.log`
+fn parse_hs_err(content: &str) -> (Option<String>, Vec<String>, Vec<String>) {
+    let lines: Vec<&str> = content.lines().collect();
+
+    // `#  EXCEPTION_ACCESS_VIOLATION (0xc0000005) at pc=...` or `#  SIGSEGV (0xb) at pc=...`
+    let description = lines
+        .iter()
+        .skip_while(|l| !l.contains("A fatal error has been detected"))
+        .skip(1)
+        .map(|l| l.trim_start_matches('#').trim())
+        .find(|l| !l.is_empty())
+        .map(str::to_string);
+
+    let stack_trace = lines
+        .iter()
+        .position(|l| l.contains("Problematic frame:"))
+        .and_then(|i| lines.get(i + 1))
+        .map(|frame| vec![frame.trim_start_matches('#').trim().to_string()])
+        .unwrap_or_default();
+
+    (description, stack_trace, Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forge_crash_reports_parse() {
+        let report = "---- Minecraft Crash Report ----
+// Who set us up the TNT?
+
+Time: 2024-08-10 12:00:00
+Description: Rendering overlay
+
+java.lang.NullPointerException: Cannot invoke \"Object.toString()\" because \"value\" is null
+\tat com.example.Overlay.render(Overlay.java:42)
+\tat net.minecraft.client.renderer.GameRenderer.render(GameRenderer.java:1)
+
+A detailed walkthrough of the error, its code path and all known details is as follows:
+---------------------------------------------------------------------------------------
+
+-- Head --
+Thread: Render thread
+Suspected Mods: 
+\tExample Mod (examplemod), Version: 1.0
+\tOther Mod (othermod), Version: 2.0
+Stacktrace:
+";
+        let (description, stack_trace, suspected_mods) = parse_minecraft_report(report);
+        assert_eq!(description.as_deref(), Some("Rendering overlay"));
+        assert_eq!(
+            stack_trace,
+            [
+                "java.lang.NullPointerException: Cannot invoke \"Object.toString()\" because \"value\" is null",
+                "\tat com.example.Overlay.render(Overlay.java:42)",
+                "\tat net.minecraft.client.renderer.GameRenderer.render(GameRenderer.java:1)",
+            ]
+        );
+        assert_eq!(
+            suspected_mods,
+            ["Example Mod (examplemod), Version: 1.0", "Other Mod (othermod), Version: 2.0"]
+        );
+    }
+
+    #[test]
+    fn inline_suspected_mods_parse() {
+        let (_, _, none) = parse_minecraft_report("Description: Ticking entity\n\nSuspected Mods: NONE\n");
+        assert!(none.is_empty());
+        let (_, _, mods) = parse_minecraft_report("Suspected mods: sodium, iris\n");
+        assert_eq!(mods, ["sodium", "iris"]);
+    }
+
+    #[test]
+    fn jvm_fatal_errors_parse() {
+        let log = "#
+# A fatal error has been detected by the Java Runtime Environment:
+#
+#  EXCEPTION_ACCESS_VIOLATION (0xc0000005) at pc=0x00007ffd1a2b3c4d, pid=1234, tid=5678
+#
+# JRE version: OpenJDK Runtime Environment Temurin-21.0.9+10 (21.0.9+10) (build 21.0.9+10-LTS)
+# Problematic frame:
+# C  [atio6axx.dll+0x1a2b3c]
+#
+";
+        let (description, stack_trace, suspected_mods) = parse_hs_err(log);
+        assert_eq!(
+            description.as_deref(),
+            Some("EXCEPTION_ACCESS_VIOLATION (0xc0000005) at pc=0x00007ffd1a2b3c4d, pid=1234, tid=5678")
+        );
+        assert_eq!(stack_trace, ["C  [atio6axx.dll+0x1a2b3c]"]);
+        assert!(suspected_mods.is_empty());
+        assert_eq!(rules::diagnose(log)[0].title, "Graphics driver crash");
+    }
+}
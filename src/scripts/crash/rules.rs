@@ -0,0 +1,200 @@
+use super::types::Diagnosis;
+
+/// A known crash cause, matched when any of `patterns` appears in a line
+pub struct Rule {
+    pub patterns: &'static [&'static str],
+    pub title: &'static str,
+    pub explanation: &'static str,
+    pub fixes: &'static [&'static str],
+}
+
+pub const RULES: &[Rule] = &[
+    Rule {
+        patterns: &[
+            "UnsupportedClassVersionError",
+            "has been compiled by a more recent version of the Java Runtime",
+            "only recognizes class file versions up to",
+            "Unsupported class file major version",
+        ],
+        title: "Wrong Java version",
+        explanation: "The game or a mod was built for another Java version than the one it was started with.",
+        fixes: &[
+            "Use Java 21 for Minecraft 1.20.5 and newer, Java 17 for 1.18 to 1.20.4 and Java 8 for 1.16 and older",
+        ],
+    },
+    Rule {
+        patterns: &[
+            "Missing or unsupported mandatory dependencies",
+            "Could not find required mod",
+            "which is missing!",
+            "requires any version of",
+            "ModResolutionException",
+        ],
+        title: "Missing dependency",
+        explanation: "A mod needs another mod (or another version of it) that isn't installed.",
+        fixes: &[
+            "Install the mod named in the error from the Browse page",
+            "Check the Issues panel on the Mods page",
+        ],
+    },
+    Rule {
+        patterns: &[
+            "DuplicateModsFoundException",
+            "Found duplicate mods",
+            "Duplicate mods found",
+        ],
+        title: "Duplicate mods",
+        explanation: "The same mod is installed more than once, usually two versions of one jar.",
+        fixes: &["Remove the older copy from the Mods page"],
+    },
+    Rule {
+        patterns: &[
+            "java.lang.OutOfMemoryError",
+            "Could not reserve enough space for object heap",
+        ],
+        title: "Out of memory",
+        explanation: "The game ran out of the memory it was allowed to use, or couldn't reserve it at startup.",
+        fixes: &[
+            "Raise the maximum memory of the instance (4-6 GB is enough for most modpacks)",
+            "If it failed at startup, lower the maximum memory below what the system has free",
+        ],
+    },
+    Rule {
+        patterns: &[
+            "GLFW error 65542",
+            "GLFW error 65543",
+            "Pixel format not accelerated",
+            "WGL: The driver does not appear to support OpenGL",
+            "No OpenGL context",
+            "Failed to create window",
+        ],
+        title: "Graphics driver can't create the window",
+        explanation: "OpenGL isn't available, usually because the GPU driver is missing or the game runs on the wrong GPU.",
+        fixes: &[
+            "Install the latest driver from your GPU vendor",
+            "On laptops, force the game's Java to use the dedicated GPU",
+        ],
+    },
+    Rule {
+        patterns: &[
+            "atio6axx.dll",
+            "atioglxx.dll",
+            "nvoglv64.dll",
+            "ig9icd64.dll",
+            "ig7icd64.dll",
+            "libnvidia-glcore",
+        ],
+        title: "Graphics driver crash",
+        explanation: "The JVM crashed inside the graphics driver.",
+        fixes: &[
+            "Update or reinstall the graphics driver",
+            "Remove shader packs and rendering mods to see if one triggers it",
+        ],
+    },
+    Rule {
+        patterns: &[
+            "Mixin apply failed",
+            "MixinApplyError",
+            "InvalidInjectionException",
+            "Mixin transformation of",
+            "MixinTransformerError",
+        ],
+        title: "Mixin failed to apply",
+        explanation: "A mod failed to patch game code, usually because it's made for another game version or clashes with another mod.",
+        fixes: &[
+            "The mixin config in the error (modid.mixins.json) names the mod, update or remove it",
+            "Make sure the mod is built for this game and loader version",
+        ],
+    },
+];
+
+/// Every rule that matches `text`, once each, with the first matching line
+pub fn diagnose(text: &str) -> Vec<Diagnosis> {
+    RULES
+        .iter()
+        .filter_map(|rule| {
+            let line = text
+                .lines()
+                .find(|line| rule.patterns.iter().any(|p| line.contains(p)))?;
+            Some(Diagnosis {
+                title: rule.title,
+                explanation: rule.explanation,
+                fixes: rule.fixes,
+                evidence: line.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn titles(log: &str) -> Vec<&'static str> {
+        diagnose(log).into_iter().map(|d| d.title).collect()
+    }
+
+    #[test]
+    fn each_rule_matches_its_log() {
+        let cases = [
+            (
+                "Exception in thread \"main\" java.lang.UnsupportedClassVersionError: net/minecraft/client/main/Main has been compiled by a more recent version of the Java Runtime (class file version 65.0), this version of the Java Runtime only recognizes class file versions up to 61.0",
+                "Wrong Java version",
+            ),
+            (
+                "java.lang.IllegalArgumentException: Unsupported class file major version 65",
+                "Wrong Java version",
+            ),
+            (
+                "net.fabricmc.loader.impl.FormattedException: Mod resolution encountered an incompatible mod set!\n - Mod 'Sodium Extra' (sodium-extra) 0.5.4 requires any version of sodium, which is missing!",
+                "Missing dependency",
+            ),
+            (
+                "[main/ERROR] [net.neoforged.fml.loading.moddiscovery.ModDiscoverer/SCAN]: Missing or unsupported mandatory dependencies:\n\tMod ID: 'architectury', Requested by: 'rei'",
+                "Missing dependency",
+            ),
+            (
+                "[main/ERROR] [net.neoforged.fml.loading.moddiscovery.ModSorter/LOADING]: Found duplicate mods:\n\tMod ID: 'sodium' from mod files: sodium-0.5.8.jar, sodium-0.6.0.jar",
+                "Duplicate mods",
+            ),
+            (
+                "Error occurred during initialization of VM\nCould not reserve enough space for object heap",
+                "Out of memory",
+            ),
+            (
+                "Exception in thread \"Render thread\" java.lang.OutOfMemoryError: Java heap space",
+                "Out of memory",
+            ),
+            (
+                "[Render thread/ERROR]: GLFW error 65542: WGL: The driver does not appear to support OpenGL",
+                "Graphics driver can't create the window",
+            ),
+            (
+                "# C  [atio6axx.dll+0x1a2b3c]",
+                "Graphics driver crash",
+            ),
+            (
+                "org.spongepowered.asm.mixin.transformer.throwables.MixinTransformerError: An unexpected critical error was encountered\nCaused by: org.spongepowered.asm.mixin.injection.throwables.InvalidInjectionException: Critical injection failure",
+                "Mixin failed to apply",
+            ),
+        ];
+        for (log, title) in cases {
+            assert_eq!(titles(log), [title], "{}", log);
+        }
+        assert_eq!(RULES.len(), 7, "add a case for the new rule");
+    }
+
+    #[test]
+    fn dependency_messages_about_java_are_not_a_java_crash() {
+        let log = "[main/INFO]: Loading 112 mods\n[main/WARN]: Mod 'Iris' (iris) 1.7.0 requires Java 17 or later, which is present";
+        assert!(titles(log).is_empty());
+    }
+
+    #[test]
+    fn the_first_matching_line_is_the_evidence() {
+        let log = "[main/INFO]: Starting\n  java.lang.OutOfMemoryError: Java heap space  \njava.lang.OutOfMemoryError: again";
+        let diagnoses = diagnose(log);
+        assert_eq!(diagnoses.len(), 1);
+        assert_eq!(diagnoses[0].evidence, "java.lang.OutOfMemoryError: Java heap space");
+    }
+}
@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportKind {
+    /// `crash-reports/crash-*.txt` written by the game
+    Minecraft,
+    /// `hs_err_pid*.log` written by the JVM when it dies in native code
+    JvmFatal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrashReport {
+    pub path: PathBuf,
+    pub kind: ReportKind,
+    pub description: Option<String>,
+    pub stack_trace: Vec<String>,
+    pub suspected_mods: Vec<String>,
+    pub content: String,
+}
+
+/// A known cause matched in the report or the game log
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnosis {
+    pub title: &'static str,
+    pub explanation: &'static str,
+    pub fixes: &'static [&'static str],
+    /// The line that matched
+    pub evidence: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrashAnalysis {
+    pub exit_code: Option<i32>,
+    pub report: Option<CrashReport>,
+    pub diagnoses: Vec<Diagnosis>,
+}
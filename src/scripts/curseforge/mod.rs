@@ -0,0 +1,63 @@
+pub mod types;
+
+use crate::secrets;
+use anyhow::Result;
+use reqwest::Client;
+use serde_json::json;
+pub use types::{File, Mod};
+
+pub const CURSEFORGE_API: &str = "https://api.curseforge.com";
+
+/// Class ids of the project categories we place outside `mods/`
+pub const CLASS_RESOURCE_PACKS: u64 = 12;
+pub const CLASS_SHADERS: u64 = 6552;
+
+/// Client for the CurseForge API or anything compatible with it (e.g. a local mock)
+#[derive(Debug, Clone)]
+pub struct CurseForgeClient {
+    base_url: String,
+    api_key: String,
+    http: Client,
+}
+
+impl CurseForgeClient {
+    pub fn new(base_url: &str, api_key: &str) -> Result<Self> {
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+            http: Client::builder().user_agent("ezLauncher/0.2.0").build()?,
+        })
+    }
+
+    /// Client for the API base configured in the launcher settings, with the key from `secrets.rs`
+    pub async fn from_settings() -> Result<Self> {
+        let settings = crate::scripts::settings::load_settings().await?;
+        Self::new(&settings.curseforge_api, secrets::CURSEFORGE_API_KEY)
+    }
+
+    pub fn http(&self) -> &Client {
+        &self.http
+    }
+
+    pub async fn get_files(&self, file_ids: &[u64]) -> Result<Vec<File>> {
+        self.post("/v1/mods/files", json!({ "fileIds": file_ids })).await
+    }
+
+    pub async fn get_mods(&self, mod_ids: &[u64]) -> Result<Vec<Mod>> {
+        self.post("/v1/mods", json!({ "modIds": mod_ids })).await
+    }
+
+    async fn post<T: serde::de::DeserializeOwned>(&self, path: &str, body: serde_json::Value) -> Result<T> {
+        let response: types::DataResponse<T> = self
+            .http
+            .post(format!("{}{}", self.base_url, path))
+            .header("x-api-key", &self.api_key)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(response.data)
+    }
+}
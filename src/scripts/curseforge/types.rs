@@ -0,0 +1,53 @@
+use serde::Deserialize;
+
+/// CurseForge wraps every response in `{ "data": ... }`
+#[derive(Debug, Deserialize)]
+pub struct DataResponse<T> {
+    pub data: T,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct File {
+    pub id: u64,
+    pub mod_id: u64,
+    pub file_name: String,
+    /// `None` when the author disallowed third party distribution
+    pub download_url: Option<String>,
+    pub file_length: u64,
+    #[serde(default)]
+    pub hashes: Vec<FileHash>,
+}
+
+impl File {
+    pub fn sha1(&self) -> Option<&str> {
+        self.hashes
+            .iter()
+            .find(|h| h.algo == HASH_ALGO_SHA1)
+            .map(|h| h.value.as_str())
+    }
+}
+
+pub const HASH_ALGO_SHA1: u32 = 1;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FileHash {
+    pub value: String,
+    pub algo: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Mod {
+    pub id: u64,
+    pub name: String,
+    pub slug: String,
+    pub class_id: Option<u64>,
+    pub links: Option<ModLinks>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModLinks {
+    pub website_url: Option<String>,
+}
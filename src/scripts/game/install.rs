@@ -1,31 +1,81 @@
 use super::types::*;
-use super::utils::{check_rules, download_file, extract_natives};
-use super::{AUTHLIB_INJECTOR_URL, MC_VERSION, NEOFORGE_VERSION, VERSION_MANIFEST_URL};
+use super::utils::{FileHash, check_rules, download_file, download_file_verified, extract_natives};
+use super::{AUTHLIB_INJECTOR_URL, FABRIC_META_URL, QUILT_META_URL, VERSION_MANIFEST_URL};
+use crate::scripts::instances::Instance;
 use anyhow::Result;
 use reqwest::Client;
 use std::path::{Path, PathBuf};
 
+/// First version whose manifest has `arguments`, older ones only have the legacy `minecraftArguments`
+const FIRST_ARGUMENTS_VERSION: &str = "17w43a";
+
+/// Versions the launch command can be built for, newest first
+pub async fn launchable_versions() -> Result<Vec<VersionEntry>> {
+    let client = Client::builder().user_agent("ezLauncher/0.2.0").build()?;
+    let index: VersionManifestIndex = client
+        .get(VERSION_MANIFEST_URL)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    launchable(index.versions)
+}
+
+fn launchable(versions: Vec<VersionEntry>) -> Result<Vec<VersionEntry>> {
+    let cutoff = versions
+        .iter()
+        .find(|v| v.id == FIRST_ARGUMENTS_VERSION)
+        .map(|v| v.release_time.clone())
+        .ok_or_else(|| anyhow::anyhow!("Version {} missing from the manifest", FIRST_ARGUMENTS_VERSION))?;
+    Ok(versions
+        .into_iter()
+        .filter(|v| v.release_time >= cutoff)
+        .collect())
+}
+
+/// Install everything `instance` needs into the shared store (libraries, assets, versions)
 pub async fn install_minecraft(
     base_dir: &Path,
     java_path: &Path,
-    version_type: VersionType,
+    instance: &Instance,
 ) -> Result<(PathBuf, VersionManifest)> {
     let mc_dir = base_dir.join("minecraft");
+    let game_version = instance.game_version.as_str();
 
     // Always install vanilla base first
-    install_vanilla_base(base_dir).await?;
+    install_vanilla_base(base_dir, game_version).await?;
+    let vanilla_path = mc_dir
+        .join("versions")
+        .join(game_version)
+        .join(format!("{}.json", game_version));
+    let vanilla: VersionManifest = serde_json::from_str(&tokio::fs::read_to_string(&vanilla_path).await?)?;
+    if vanilla.arguments.is_none() {
+        return Err(anyhow::anyhow!(
+            "Minecraft {} is older than 1.13, which this launcher can't start",
+            game_version
+        ));
+    }
 
-    let manifest_path = match version_type {
-        VersionType::Vanilla => mc_dir
-            .join("versions")
-            .join(MC_VERSION)
-            .join(format!("{}.json", MC_VERSION)),
-        VersionType::NeoForge => {
-            install_neoforge(base_dir, java_path).await?;
+    let manifest_path = match (instance.loader, &instance.loader_version) {
+        (VersionType::Vanilla, _) => vanilla_path,
+        (VersionType::NeoForge, Some(neoforge_version)) => {
+            install_neoforge(base_dir, java_path, neoforge_version).await?;
             mc_dir
                 .join("versions")
-                .join(format!("neoforge-{}", NEOFORGE_VERSION))
-                .join(format!("neoforge-{}.json", NEOFORGE_VERSION))
+                .join(format!("neoforge-{}", neoforge_version))
+                .join(format!("neoforge-{}.json", neoforge_version))
+        }
+        (VersionType::Fabric | VersionType::Quilt, Some(loader_version)) => {
+            let id = install_fabric_like(base_dir, instance.loader, game_version, loader_version).await?;
+            mc_dir.join("versions").join(&id).join(format!("{}.json", id))
+        }
+        (loader, None) => {
+            return Err(anyhow::anyhow!(
+                "Instance {} has no {:?} version set",
+                instance.name,
+                loader
+            ));
         }
     };
 
@@ -75,6 +125,10 @@ pub async fn install_minecraft(
             if manifest.asset_index.is_none() {
                 manifest.asset_index = parent_manifest.asset_index;
             }
+            // Inherit the log configuration, loaders don't ship their own
+            if manifest.logging.is_none() {
+                manifest.logging = parent_manifest.logging;
+            }
         }
     }
 
@@ -84,14 +138,17 @@ pub async fn install_minecraft(
     // Download assets
     download_assets(&mc_dir, &manifest).await?;
 
+    // Download the Log4j config so the game logs XML events we can parse
+    download_log_config(&mc_dir, &manifest).await?;
+
     Ok((mc_dir, manifest))
 }
 
-async fn install_vanilla_base(base_dir: &Path) -> Result<()> {
+async fn install_vanilla_base(base_dir: &Path, game_version: &str) -> Result<()> {
     let mc_dir = base_dir.join("minecraft");
-    let version_dir = mc_dir.join("versions").join(MC_VERSION);
-    let version_json_path = version_dir.join(format!("{}.json", MC_VERSION));
-    let client_jar_path = version_dir.join(format!("{}.jar", MC_VERSION));
+    let version_dir = mc_dir.join("versions").join(game_version);
+    let version_json_path = version_dir.join(format!("{}.json", game_version));
+    let client_jar_path = version_dir.join(format!("{}.jar", game_version));
 
     let client = Client::builder().user_agent("ezLauncher/0.2.0").build()?;
 
@@ -109,11 +166,11 @@ async fn install_vanilla_base(base_dir: &Path) -> Result<()> {
         let version_entry = manifest_index
             .versions
             .iter()
-            .find(|v| v.id == MC_VERSION)
-            .ok_or_else(|| anyhow::anyhow!("Version {} not found", MC_VERSION))?;
+            .find(|v| v.id == game_version)
+            .ok_or_else(|| anyhow::anyhow!("Version {} not found", game_version))?;
 
         // Step 3: Download version-specific JSON
-        log::info!("Downloading version manifest for {}...", MC_VERSION);
+        log::info!("Downloading version manifest for {}...", game_version);
         let version_json: String = client.get(&version_entry.url).send().await?.text().await?;
 
         // Save version JSON
@@ -155,16 +212,16 @@ async fn download_libraries_and_natives(mc_dir: &Path, manifest: &VersionManifes
             }
         }
 
-        if let Some(downloads) = &library.downloads {
-            // Download main artifact
-            if let Some(artifact) = &downloads.artifact {
-                let lib_path = lib_dir.join(&artifact.path);
-                if !lib_path.exists() {
-                    log::info!("Downloading library: {}", library.name);
-                    download_file(&client, &artifact.url, &lib_path).await?;
-                }
+        // Download main artifact
+        if let Some(artifact) = library.artifact() {
+            let lib_path = lib_dir.join(&artifact.path);
+            if !lib_path.exists() {
+                log::info!("Downloading library: {}", library.name);
+                download_file(&client, &artifact.url, &lib_path).await?;
             }
+        }
 
+        if let Some(downloads) = &library.downloads {
             // Download natives if present
             if let Some(natives) = &library.natives {
                 let os_key = if cfg!(target_os = "windows") {
@@ -269,12 +326,38 @@ async fn download_assets(mc_dir: &Path, manifest: &VersionManifest) -> Result<()
     Ok(())
 }
 
-async fn install_neoforge(base_dir: &Path, java_path: &Path) -> Result<()> {
+async fn download_log_config(mc_dir: &Path, manifest: &VersionManifest) -> Result<()> {
+    let Some(config) = manifest.logging.as_ref().and_then(Logging::client_xml) else {
+        return Ok(());
+    };
+
+    let path = log_config_path(mc_dir, config);
+    if !path.exists() {
+        log::info!("Downloading log config {}...", config.file.id);
+        let client = Client::builder().user_agent("ezLauncher/0.2.0").build()?;
+        download_file_verified(
+            &client,
+            &config.file.url,
+            &path,
+            FileHash::Sha1(&config.file.sha1),
+            Some(config.file.size),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub fn log_config_path(mc_dir: &Path, config: &LoggingConfig) -> PathBuf {
+    mc_dir.join("assets").join("log_configs").join(&config.file.id)
+}
+
+async fn install_neoforge(base_dir: &Path, java_path: &Path, neoforge_version: &str) -> Result<()> {
     let installer_url = format!(
         "https://maven.neoforged.net/releases/net/neoforged/neoforge/{}/neoforge-{}-installer.jar",
-        NEOFORGE_VERSION, NEOFORGE_VERSION
+        neoforge_version, neoforge_version
     );
-    let installer_path = base_dir.join(format!("neoforge-{}-installer.jar", NEOFORGE_VERSION));
+    let installer_path = base_dir.join(format!("neoforge-{}-installer.jar", neoforge_version));
 
     if !installer_path.exists() {
         log::info!("Downloading NeoForge installer...");
@@ -292,7 +375,7 @@ async fn install_neoforge(base_dir: &Path, java_path: &Path) -> Result<()> {
 
     let neoforge_version_dir = mc_dir
         .join("versions")
-        .join(format!("neoforge-{}", NEOFORGE_VERSION));
+        .join(format!("neoforge-{}", neoforge_version));
 
     if !neoforge_version_dir.exists() {
         log::info!("Installing NeoForge...");
@@ -321,3 +404,74 @@ async fn install_neoforge(base_dir: &Path, java_path: &Path) -> Result<()> {
 
     Ok(())
 }
+
+/// Fabric and Quilt publish ready-made version JSONs inheriting from vanilla, no installer needed
+async fn install_fabric_like(
+    base_dir: &Path,
+    loader: VersionType,
+    game_version: &str,
+    loader_version: &str,
+) -> Result<String> {
+    let (meta_url, id) = match loader {
+        VersionType::Fabric => (
+            format!("{}/versions/loader/{}/{}/profile/json", FABRIC_META_URL, game_version, loader_version),
+            format!("fabric-loader-{}-{}", loader_version, game_version),
+        ),
+        VersionType::Quilt => (
+            format!("{}/versions/loader/{}/{}/profile/json", QUILT_META_URL, game_version, loader_version),
+            format!("quilt-loader-{}-{}", loader_version, game_version),
+        ),
+        _ => return Err(anyhow::anyhow!("{:?} is not a Fabric-like loader", loader)),
+    };
+
+    let version_dir = base_dir.join("minecraft").join("versions").join(&id);
+    let version_json_path = version_dir.join(format!("{}.json", id));
+
+    if !version_json_path.exists() {
+        log::info!("Downloading {:?} {} profile...", loader, loader_version);
+        let client = Client::builder().user_agent("ezLauncher/0.2.0").build()?;
+        let profile = client
+            .get(&meta_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        // Make sure it parses before saving
+        serde_json::from_str::<VersionManifest>(&profile)?;
+        tokio::fs::create_dir_all(&version_dir).await?;
+        tokio::fs::write(&version_json_path, profile).await?;
+    }
+
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, kind: &str, release_time: &str) -> VersionEntry {
+        VersionEntry {
+            id: id.to_string(),
+            kind: kind.to_string(),
+            url: format!("https://example.com/{}.json", id),
+            release_time: release_time.to_string(),
+        }
+    }
+
+    #[test]
+    fn only_versions_with_arguments_are_launchable() {
+        let versions = vec![
+            entry("1.21.1", "release", "2024-08-08T12:24:45+00:00"),
+            entry("1.13", "release", "2018-07-18T15:11:46+00:00"),
+            entry("17w43a", "snapshot", "2017-10-25T14:30:01+00:00"),
+            entry("1.12.2", "release", "2017-09-18T08:39:46+00:00"),
+            entry("b1.7.3", "old_beta", "2011-07-08T00:00:00+00:00"),
+        ];
+        let ids: Vec<String> = launchable(versions).unwrap().into_iter().map(|v| v.id).collect();
+        assert_eq!(ids, ["1.21.1", "1.13", "17w43a"]);
+
+        assert!(launchable(vec![entry("1.12.2", "release", "2017-09-18T08:39:46+00:00")]).is_err());
+    }
+}
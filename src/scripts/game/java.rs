@@ -1,9 +1,161 @@
 use super::utils::download_file;
+use crate::scripts::instances::JavaSettings;
+use crate::scripts::settings::JavaDefaults;
 use anyhow::Result;
 use reqwest::Client;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
+/// Named sets of JVM arguments offered in the settings
+pub const JVM_PRESETS: &[(&str, &[&str])] = &[
+    (
+        "Aikar's flags (G1GC)",
+        &[
+            "-XX:+UseG1GC",
+            "-XX:+ParallelRefProcEnabled",
+            "-XX:MaxGCPauseMillis=200",
+            "-XX:+UnlockExperimentalVMOptions",
+            "-XX:+DisableExplicitGC",
+            "-XX:+AlwaysPreTouch",
+            "-XX:G1NewSizePercent=30",
+            "-XX:G1MaxNewSizePercent=40",
+            "-XX:G1HeapRegionSize=8M",
+            "-XX:G1ReservePercent=20",
+            "-XX:G1HeapWastePercent=5",
+            "-XX:G1MixedGCCountTarget=4",
+            "-XX:InitiatingHeapOccupancyPercent=15",
+            "-XX:G1MixedGCLiveThresholdPercent=90",
+            "-XX:G1RSetUpdatingPauseIntervalMillis=32",
+            "-XX:SurvivorRatio=32",
+            "-XX:+PerfDisableSharedMem",
+            "-XX:MaxTenuringThreshold=1",
+        ],
+    ),
+    ("Generational ZGC (Java 21+)", &["-XX:+UseZGC", "-XX:+ZGenerational"]),
+];
+
+/// Java settings of a launch, the instance's overrides applied over the launcher defaults
+#[derive(Debug, Clone, PartialEq)]
+pub struct JavaOptions {
+    pub path: Option<PathBuf>,
+    pub min_memory_mb: u32,
+    pub max_memory_mb: u32,
+    pub jvm_args: Vec<String>,
+    pub env: BTreeMap<String, String>,
+    /// Program and arguments the Java command is prefixed with, empty for none
+    pub wrapper: Vec<String>,
+}
+
+impl JavaOptions {
+    pub fn resolve(instance: &JavaSettings, defaults: &JavaDefaults) -> Self {
+        let max_memory_mb = instance.max_memory_mb.unwrap_or(defaults.max_memory_mb);
+        // -Xms above -Xmx makes the JVM refuse to start
+        let min_memory_mb = instance
+            .min_memory_mb
+            .unwrap_or(defaults.min_memory_mb)
+            .min(max_memory_mb);
+
+        let mut env = defaults.env.clone();
+        env.extend(instance.env.clone());
+
+        let wrapper = instance
+            .wrapper
+            .as_ref()
+            .or(defaults.wrapper.as_ref())
+            .map(|w| {
+                split_command(w).unwrap_or_else(|e| {
+                    log::warn!("Ignoring the wrapper command {}: {}", w, e);
+                    Vec::new()
+                })
+            })
+            .unwrap_or_default();
+
+        Self {
+            path: instance.path.clone().or_else(|| defaults.path.clone()),
+            min_memory_mb,
+            max_memory_mb,
+            jvm_args: defaults
+                .jvm_args
+                .iter()
+                .chain(&instance.jvm_args)
+                .cloned()
+                .collect(),
+            env,
+            wrapper,
+        }
+    }
+}
+
+/// Split a command line into arguments like a POSIX shell: quotes group words (`"my wrapper" --flag`)
+/// and a backslash escapes a space, quote or backslash. Other backslashes are kept so Windows paths work.
+pub fn split_command(line: &str) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                args.extend(current.take());
+            }
+            '\'' => {
+                let word = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err(anyhow::anyhow!("Unterminated ' quote")),
+                    }
+                }
+            }
+            '"' => {
+                let word = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') if matches!(chars.peek(), Some('"' | '\\')) => word.extend(chars.next()),
+                        Some(c) => word.push(c),
+                        None => return Err(anyhow::anyhow!("Unterminated \" quote")),
+                    }
+                }
+            }
+            '\\' if chars.peek().is_some_and(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '\\')) => {
+                current.get_or_insert_with(String::new).extend(chars.next());
+            }
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    args.extend(current);
+    Ok(args)
+}
+
+/// Major version of a Java executable (8 for `1.8.0_431`, 21 for `21.0.9`), from `java -version`
+pub async fn major_version(java: &Path) -> Option<u32> {
+    let output = tokio::process::Command::new(java)
+        .arg("-version")
+        .output()
+        .await
+        .inspect_err(|e| log::warn!("Failed to run {}: {}", java.display(), e))
+        .ok()?;
+    // Printed to stderr, e.g. `openjdk version "21.0.9" 2025-10-21`
+    let text = String::from_utf8_lossy(&output.stderr);
+    parse_major_version(&text)
+}
+
+fn parse_major_version(version_output: &str) -> Option<u32> {
+    let version = version_output.split('"').nth(1)?;
+    let version = version.strip_prefix("1.").unwrap_or(version);
+    let major: String = version.chars().take_while(char::is_ascii_digit).collect();
+    major.parse().ok()
+}
+
+/// Physical memory of this machine, bounds the memory sliders
+pub fn total_memory_mb() -> u64 {
+    let mut system = sysinfo::System::new();
+    system.refresh_memory();
+    system.total_memory() / 1024 / 1024
+}
+
 #[derive(Debug, Deserialize)]
 struct AdoptiumRelease {
     binary: AdoptiumBinary,
@@ -81,3 +233,40 @@ pub async fn install_java(base_dir: &Path) -> Result<PathBuf> {
     tokio::fs::remove_file(archive_path).await?;
     Ok(java_bin)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapper_commands_split_like_a_shell() {
+        let cases: &[(&str, &[&str])] = &[
+            ("", &[]),
+            ("gamemoderun", &["gamemoderun"]),
+            ("  prime-run   gamemoderun ", &["prime-run", "gamemoderun"]),
+            (r#""/opt/my tools/wrap" --flag"#, &["/opt/my tools/wrap", "--flag"]),
+            ("'/opt/my tools/wrap' '$HOME'", &["/opt/my tools/wrap", "$HOME"]),
+            (r"/opt/my\ tools/wrap", &["/opt/my tools/wrap"]),
+            (r#"env "A=\"quoted\"" ''"#, &["env", r#"A="quoted""#, ""]),
+            (r"C:\Tools\wrap.exe --x", &[r"C:\Tools\wrap.exe", "--x"]),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(split_command(line).unwrap(), *expected, "{}", line);
+        }
+        assert!(split_command("\"unterminated").is_err());
+    }
+
+    #[test]
+    fn java_versions_parse() {
+        let cases = [
+            ("openjdk version \"21.0.9\" 2025-10-21\nOpenJDK Runtime Environment", Some(21)),
+            ("java version \"1.8.0_431\"\nJava(TM) SE Runtime Environment", Some(8)),
+            ("openjdk version \"17\" 2021-09-14", Some(17)),
+            ("openjdk version \"24-ea\" 2025-03-18", Some(24)),
+            ("Error: could not find java.dll", None),
+        ];
+        for (output, expected) in cases {
+            assert_eq!(parse_major_version(output), expected, "{}", output);
+        }
+    }
+}
@@ -1,18 +1,31 @@
+use super::install::log_config_path;
+use super::LaunchedGame;
+use super::java::{self, JavaOptions};
 use super::types::*;
-use super::utils::check_rules;
-use super::{ELY_BY_API, MC_VERSION};
+use super::utils::{check_feature_rules, check_rules};
+use crate::scripts::instances::Instance;
 use anyhow::Result;
-use std::path::PathBuf;
-
-pub async fn launch_game(
-    mc_dir: PathBuf,
-    java_path: PathBuf,
-    manifest: VersionManifest,
-    username: String,
-    uuid: String,
-    token: String,
-) -> Result<()> {
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+/// Name of the JVM argument file in the launch's temp directory
+pub const ARGFILE_NAME: &str = "jvm.args";
+
+/// Everything needed to start the game, without starting it
+pub async fn build_launch_command(
+    mc_dir: &Path,
+    java_path: &Path,
+    java: &JavaOptions,
+    manifest: &VersionManifest,
+    instance: &Instance,
+    username: &str,
+    uuid: &str,
+    token: &str,
+    auth_server: &str,
+    quick_play: Option<&QuickPlay>,
+) -> Result<LaunchCommand> {
     log::info!("Building launch command...");
+    let game_version = instance.game_version.as_str();
 
     // Parse ignoreList from JVM args to exclude libraries from classpath
     let mut ignore_list: Vec<String> = Vec::new();
@@ -22,7 +35,7 @@ pub async fn launch_game(
                 if let serde_json::Value::String(s) = arg {
                     if s.starts_with("-DignoreList=") {
                         let list = s.trim_start_matches("-DignoreList=");
-                        let list = list.replace("${version_name}", MC_VERSION); // Substitute version name
+                        let list = list.replace("${version_name}", game_version); // Substitute version name
                         ignore_list = list.split(',').map(|s| s.to_string()).collect();
                         log::info!("Ignore list: {:?}", ignore_list);
                         break;
@@ -43,29 +56,27 @@ pub async fn launch_game(
             }
         }
 
-        if let Some(downloads) = &library.downloads {
-            if let Some(artifact) = &downloads.artifact {
-                let lib_path = lib_dir.join(&artifact.path);
-                let lib_filename = lib_path.file_name().unwrap_or_default().to_string_lossy();
-
-                // Skip if in ignore list
-                if ignore_list.iter().any(|ignore| {
-                    lib_filename == *ignore
-                        || (ignore.ends_with("-") && lib_filename.starts_with(ignore))
-                }) {
-                    log::info!("Ignoring library: {}", lib_filename);
-                    continue;
-                }
+        if let Some(artifact) = library.artifact() {
+            let lib_path = lib_dir.join(&artifact.path);
+            let lib_filename = lib_path.file_name().unwrap_or_default().to_string_lossy();
 
-                if lib_path.exists() {
-                    if let Ok(absolute) = std::fs::canonicalize(&lib_path) {
-                        classpath.push(
-                            absolute
-                                .to_string_lossy()
-                                .trim_start_matches(r"\\?\")
-                                .to_string(),
-                        );
-                    }
+            // Skip if in ignore list
+            if ignore_list.iter().any(|ignore| {
+                lib_filename == *ignore
+                    || (ignore.ends_with("-") && lib_filename.starts_with(ignore))
+            }) {
+                log::info!("Ignoring library: {}", lib_filename);
+                continue;
+            }
+
+            if lib_path.exists() {
+                if let Ok(absolute) = std::fs::canonicalize(&lib_path) {
+                    classpath.push(
+                        absolute
+                            .to_string_lossy()
+                            .trim_start_matches(r"\\?\")
+                            .to_string(),
+                    );
                 }
             }
         }
@@ -73,11 +84,11 @@ pub async fn launch_game(
 
     // Add client JAR (absolute path)
     // Only add if not in ignore list (NeoForge puts client jar on module path usually)
-    let client_jar_name = format!("{}.jar", MC_VERSION);
+    let client_jar_name = format!("{}.jar", game_version);
     if !ignore_list.contains(&client_jar_name) {
         let client_jar = mc_dir
             .join("versions")
-            .join(MC_VERSION)
+            .join(game_version)
             .join(&client_jar_name);
         if let Ok(absolute) = std::fs::canonicalize(&client_jar) {
             classpath.push(
@@ -101,45 +112,69 @@ pub async fn launch_game(
         ":"
     };
     let classpath_str = classpath.join(separator);
-    log::info!("Classpath: {}", classpath_str);
+    log::info!("Classpath: {} entries", classpath.len());
 
     // Build command
-    let java_absolute = std::fs::canonicalize(&java_path)?;
-    let mc_absolute = std::fs::canonicalize(&mc_dir)?;
+    let java_absolute = std::fs::canonicalize(java_path)?;
+    let mc_absolute = std::fs::canonicalize(mc_dir)?;
     let natives_absolute = std::fs::canonicalize(mc_dir.join("natives"))?;
 
-    let mut cmd = tokio::process::Command::new(&java_absolute);
+    // Each instance runs in its own game directory, only the store in mc_dir is shared
+    tokio::fs::create_dir_all(instance.game_dir()).await?;
+    let game_dir_absolute = std::fs::canonicalize(instance.game_dir())?;
 
-    cmd.current_dir(&mc_absolute);
+    let mut java_args: Vec<String> = Vec::new();
 
     // Memory args
-    cmd.arg("-Xmx4G").arg("-Xms1G");
+    java_args.push(format!("-Xmx{}M", java.max_memory_mb));
+    java_args.push(format!("-Xms{}M", java.min_memory_mb));
 
-    // Authlib-injector for Ely.by authentication
+    // Extra JVM args from the launcher and instance settings
+    java_args.extend(java.jvm_args.iter().cloned());
+
+    // Authlib-injector pointed at the account's auth server (Ely.by by default)
     let authlib_path = mc_dir
         .parent()
         .ok_or_else(|| anyhow::anyhow!("Invalid mc_dir"))?
         .join("authlib-injector.jar");
     if let Ok(authlib_absolute) = std::fs::canonicalize(&authlib_path) {
-        cmd.arg(format!(
+        java_args.push(format!(
             "-javaagent:{}={}",
             authlib_absolute
                 .to_string_lossy()
                 .trim_start_matches(r"\\?\"),
-            ELY_BY_API
+            auth_server
         ));
     }
 
     // Native library path
-    cmd.arg(format!(
+    java_args.push(format!(
         "-Djava.library.path={}",
         natives_absolute
             .to_string_lossy()
             .trim_start_matches(r"\\?\")
     ));
 
-    // Classpath
-    cmd.arg("-cp").arg(&classpath_str);
+    // Log4j config, makes the game print its log as XML events
+    if let Some(config) = manifest.logging.as_ref().and_then(Logging::client_xml) {
+        if let Ok(config_absolute) = std::fs::canonicalize(log_config_path(mc_dir, config)) {
+            java_args.push(config.argument.replace(
+                "${path}",
+                config_absolute.to_string_lossy().trim_start_matches(r"\\?\"),
+            ));
+        }
+    }
+
+    // Classpath, unless the manifest passes it itself (`-cp ${classpath}`)
+    let manifest_classpath = manifest
+        .arguments
+        .as_ref()
+        .and_then(|args| args.jvm.as_ref())
+        .is_some_and(|args| args.iter().any(|a| a.as_str().is_some_and(|s| s.contains("${classpath}"))));
+    if !manifest_classpath {
+        java_args.push("-cp".to_string());
+        java_args.push(classpath_str.clone());
+    }
 
     // JVM arguments from manifest (with variable substitution)
     if let Some(args) = &manifest.arguments {
@@ -164,49 +199,233 @@ pub async fn launch_game(
                                 .trim_start_matches(r"\\?\"),
                         )
                         .replace("${classpath_separator}", separator)
-                        .replace("${version_name}", MC_VERSION);
-                    cmd.arg(substituted);
+                        .replace("${version_name}", game_version);
+                    java_args.push(substituted);
                 }
             }
         }
     }
 
-    // Main class
-    cmd.arg(&manifest.main_class);
-
     // Game arguments (with variable substitution)
+    let mut game_args_out: Vec<String> = Vec::new();
+    let assets_index_name = manifest
+        .asset_index
+        .as_ref()
+        .map(|index| index.id.as_str())
+        .unwrap_or(game_version);
+    let game_dir_str = game_dir_absolute.to_string_lossy();
+    let game_dir_str = game_dir_str.trim_start_matches(r"\\?\");
+    let assets_root = mc_absolute.join("assets");
+    let assets_root = assets_root.to_string_lossy();
+    let assets_root = assets_root.trim_start_matches(r"\\?\");
+    let (quick_play_server, quick_play_world, quick_play_realm) = match quick_play {
+        Some(QuickPlay::Multiplayer(address)) => (address.as_str(), "", ""),
+        Some(QuickPlay::Singleplayer(world)) => ("", world.as_str(), ""),
+        Some(QuickPlay::Realms(realm)) => ("", "", realm.as_str()),
+        None => ("", "", ""),
+    };
+    let substitute = |s: &str| {
+        s.replace("${auth_player_name}", username)
+            .replace("${version_name}", game_version)
+            .replace("${game_directory}", game_dir_str)
+            .replace("${assets_root}", assets_root)
+            .replace("${assets_index_name}", assets_index_name)
+            .replace("${auth_uuid}", uuid)
+            .replace("${auth_access_token}", token)
+            .replace("${user_type}", "mojang")
+            .replace("${version_type}", "release")
+            .replace("${quickPlayMultiplayer}", quick_play_server)
+            .replace("${quickPlaySingleplayer}", quick_play_world)
+            .replace("${quickPlayRealms}", quick_play_realm)
+    };
+
+    // Quick Play arguments are only turned on through their launcher features
+    let quick_play_supported = supports_quick_play(manifest);
+    let features: &[&str] = match quick_play {
+        Some(QuickPlay::Multiplayer(_)) if quick_play_supported => &["is_quick_play_multiplayer"],
+        Some(QuickPlay::Singleplayer(_)) if quick_play_supported => &["is_quick_play_singleplayer"],
+        Some(QuickPlay::Realms(_)) if quick_play_supported => &["is_quick_play_realms"],
+        _ => &[],
+    };
+
     if let Some(args) = &manifest.arguments {
         if let Some(game_args) = &args.game {
             for arg in game_args {
-                if let serde_json::Value::String(s) = arg {
-                    let substituted = s
-                        .replace("${auth_player_name}", &username)
-                        .replace("${version_name}", MC_VERSION)
-                        .replace(
-                            "${game_directory}",
-                            mc_absolute.to_string_lossy().trim_start_matches(r"\\?\"),
-                        )
-                        .replace(
-                            "${assets_root}",
-                            mc_absolute
-                                .join("assets")
-                                .to_string_lossy()
-                                .trim_start_matches(r"\\?\"),
-                        )
-                        .replace("${assets_index_name}", MC_VERSION)
-                        .replace("${auth_uuid}", &uuid)
-                        .replace("${auth_access_token}", &token)
-                        .replace("${user_type}", "mojang")
-                        .replace("${version_type}", "release");
-                    cmd.arg(substituted);
+                match arg {
+                    serde_json::Value::String(s) => game_args_out.push(substitute(s)),
+                    serde_json::Value::Object(_) => {
+                        let Ok(conditional) = serde_json::from_value::<ConditionalArgument>(arg.clone()) else {
+                            continue;
+                        };
+                        if !check_feature_rules(&conditional.rules, features) {
+                            continue;
+                        }
+                        match conditional.value {
+                            serde_json::Value::String(s) => game_args_out.push(substitute(&s)),
+                            serde_json::Value::Array(values) => game_args_out.extend(
+                                values.iter().filter_map(|v| v.as_str()).map(substitute),
+                            ),
+                            _ => {}
+                        }
+                    }
+                    _ => {}
                 }
             }
         }
     }
 
-    log::info!("Launching Minecraft {}...", MC_VERSION);
+    // Versions before 1.20 only know how to join a server on start
+    if !quick_play_supported {
+        match quick_play {
+            Some(QuickPlay::Multiplayer(address)) => {
+                let (host, port) = split_address(address);
+                game_args_out.extend([
+                    "--server".to_string(),
+                    host.to_string(),
+                    "--port".to_string(),
+                    port.to_string(),
+                ]);
+            }
+            Some(QuickPlay::Singleplayer(world)) => {
+                log::warn!(
+                    "Minecraft {} can't open worlds on start, {} has to be opened from the menu",
+                    game_version,
+                    world
+                );
+            }
+            Some(QuickPlay::Realms(realm)) => {
+                log::warn!(
+                    "Minecraft {} can't join Realms on start, realm {} has to be joined from the menu",
+                    game_version,
+                    realm
+                );
+            }
+            None => {}
+        }
+    }
+
+    // Java 9+ reads arguments from an @argfile, keeps huge classpaths under the command line limit.
+    // A custom Java may be older, so ask it and stay on the command line when it can't tell.
+    let supports_argfiles = match &java.path {
+        None => true,
+        Some(_) => match java::major_version(java_path).await {
+            Some(major) => major >= 9,
+            None => {
+                log::warn!("Couldn't tell the version of {}, not using an argfile", java_path.display());
+                false
+            }
+        },
+    };
+
+    Ok(LaunchCommand {
+        java: java_absolute,
+        wrapper: java.wrapper.clone(),
+        jvm_args: java_args,
+        main_class: manifest.main_class.clone(),
+        game_args: game_args_out,
+        env: java.env.clone(),
+        cwd: game_dir_absolute,
+        supports_argfiles,
+    })
+}
+
+/// Whether the version has the Quick Play arguments (1.20+)
+fn supports_quick_play(manifest: &VersionManifest) -> bool {
+    manifest
+        .arguments
+        .as_ref()
+        .and_then(|args| args.game.as_ref())
+        .is_some_and(|args| {
+            args.iter()
+                .filter_map(|arg| arg.get("rules")?.as_array())
+                .flatten()
+                .filter_map(|rule| rule.get("features")?.as_object())
+                .any(|features| features.keys().any(|f| f.starts_with("is_quick_play_")))
+        })
+}
+
+/// `host:port` into its parts, the port defaults to 25565
+fn split_address(address: &str) -> (&str, &str) {
+    match address.rsplit_once(':') {
+        // A bare IPv6 address has colons but no port
+        Some((host, port)) if !host.contains(':') || host.ends_with(']') => {
+            (host.trim_start_matches('[').trim_end_matches(']'), port)
+        }
+        _ => (address, "25565"),
+    }
+}
+
+/// Spawn the game with stdout/stderr piped, the caller supervises the returned process
+pub async fn launch_game(instance: &Instance, java_path: PathBuf, command: LaunchCommand) -> Result<LaunchedGame> {
+    // Wrappers like `gamemoderun` or `prime-run` take the Java command as their arguments
+    let mut cmd = match command.wrapper.split_first() {
+        Some((program, args)) => {
+            let mut cmd = tokio::process::Command::new(program);
+            cmd.args(args).arg(&command.java);
+            cmd
+        }
+        None => tokio::process::Command::new(&command.java),
+    };
+
+    cmd.current_dir(&command.cwd);
+    cmd.envs(&command.env);
+
+    let temp_dir = if command.supports_argfiles {
+        let dir = launch_temp_dir(instance);
+        let argfile = dir.join(ARGFILE_NAME);
+        write_argfile(&argfile, &command.jvm_args).await?;
+        cmd.arg(format!("@{}", argfile.to_string_lossy()));
+        Some(dir)
+    } else {
+        cmd.args(&command.jvm_args);
+        None
+    };
+
+    cmd.arg(&command.main_class);
+    cmd.args(&command.game_args);
+
+    log::info!("Launching {} ({})...", instance.name, instance.version_label());
     log::debug!("Command: {:?}", cmd);
-    cmd.spawn()?;
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+    let child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            if let Some(dir) = &temp_dir {
+                let _ = tokio::fs::remove_dir_all(dir).await;
+            }
+            return Err(e.into());
+        }
+    };
+
+    Ok(LaunchedGame {
+        child,
+        java_path,
+        temp_dir,
+    })
+}
 
+/// Fresh directory for the files of one launch, removed when the game exits
+fn launch_temp_dir(instance: &Instance) -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    std::env::temp_dir()
+        .join("ezlauncher")
+        .join(format!("{}-{}", instance.id, nanos))
+}
+
+/// One argument per line, quoted so spaces and backslashes (Windows paths) survive
+pub async fn write_argfile(path: &Path, args: &[String]) -> Result<()> {
+    let content = args
+        .iter()
+        .map(|arg| format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\"")))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(path, content).await?;
     Ok(())
 }
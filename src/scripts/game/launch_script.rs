@@ -0,0 +1,208 @@
+use super::launch::{ARGFILE_NAME, build_launch_command, write_argfile};
+use super::types::LaunchCommand;
+use super::{DATA_DIR, check_mods, prepare};
+use crate::components::login::{script::UserInfo, yggdrasil::Session};
+use crate::scripts::instances::Instance;
+use crate::scripts::shell::{cmd_escape, path_str, sh_quote};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// Stands in for a redacted access token, the scripts read it from this environment variable
+const TOKEN_PLACEHOLDER: &str = "${ACCESS_TOKEN}";
+
+/// Install the instance and write how to launch it to `launch_scripts/<id>/`:
+/// a shell (or batch) script, the JVM argfile and `launch.json`. Nothing is started.
+pub async fn export_launch_script(
+    instance: &Instance,
+    user: UserInfo,
+    session: Option<Session>,
+    redact_token: bool,
+) -> Result<PathBuf> {
+    // Step 1: Same install steps as a real launch
+    let prepared = prepare(instance, &user, session).await?;
+    check_mods(instance).await?;
+    let mut command = build_launch_command(
+        &prepared.mc_dir,
+        &prepared.java_path,
+        &prepared.java,
+        &prepared.manifest,
+        instance,
+        &user.username,
+        &user.uuid,
+        &user.access_token,
+        &prepared.auth_server,
+        None,
+    )
+    .await?;
+
+    if redact_token && !user.access_token.is_empty() {
+        redact(&mut command, &user.access_token);
+    }
+
+    // Step 2: Argfile, kept next to the script instead of a temp dir
+    let out_dir = PathBuf::from(DATA_DIR)
+        .join("launch_scripts")
+        .join(&instance.id);
+    tokio::fs::create_dir_all(&out_dir).await?;
+    let out_dir = std::fs::canonicalize(&out_dir)?;
+    let argfile = if command.supports_argfiles {
+        let path = out_dir.join(ARGFILE_NAME);
+        write_argfile(&path, &command.jvm_args).await?;
+        Some(path)
+    } else {
+        None
+    };
+
+    // Step 3: Script for this platform
+    let header = format!(
+        "Launch script for {} ({}), exported by ezLauncher",
+        instance.name,
+        instance.version_label()
+    );
+    let script_path = if cfg!(target_os = "windows") {
+        let path = out_dir.join("launch.bat");
+        let script = batch_script(&command, argfile.as_deref(), &header, redact_token);
+        tokio::fs::write(&path, script).await?;
+        path
+    } else {
+        let path = out_dir.join("launch.sh");
+        let script = shell_script(&command, argfile.as_deref(), &header, redact_token);
+        tokio::fs::write(&path, script).await?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            tokio::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).await?;
+        }
+        path
+    };
+
+    // Step 4: The same command as JSON for tooling
+    tokio::fs::write(
+        out_dir.join("launch.json"),
+        serde_json::to_string_pretty(&command)?,
+    )
+    .await?;
+
+    log::info!("Launch script written to {}", script_path.display());
+    Ok(out_dir)
+}
+
+fn redact(command: &mut LaunchCommand, token: &str) {
+    for arg in command
+        .jvm_args
+        .iter_mut()
+        .chain(command.game_args.iter_mut())
+    {
+        *arg = arg.replace(token, TOKEN_PLACEHOLDER);
+    }
+    for value in command.env.values_mut() {
+        *value = value.replace(token, TOKEN_PLACEHOLDER);
+    }
+}
+
+/// Arguments in the order they're passed: wrapper, java, JVM args (or the argfile), main class, game args
+fn script_args(command: &LaunchCommand, argfile: Option<&Path>) -> Vec<String> {
+    let mut args = command.wrapper.clone();
+    args.push(path_str(&command.java));
+    match argfile {
+        Some(argfile) => args.push(format!("@{}", path_str(argfile))),
+        None => args.extend(command.jvm_args.iter().cloned()),
+    }
+    args.push(command.main_class.clone());
+    args.extend(command.game_args.iter().cloned());
+    args
+}
+
+/// Single quoted, with the redacted token expanded from the environment
+fn shell_quote(arg: &str) -> String {
+    if arg.is_empty() {
+        return sh_quote(arg);
+    }
+    arg.split(TOKEN_PLACEHOLDER)
+        .map(|part| match part {
+            "" => String::new(),
+            part => sh_quote(part),
+        })
+        .collect::<Vec<_>>()
+        .join("\"${ACCESS_TOKEN}\"")
+}
+
+fn shell_script(
+    command: &LaunchCommand,
+    argfile: Option<&Path>,
+    header: &str,
+    redacted: bool,
+) -> String {
+    let mut script = format!("#!/bin/sh\n# {}\n", header);
+    if redacted {
+        script.push_str(": \"${ACCESS_TOKEN:?Set ACCESS_TOKEN to the account's access token}\"\n");
+    }
+    script.push_str(&format!(
+        "cd {} || exit 1\n",
+        shell_quote(&path_str(&command.cwd))
+    ));
+    for (key, value) in &command.env {
+        script.push_str(&format!("export {}={}\n", key, shell_quote(value)));
+    }
+
+    let args: Vec<String> = script_args(command, argfile)
+        .iter()
+        .map(|a| shell_quote(a))
+        .collect();
+    script.push_str(&format!("exec {}\n", args.join(" \\\n  ")));
+    script
+}
+
+/// Double quoted, `%` escaped, with the redacted token expanded from the environment
+fn batch_quote(arg: &str) -> String {
+    let quoted = arg
+        .split(TOKEN_PLACEHOLDER)
+        .map(cmd_escape)
+        .collect::<Vec<_>>()
+        .join("%ACCESS_TOKEN%");
+    format!("\"{}\"", quoted)
+}
+
+fn batch_script(
+    command: &LaunchCommand,
+    argfile: Option<&Path>,
+    header: &str,
+    redacted: bool,
+) -> String {
+    let mut script = format!("@echo off\r\nrem {}\r\n", header);
+    if redacted {
+        script.push_str(
+            "if \"%ACCESS_TOKEN%\"==\"\" (echo Set ACCESS_TOKEN to the account's access token & exit /b 1)\r\n",
+        );
+    }
+    script.push_str(&format!(
+        "cd /d {}\r\n",
+        batch_quote(&path_str(&command.cwd))
+    ));
+    for (key, value) in &command.env {
+        script.push_str(&format!("set \"{}={}\"\r\n", key, value.replace('%', "%%")));
+    }
+
+    let args: Vec<String> = script_args(command, argfile)
+        .iter()
+        .map(|a| batch_quote(a))
+        .collect();
+    script.push_str(&format!("{}\r\n", args.join(" ^\r\n  ")));
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacted_tokens_are_read_from_the_environment() {
+        assert_eq!(shell_quote(TOKEN_PLACEHOLDER), "\"${ACCESS_TOKEN}\"");
+        assert_eq!(
+            shell_quote("a'b ${ACCESS_TOKEN}$HOME"),
+            "'a'\\''b '\"${ACCESS_TOKEN}\"'$HOME'"
+        );
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(batch_quote("50% ${ACCESS_TOKEN}"), "\"50%% %ACCESS_TOKEN%\"");
+    }
+}
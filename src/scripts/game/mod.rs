@@ -3,38 +3,188 @@ pub mod utils;
 pub mod java;
 pub mod install;
 pub mod launch;
+pub mod launch_script;
 
 use anyhow::Result;
 use std::path::PathBuf;
-pub use types::VersionType;
+use std::sync::LazyLock;
+use tokio::process::Child;
+use crate::components::login::{script::{self, UserInfo}, yggdrasil::Session};
+use crate::scripts::instances::{self, Instance};
+use crate::scripts::locks::{KeyedGuard, KeyedLocks};
+use crate::scripts::mods::{self, Severity};
+use crate::scripts::process::hooks;
+use crate::scripts::settings;
+pub use types::{QuickPlay, VersionType};
+pub use java::JavaOptions;
 use java::install_java;
 use install::install_minecraft;
-use launch::launch_game;
+use launch::{build_launch_command, launch_game};
 
 pub const VERSION_MANIFEST_URL: &str = "https://launchermeta.mojang.com/mc/game/version_manifest.json";
 pub const MC_VERSION: &str = "1.21.1";
 pub const NEOFORGE_VERSION: &str = "21.1.65";
 pub const AUTHLIB_INJECTOR_URL: &str = "https://github.com/yushijinhun/authlib-injector/releases/download/v1.2.5/authlib-injector-1.2.5.jar";
+pub const FABRIC_META_URL: &str = "https://meta.fabricmc.net/v2";
+pub const QUILT_META_URL: &str = "https://meta.quiltmc.org/v3";
 pub const ELY_BY_API: &str = "https://authserver.ely.by/api/authlib-injector";
+pub const DATA_DIR: &str = "ezlauncher_data";
 
+/// Installs in progress by what they write to, concurrent launches wait for each other
+/// instead of downloading the same files at once
+static INSTALL_LOCKS: LazyLock<KeyedLocks> = LazyLock::new(KeyedLocks::default);
+
+/// Held until the guard is dropped
+async fn install_lock(key: String) -> KeyedGuard<'static> {
+    match INSTALL_LOCKS.try_lock(&key) {
+        Some(guard) => guard,
+        None => {
+            log::info!("Waiting for another install ({})...", key);
+            INSTALL_LOCKS.lock(&key).await
+        }
+    }
+}
+
+/// A started game and the Java it runs on
+pub struct LaunchedGame {
+    pub child: Child,
+    pub java_path: PathBuf,
+    /// Files of this launch only (the JVM argfile), to remove once the game exits
+    pub temp_dir: Option<PathBuf>,
+}
+
+/// Install what's missing and start the game, returning the running process.
+/// `quick_play` joins a server or opens a world right away. Mod problems stop the launch
+/// with a `ModProblems` error unless `ignore_mod_problems` is set.
 pub async fn launch(
-    username: String,
-    uuid: String,
-    token: String,
-    version_type: VersionType,
-) -> Result<()> {
-    let base_dir = PathBuf::from("ezlauncher_data");
-
-    // Step 1: Install Java
-    let java_path = install_java(&base_dir).await?;
+    mut instance: Instance,
+    user: UserInfo,
+    session: Option<Session>,
+    quick_play: Option<QuickPlay>,
+    ignore_mod_problems: bool,
+) -> Result<LaunchedGame> {
+    let prepared = prepare(&instance, &user, session).await?;
+
+    // Step 3: Catch broken mod setups before the game spends a minute booting into a crash
+    if ignore_mod_problems {
+        log::warn!("Launching {} without the mod check", instance.name);
+    } else {
+        check_mods(&instance).await?;
+    }
+
+    // Step 4: User hook, e.g. pulling configs from git
+    hooks::pre_launch(&instance, &prepared.java_path).await?;
+
+    // Step 5: Launch game
+    let last_played = Some(instances::now_secs());
+    instances::update_instance(&instance.id, |current| current.last_played = last_played).await?;
+    instance.last_played = last_played;
+    let command = build_launch_command(
+        &prepared.mc_dir,
+        &prepared.java_path,
+        &prepared.java,
+        &prepared.manifest,
+        &instance,
+        &user.username,
+        &user.uuid,
+        &user.access_token,
+        &prepared.auth_server,
+        quick_play.as_ref(),
+    )
+    .await?;
+    launch_game(&instance, prepared.java_path, command).await
+}
+
+/// An installed instance, ready to build the launch command for
+struct PreparedLaunch {
+    mc_dir: PathBuf,
+    java_path: PathBuf,
+    java: JavaOptions,
+    manifest: types::VersionManifest,
+    auth_server: String,
+}
+
+/// Everything before the launch command: session check, Java and game files
+async fn prepare(
+    instance: &Instance,
+    user: &UserInfo,
+    session: Option<Session>,
+) -> Result<PreparedLaunch> {
+    let base_dir = PathBuf::from(DATA_DIR);
+
+    // Step 0: Make sure the session is still alive, the game would only fail to verify the username
+    if !script::validate(user, session.as_ref()).await? {
+        return Err(anyhow::anyhow!(
+            "Your session has expired or was revoked. Log out and log in again to play."
+        ));
+    }
+    let auth_server = session
+        .map(|s| s.server)
+        .unwrap_or_else(|| ELY_BY_API.to_string());
+
+    // A second run of the same instance waits until the first one is installed
+    let _instance_lock = install_lock(format!("instance {}", instance.id)).await;
+
+    // Step 1: Install Java (unless the instance or the launcher settings bring their own)
+    let java = JavaOptions::resolve(&instance.java, &settings::load_settings().await?.java);
+    let java_path = match &java.path {
+        Some(path) => path.clone(),
+        None => {
+            let _java_lock = install_lock("java".to_string()).await;
+            install_java(&base_dir).await?
+        }
+    };
     log::info!("Java ready at: {:?}", java_path);
 
-    // Step 2: Install Minecraft
-    let (mc_dir, manifest) = install_minecraft(&base_dir, &java_path, version_type).await?;
+    // Step 2: Install Minecraft. Libraries, assets, natives and authlib-injector are shared by every
+    // version (and the NeoForge installer writes into the shared directory), so one install at a time
+    let (mc_dir, manifest) = {
+        let _game_files_lock = install_lock("game files".to_string()).await;
+        install_minecraft(&base_dir, &java_path, instance).await?
+    };
     log::info!("Minecraft installed");
 
-    // Step 3: Launch game
-    launch_game(mc_dir, java_path, manifest, username, uuid, token).await?;
+    Ok(PreparedLaunch {
+        mc_dir,
+        java_path,
+        java,
+        manifest,
+        auth_server,
+    })
+}
+
+/// Mod errors that would keep the game from starting
+#[derive(Debug, Clone)]
+pub struct ModProblems(pub Vec<String>);
+
+impl std::fmt::Display for ModProblems {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Found {} mod problems, fix them in the Mods page:\n- {}",
+            self.0.len(),
+            self.0.join("\n- ")
+        )
+    }
+}
+
+impl std::error::Error for ModProblems {}
+
+async fn check_mods(instance: &Instance) -> Result<()> {
+    let issues = mods::analyze_mods(instance).await?;
+    let mut errors = Vec::new();
+    for issue in issues {
+        match issue.severity {
+            Severity::Warning => log::warn!("{}", issue.message),
+            Severity::Error => {
+                log::error!("{}", issue.message);
+                errors.push(issue.message);
+            }
+        }
+    }
 
-    Ok(())
+    if errors.is_empty() {
+        return Ok(());
+    }
+    Err(ModProblems(errors).into())
 }
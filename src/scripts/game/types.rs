@@ -1,10 +1,13 @@
-use serde::Deserialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum VersionType {
     Vanilla,
     NeoForge,
+    Fabric,
+    Quilt,
 }
 
 #[derive(Debug, Deserialize)]
@@ -12,10 +15,15 @@ pub struct VersionManifestIndex {
     pub versions: Vec<VersionEntry>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct VersionEntry {
     pub id: String,
+    /// "release", "snapshot", "old_beta" or "old_alpha"
+    #[serde(rename = "type")]
+    pub kind: String,
     pub url: String,
+    pub release_time: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -28,6 +36,7 @@ pub struct VersionManifest {
     pub downloads: Option<Downloads>,
     pub asset_index: Option<AssetIndex>,
     pub inherits_from: Option<String>,
+    pub logging: Option<Logging>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -36,12 +45,57 @@ pub struct Arguments {
     pub jvm: Option<Vec<serde_json::Value>>,
 }
 
+/// An object in `arguments`, only passed when its rules allow it
+#[derive(Debug, Deserialize)]
+pub struct ConditionalArgument {
+    pub rules: Vec<Rule>,
+    /// One argument or a list of them
+    pub value: serde_json::Value,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Library {
     pub name: String,
     pub downloads: Option<LibraryDownloads>,
     pub rules: Option<Vec<Rule>>,
     pub natives: Option<HashMap<String, String>>,
+    /// Maven repository base, used by Fabric/Quilt libraries that have no `downloads`
+    pub url: Option<String>,
+}
+
+impl Library {
+    /// Main artifact, either from `downloads` or resolved from the maven coordinate
+    pub fn artifact(&self) -> Option<Artifact> {
+        if let Some(artifact) = self.downloads.as_ref().and_then(|d| d.artifact.as_ref()) {
+            return Some(artifact.clone());
+        }
+
+        let repo = self.url.as_ref()?;
+        let path = maven_path(&self.name)?;
+        Some(Artifact {
+            url: format!("{}/{}", repo.trim_end_matches('/'), path),
+            path,
+        })
+    }
+}
+
+/// `group:artifact:version[:classifier]` -> `group/path/artifact/version/artifact-version[-classifier].jar`
+pub fn maven_path(coordinate: &str) -> Option<String> {
+    let mut parts = coordinate.split(':');
+    let group = parts.next()?;
+    let artifact = parts.next()?;
+    let version = parts.next()?;
+    let classifier = parts.next().map(|c| format!("-{}", c)).unwrap_or_default();
+
+    Some(format!(
+        "{}/{}/{}/{}-{}{}.jar",
+        group.replace('.', "/"),
+        artifact,
+        version,
+        artifact,
+        version,
+        classifier
+    ))
 }
 
 #[derive(Debug, Deserialize)]
@@ -50,7 +104,7 @@ pub struct LibraryDownloads {
     pub classifiers: Option<HashMap<String, Artifact>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Artifact {
     pub url: String,
     pub path: String,
@@ -60,6 +114,8 @@ pub struct Artifact {
 pub struct Rule {
     pub action: String,
     pub os: Option<OsRule>,
+    /// Launcher features the rule depends on, e.g. `is_quick_play_multiplayer`
+    pub features: Option<HashMap<String, bool>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -87,6 +143,36 @@ pub struct AssetIndex {
     pub url: String,
 }
 
+/// Log4j configuration the launcher is expected to pass to the game
+#[derive(Debug, Deserialize)]
+pub struct Logging {
+    pub client: Option<LoggingConfig>,
+}
+
+impl Logging {
+    /// The client config, only if it's a Log4j2 XML config (the one that makes the game log XML events)
+    pub fn client_xml(&self) -> Option<&LoggingConfig> {
+        self.client.as_ref().filter(|c| c.kind == "log4j2-xml")
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoggingConfig {
+    /// JVM argument with a `${path}` placeholder, e.g. `-Dlog4j.configurationFile=${path}`
+    pub argument: String,
+    pub file: LoggingFile,
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoggingFile {
+    pub id: String,
+    pub sha1: String,
+    pub size: u64,
+    pub url: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct AssetObject {
     pub hash: String,
@@ -97,3 +183,29 @@ pub struct AssetObject {
 pub struct AssetsIndex {
     pub objects: HashMap<String, AssetObject>,
 }
+
+/// A fully built launch, what `launch_game` runs and what launch scripts are written from
+#[derive(Debug, Clone, Serialize)]
+pub struct LaunchCommand {
+    pub java: PathBuf,
+    /// Program and arguments the Java command is prefixed with, empty for none
+    pub wrapper: Vec<String>,
+    pub jvm_args: Vec<String>,
+    pub main_class: String,
+    pub game_args: Vec<String>,
+    pub env: BTreeMap<String, String>,
+    pub cwd: PathBuf,
+    /// Whether the JVM arguments can go through an @argfile (Java 9+)
+    pub supports_argfiles: bool,
+}
+
+/// Where the game goes right after starting, skipping the title screen
+#[derive(Debug, Clone, PartialEq)]
+pub enum QuickPlay {
+    /// Server address, `host` or `host:port`
+    Multiplayer(String),
+    /// Folder name of the world in `saves/`
+    Singleplayer(String),
+    /// Realm id, Realms only accept accounts Mojang knows
+    Realms(String),
+}
@@ -1,5 +1,7 @@
 use anyhow::Result;
 use reqwest::Client;
+use sha1::Sha1;
+use sha2::{Digest, Sha512};
 use std::path::Path;
 use super::types::Rule;
 
@@ -8,13 +10,67 @@ pub async fn download_file(client: &Client, url: &str, path: &Path) -> Result<()
         tokio::fs::create_dir_all(parent).await?;
     }
 
-    let response = client.get(url).send().await?;
+    let response = client.get(url).send().await?.error_for_status()?;
     let bytes = response.bytes().await?;
     tokio::fs::write(path, bytes).await?;
 
     Ok(())
 }
 
+/// Expected checksum of a download
+#[derive(Debug, Clone, Copy)]
+pub enum FileHash<'a> {
+    Sha1(&'a str),
+    Sha512(&'a str),
+}
+
+impl FileHash<'_> {
+    pub fn matches(&self, bytes: &[u8]) -> bool {
+        match self {
+            FileHash::Sha1(expected) => hex(&Sha1::digest(bytes)).eq_ignore_ascii_case(expected),
+            FileHash::Sha512(expected) => hex(&Sha512::digest(bytes)).eq_ignore_ascii_case(expected),
+        }
+    }
+}
+
+/// Download and check the hash and size before writing, so a broken file never lands on disk
+pub async fn download_file_verified(
+    client: &Client,
+    url: &str,
+    path: &Path,
+    hash: FileHash<'_>,
+    size: Option<u64>,
+) -> Result<()> {
+    let response = client.get(url).send().await?.error_for_status()?;
+    let bytes = response.bytes().await?;
+
+    if let Some(size) = size {
+        if bytes.len() as u64 != size {
+            return Err(anyhow::anyhow!(
+                "Size mismatch for {}: expected {} bytes, got {}",
+                url,
+                size,
+                bytes.len()
+            ));
+        }
+    }
+
+    if !hash.matches(&bytes) {
+        return Err(anyhow::anyhow!("Checksum mismatch for {}", url));
+    }
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(path, bytes).await?;
+
+    Ok(())
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 pub fn check_rules(rules: &[Rule]) -> bool {
     let current_os = std::env::consts::OS;
     let mut allowed = false;
@@ -45,6 +101,20 @@ pub fn check_rules(rules: &[Rule]) -> bool {
     allowed
 }
 
+/// `check_rules` for arguments that also depend on launcher features, only the `enabled` ones are on
+pub fn check_feature_rules(rules: &[Rule], enabled: &[&str]) -> bool {
+    let features_enabled = rules
+        .iter()
+        .filter(|rule| rule.action == "allow")
+        .filter_map(|rule| rule.features.as_ref())
+        .all(|features| {
+            features
+                .iter()
+                .all(|(name, value)| enabled.contains(&name.as_str()) == *value)
+        });
+    features_enabled && check_rules(rules)
+}
+
 pub fn extract_natives(jar_path: &Path, natives_dir: &Path) -> Result<()> {
     let file = std::fs::File::open(jar_path)?;
     let mut archive = zip::ZipArchive::new(file)?;
@@ -122,3 +192,31 @@ pub fn extract_zip(zip_path: &Path, out_dir: &Path) -> Result<()> {
 
     Ok(())
 }
+
+pub fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
+    for entry in walkdir::WalkDir::new(src) {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(src)?;
+        let target = dst.join(relative);
+
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else {
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// `1.21.1` -> `[1, 21, 1]`, padded so `1.21` == `1.21.0`. `None` for snapshots and other non-release ids.
+pub fn parse_release(version: &str) -> Option<[u32; 3]> {
+    let mut parts = [0; 3];
+    for (i, part) in version.split('.').enumerate() {
+        *parts.get_mut(i)? = part.parse().ok()?;
+    }
+    Some(parts)
+}
\ No newline at end of file
@@ -0,0 +1,92 @@
+use super::types::{Instance, SessionEnd, SessionRecord};
+use super::{load_instance, lock_instance, write_atomic};
+use anyhow::Result;
+use std::path::PathBuf;
+
+pub const HISTORY_FILE: &str = "history.json";
+
+/// Sessions kept per instance, older ones are dropped (their playtime stays counted)
+const MAX_SESSIONS: usize = 1000;
+
+/// Totals over the recorded sessions
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistorySummary {
+    pub sessions: usize,
+    pub crashes: usize,
+    pub total_secs: u64,
+    pub longest_secs: u64,
+}
+
+impl HistorySummary {
+    pub fn average_secs(&self) -> u64 {
+        match self.sessions {
+            0 => 0,
+            n => self.total_secs / n as u64,
+        }
+    }
+}
+
+fn history_path(instance: &Instance) -> PathBuf {
+    instance.dir().join(HISTORY_FILE)
+}
+
+/// Sessions of the instance, oldest first
+pub async fn load_history(instance: &Instance) -> Result<Vec<SessionRecord>> {
+    let path = history_path(instance);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = tokio::fs::read_to_string(&path).await?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+pub fn summarize(history: &[SessionRecord]) -> HistorySummary {
+    HistorySummary {
+        sessions: history.len(),
+        crashes: history
+            .iter()
+            .filter(|s| s.end == SessionEnd::Crashed)
+            .count(),
+        total_secs: history.iter().map(SessionRecord::duration_secs).sum(),
+        longest_secs: history
+            .iter()
+            .map(SessionRecord::duration_secs)
+            .max()
+            .unwrap_or_default(),
+    }
+}
+
+/// Append a finished session and add it to the instance's playtime and last played time
+pub async fn record_session(instance: &Instance, record: SessionRecord) -> Result<()> {
+    // Another session of the same instance may be finishing right now
+    let _lock = lock_instance(&instance.id).await;
+    let mut history = match load_history(instance).await {
+        Ok(history) => history,
+        Err(e) => {
+            // Keep the unreadable file around instead of overwriting the user's history with this session
+            let path = history_path(instance);
+            let backup = path.with_extension("json.bak");
+            tokio::fs::rename(&path, &backup).await?;
+            log::warn!(
+                "Starting a new history for {}, the old one is in {}: {}",
+                instance.name,
+                backup.display(),
+                e
+            );
+            Vec::new()
+        }
+    };
+    history.push(record.clone());
+    if history.len() > MAX_SESSIONS {
+        let overflow = history.len() - MAX_SESSIONS;
+        history.drain(..overflow);
+    }
+    write_atomic(&history_path(instance), serde_json::to_string_pretty(&history)?).await?;
+
+    // Reload, the instance may have been edited while the game was running
+    let mut current = load_instance(&instance.id).await?;
+    current.playtime_secs += record.duration_secs();
+    current.last_played = Some(record.ended_at);
+    current.save().await?;
+    Ok(())
+}
@@ -0,0 +1,309 @@
+pub mod history;
+pub mod types;
+
+use crate::scripts::game::utils::{copy_dir_all, parse_release};
+use crate::scripts::game::{DATA_DIR, MC_VERSION, NEOFORGE_VERSION, VersionType};
+use anyhow::Result;
+use std::cmp::Ordering;
+use crate::scripts::locks::{KeyedGuard, KeyedLocks};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+pub use types::{HookSettings, Instance, JavaSettings, SessionEnd, SessionRecord};
+
+pub const INSTANCE_FILE: &str = "instance.json";
+
+/// One per instance id, taken around every read-modify-write of its files
+static INSTANCE_LOCKS: LazyLock<KeyedLocks> = LazyLock::new(KeyedLocks::default);
+
+pub fn instances_dir() -> PathBuf {
+    PathBuf::from(DATA_DIR).join("instances")
+}
+
+/// Held until the guard is dropped, not reentrant
+pub async fn lock_instance(id: &str) -> KeyedGuard<'static> {
+    INSTANCE_LOCKS.lock(id).await
+}
+
+/// Write through a temp file so a crash or a concurrent reader never sees half a file
+pub async fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    tokio::fs::write(&tmp, contents).await?;
+    tokio::fs::rename(&tmp, path).await?;
+    Ok(())
+}
+
+impl Instance {
+    pub fn dir(&self) -> PathBuf {
+        instances_dir().join(&self.id)
+    }
+
+    /// Isolated game directory passed as `${game_directory}` (mods, config, saves, options.txt...)
+    pub fn game_dir(&self) -> PathBuf {
+        self.dir().join("minecraft")
+    }
+
+    /// Human readable version, e.g. "NeoForge 21.1.65"
+    pub fn version_label(&self) -> String {
+        match (&self.loader, &self.loader_version) {
+            (VersionType::Vanilla, _) | (_, None) => format!("Vanilla {}", self.game_version),
+            (loader, Some(version)) => format!("{:?} {}", loader, version),
+        }
+    }
+
+    /// e.g. "3h 12m", "Never played" only if the instance was never started
+    pub fn playtime_label(&self) -> String {
+        if self.last_played.is_none() && self.playtime_secs == 0 {
+            return "Never played".to_string();
+        }
+        duration_label(self.playtime_secs)
+    }
+
+    /// e.g. "2 days ago"
+    pub fn last_played_label(&self) -> String {
+        match self.last_played {
+            Some(last_played) => ago_label(last_played),
+            None => "Never".to_string(),
+        }
+    }
+
+    /// Overwrites the whole file, use `update_instance` to change an existing instance
+    pub async fn save(&self) -> Result<()> {
+        tokio::fs::create_dir_all(self.game_dir()).await?;
+        write_atomic(&self.dir().join(INSTANCE_FILE), serde_json::to_string_pretty(self)?).await
+    }
+}
+
+pub async fn load_instance(id: &str) -> Result<Instance> {
+    let path = instances_dir().join(id).join(INSTANCE_FILE);
+    let content = tokio::fs::read_to_string(&path).await?;
+    let mut instance: Instance = serde_json::from_str(&content)?;
+    instance.id = id.to_string();
+    Ok(instance)
+}
+
+/// Re-read the instance under its lock, apply `change` and save it, so concurrent edits aren't lost
+pub async fn update_instance(id: &str, change: impl FnOnce(&mut Instance)) -> Result<Instance> {
+    let _lock = lock_instance(id).await;
+    let mut instance = load_instance(id).await?;
+    change(&mut instance);
+    instance.save().await?;
+    Ok(instance)
+}
+
+pub async fn list_instances() -> Result<Vec<Instance>> {
+    let dir = instances_dir();
+    if !dir.exists() {
+        create_default_instances().await?;
+    }
+
+    let mut instances = Vec::new();
+    let mut entries = tokio::fs::read_dir(&dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if !entry.path().join(INSTANCE_FILE).exists() {
+            continue;
+        }
+        let id = entry.file_name().to_string_lossy().to_string();
+        match load_instance(&id).await {
+            Ok(instance) => instances.push(instance),
+            Err(e) => log::error!("Failed to load instance {}: {}", id, e),
+        }
+    }
+
+    instances.sort_by_key(|i| i.name.to_lowercase());
+    Ok(instances)
+}
+
+pub async fn create_instance(
+    name: &str,
+    game_version: &str,
+    loader: VersionType,
+    loader_version: Option<String>,
+) -> Result<Instance> {
+    let instance = Instance {
+        id: unique_id(name),
+        name: name.to_string(),
+        game_version: game_version.to_string(),
+        loader,
+        loader_version,
+        java: JavaSettings::default(),
+        last_played: None,
+        playtime_secs: 0,
+        hooks: HookSettings::default(),
+    };
+    instance.save().await?;
+    log::info!("Created instance {} ({})", instance.name, instance.id);
+    Ok(instance)
+}
+
+/// Copy the whole instance, game directory included, under a new name
+pub async fn duplicate_instance(instance: &Instance, new_name: &str) -> Result<Instance> {
+    let id = unique_id(new_name);
+    let src = instance.dir();
+    let dst = instances_dir().join(&id);
+    tokio::task::spawn_blocking(move || copy_dir_all(&src, &dst)).await??;
+
+    let mut copy = load_instance(&id).await?;
+    copy.name = new_name.to_string();
+    copy.last_played = None;
+    copy.playtime_secs = 0;
+    copy.save().await?;
+    let _ = tokio::fs::remove_file(copy.dir().join(history::HISTORY_FILE)).await;
+    log::info!("Duplicated instance {} as {}", instance.name, copy.name);
+    Ok(copy)
+}
+
+/// Only the display name changes, the id (and so the directory) stays the same
+pub async fn rename_instance(instance: &Instance, new_name: &str) -> Result<Instance> {
+    let new_name = new_name.trim().to_string();
+    update_instance(&instance.id, |renamed| renamed.name = new_name).await
+}
+
+pub async fn delete_instance(instance: &Instance) -> Result<()> {
+    tokio::fs::remove_dir_all(instance.dir()).await?;
+    log::info!("Deleted instance {}", instance.name);
+    Ok(())
+}
+
+/// Game versions by their release numbers, so "1.21" comes after "1.9".
+/// Pre-releases (`1.21-pre1`) come before their release, snapshots (`24w14a`) before everything.
+pub fn compare_game_versions(a: &str, b: &str) -> Ordering {
+    let key = |version: &str| {
+        let (release, suffix) = version.split_once('-').unwrap_or((version, ""));
+        // A release sorts after its pre-releases
+        (parse_release(release), suffix.is_empty())
+    };
+    key(a).cmp(&key(b)).then_with(|| a.cmp(b))
+}
+
+/// e.g. "45m" or "3h 12m"
+pub fn duration_label(secs: u64) -> String {
+    let minutes = secs / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, 0) => "<1 min".to_string(),
+        (0, m) => format!("{}m", m),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+/// How long ago a unix timestamp (seconds) was, e.g. "2 days ago"
+pub fn ago_label(timestamp: u64) -> String {
+    let ago = now_secs().saturating_sub(timestamp);
+    match ago {
+        0..60 => "Just now".to_string(),
+        60..3600 => format!("{} min ago", ago / 60),
+        3600..86400 => format!("{} h ago", ago / 3600),
+        _ => format!("{} days ago", ago / 86400),
+    }
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// The two setups the launcher shipped with before instances existed
+async fn create_default_instances() -> Result<()> {
+    create_instance(
+        &format!("Vanilla {}", MC_VERSION),
+        MC_VERSION,
+        VersionType::Vanilla,
+        None,
+    )
+    .await?;
+    create_instance(
+        &format!("NeoForge {}", NEOFORGE_VERSION),
+        MC_VERSION,
+        VersionType::NeoForge,
+        Some(NEOFORGE_VERSION.to_string()),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Directory-safe id derived from the name, suffixed if already taken
+fn unique_id(name: &str) -> String {
+    let slug: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let slug = slug.trim_matches('-').to_string();
+    let slug = if slug.is_empty() { "instance".to_string() } else { slug };
+
+    let mut id = slug.clone();
+    let mut n = 2;
+    while instances_dir().join(&id).exists() {
+        id = format!("{}-{}", slug, n);
+        n += 1;
+    }
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn game_versions_sort_numerically() {
+        let mut versions = vec!["1.9", "1.21", "24w14a", "1.21-pre1", "1.20.6", "1.21.1", "1.8.9"];
+        versions.sort_by(|a, b| compare_game_versions(a, b));
+        assert_eq!(
+            versions,
+            ["24w14a", "1.8.9", "1.9", "1.20.6", "1.21-pre1", "1.21", "1.21.1"]
+        );
+    }
+
+    #[test]
+    fn short_sessions_still_count_as_played() {
+        let mut instance = Instance {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            game_version: "1.21.1".to_string(),
+            loader: VersionType::Vanilla,
+            loader_version: None,
+            java: Default::default(),
+            last_played: None,
+            playtime_secs: 0,
+            hooks: Default::default(),
+        };
+        assert_eq!(instance.playtime_label(), "Never played");
+
+        instance.last_played = Some(now_secs());
+        assert_eq!(instance.playtime_label(), "<1 min");
+        instance.playtime_secs = 59;
+        assert_eq!(instance.playtime_label(), "<1 min");
+        instance.playtime_secs = 3 * 3600 + 12 * 60;
+        assert_eq!(instance.playtime_label(), "3h 12m");
+    }
+
+    #[tokio::test]
+    async fn locked_updates_dont_lose_writes() {
+        let dir = std::env::temp_dir().join(format!("ezlauncher-instance-lock-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join("counter.json");
+        write_atomic(&path, "0").await.unwrap();
+
+        let tasks: Vec<_> = (0..20)
+            .map(|_| {
+                let path = path.clone();
+                tokio::spawn(async move {
+                    let _lock = lock_instance("lock-test").await;
+                    let count: u32 = tokio::fs::read_to_string(&path).await.unwrap().parse().unwrap();
+                    tokio::task::yield_now().await;
+                    write_atomic(&path, (count + 1).to_string()).await.unwrap();
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert_eq!(tokio::fs::read_to_string(&path).await.unwrap(), "20");
+        assert!(!dir.join("counter.json.tmp").exists());
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
@@ -0,0 +1,78 @@
+use crate::scripts::game::VersionType;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Contents of an instance's `instance.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Instance {
+    /// Directory name under `instances/`, not stored in the file
+    #[serde(skip)]
+    pub id: String,
+    pub name: String,
+    pub game_version: String,
+    pub loader: VersionType,
+    pub loader_version: Option<String>,
+    #[serde(default)]
+    pub java: JavaSettings,
+    /// Unix timestamp (seconds) of the last launch
+    #[serde(default)]
+    pub last_played: Option<u64>,
+    /// Total time spent in game, in seconds
+    #[serde(default)]
+    pub playtime_secs: u64,
+    #[serde(default)]
+    pub hooks: HookSettings,
+}
+
+/// Shell commands run around a game run, see `process::hooks` for their environment
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HookSettings {
+    /// Run before the game starts, a non-zero exit aborts the launch
+    pub pre_launch: Option<String>,
+    /// Run after the game exits, `EXIT_CODE` holds the game's exit code
+    pub post_exit: Option<String>,
+}
+
+/// Per-instance overrides of the launcher's `JavaDefaults`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JavaSettings {
+    /// Custom Java executable, falls back to the launcher's then the bundled Java 21
+    pub path: Option<PathBuf>,
+    pub min_memory_mb: Option<u32>,
+    pub max_memory_mb: Option<u32>,
+    /// Added after the launcher-wide arguments
+    pub jvm_args: Vec<String>,
+    /// Merged over the launcher-wide variables
+    pub env: BTreeMap<String, String>,
+    /// Replaces the launcher-wide wrapper, an empty string runs without one
+    pub wrapper: Option<String>,
+}
+
+/// How a game session ended
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SessionEnd {
+    Exited,
+    /// Stopped or killed from the launcher
+    Stopped,
+    Crashed,
+}
+
+/// One game run, kept in the instance's `history.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// Unix timestamp (seconds) the game process was spawned
+    pub started_at: u64,
+    /// Unix timestamp (seconds) the game process exited
+    pub ended_at: u64,
+    pub exit_code: Option<i32>,
+    pub end: SessionEnd,
+}
+
+impl SessionRecord {
+    pub fn duration_secs(&self) -> u64 {
+        self.ended_at.saturating_sub(self.started_at)
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OwnedMutexGuard;
+
+/// Async locks created on demand per key (an instance id, a store...), dropped again once unused
+#[derive(Default)]
+pub struct KeyedLocks {
+    locks: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+/// Held until dropped, not reentrant
+pub struct KeyedGuard<'a> {
+    locks: &'a KeyedLocks,
+    key: String,
+    lock: Arc<tokio::sync::Mutex<()>>,
+    guard: Option<OwnedMutexGuard<()>>,
+}
+
+impl KeyedLocks {
+    pub async fn lock(&self, key: &str) -> KeyedGuard<'_> {
+        let lock = self.entry(key);
+        let guard = lock.clone().lock_owned().await;
+        self.guard(key, lock, guard)
+    }
+
+    /// `None` if someone else holds the lock
+    pub fn try_lock(&self, key: &str) -> Option<KeyedGuard<'_>> {
+        let lock = self.entry(key);
+        let guard = lock.clone().try_lock_owned().ok()?;
+        Some(self.guard(key, lock, guard))
+    }
+
+    fn entry(&self, key: &str) -> Arc<tokio::sync::Mutex<()>> {
+        self.locks.lock().unwrap().entry(key.to_string()).or_default().clone()
+    }
+
+    fn guard(&self, key: &str, lock: Arc<tokio::sync::Mutex<()>>, guard: OwnedMutexGuard<()>) -> KeyedGuard<'_> {
+        KeyedGuard {
+            locks: self,
+            key: key.to_string(),
+            lock,
+            guard: Some(guard),
+        }
+    }
+}
+
+impl Drop for KeyedGuard<'_> {
+    fn drop(&mut self) {
+        self.guard.take();
+        let mut locks = self.locks.locks.lock().unwrap();
+        // Only the map and this guard know the lock, nobody is waiting for it
+        if Arc::strong_count(&self.lock) == 2 {
+            locks.remove(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn len(locks: &KeyedLocks) -> usize {
+        locks.locks.lock().unwrap().len()
+    }
+
+    #[tokio::test]
+    async fn locks_are_exclusive_per_key_and_cleaned_up() {
+        let locks = KeyedLocks::default();
+        let a = locks.lock("a").await;
+        assert!(locks.try_lock("a").is_none());
+        let b = locks.try_lock("b").expect("other keys are independent");
+        assert_eq!(len(&locks), 2);
+
+        drop(b);
+        assert_eq!(len(&locks), 1);
+        drop(a);
+        assert_eq!(len(&locks), 0);
+        assert!(locks.try_lock("a").is_some());
+    }
+
+    #[tokio::test]
+    async fn waiters_keep_the_lock_alive() {
+        let locks = Arc::new(KeyedLocks::default());
+        let first = locks.lock("a").await;
+        let waiter = tokio::spawn({
+            let locks = locks.clone();
+            async move {
+                let _second = locks.lock("a").await;
+            }
+        });
+        // Let the waiter queue up behind `first`
+        while Arc::strong_count(&locks.entry("a")) < 4 {
+            tokio::task::yield_now().await;
+        }
+        drop(first);
+        waiter.await.unwrap();
+        assert_eq!(len(&locks), 0);
+    }
+}
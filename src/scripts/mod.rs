@@ -1,2 +1,19 @@
 pub mod window_size_center;
-pub mod game;
\ No newline at end of file
+pub mod game;
+pub mod instances;
+pub mod skins;
+pub mod settings;
+pub mod modrinth;
+pub mod curseforge;
+pub mod modpacks;
+pub mod mods;
+pub mod packs;
+pub mod locks;
+pub mod shell;
+
+pub mod process;
+pub mod crash;
+pub mod quickplay;
+
+#[cfg(test)]
+pub mod test_server;
\ No newline at end of file
@@ -0,0 +1,372 @@
+use super::{extract_overrides, safe_relative_path};
+use crate::scripts::curseforge::{CLASS_RESOURCE_PACKS, CLASS_SHADERS, CurseForgeClient, Mod};
+use crate::scripts::game::VersionType;
+use crate::scripts::game::utils::{FileHash, download_file, download_file_verified};
+use crate::scripts::instances::{self, Instance};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeManifest {
+    pub minecraft: ManifestMinecraft,
+    pub manifest_type: String,
+    pub name: String,
+    pub version: Option<String>,
+    #[serde(default)]
+    pub files: Vec<ManifestFile>,
+    pub overrides: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestMinecraft {
+    pub version: String,
+    #[serde(default)]
+    pub mod_loaders: Vec<ModLoader>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ModLoader {
+    /// e.g. `neoforge-21.1.65` or `fabric-0.16.5`
+    pub id: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ManifestFile {
+    #[serde(rename = "projectID")]
+    pub project_id: u64,
+    #[serde(rename = "fileID")]
+    pub file_id: u64,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+/// A file the author doesn't allow launchers to download, the user has to fetch it from the website
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManualDownload {
+    pub project_name: String,
+    /// Name of the file to download, `File <id>` if CurseForge no longer lists it
+    pub file_name: String,
+    /// Download page on curseforge.com
+    pub url: String,
+    /// Folder of the instance to put the file in
+    pub target: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurseForgeImport {
+    pub instance: Instance,
+    pub manual_downloads: Vec<ManualDownload>,
+}
+
+/// Create a new instance from a CurseForge modpack zip
+pub async fn import_curseforge_zip(client: &CurseForgeClient, path: &Path) -> Result<CurseForgeImport> {
+    // Step 1: Read the manifest
+    let pack_path = path.to_path_buf();
+    let manifest = tokio::task::spawn_blocking(move || read_manifest(&pack_path)).await??;
+    if manifest.manifest_type != "minecraftModpack" {
+        return Err(anyhow::anyhow!("Unsupported manifest type: {}", manifest.manifest_type));
+    }
+    log::info!(
+        "Importing CurseForge modpack {} {}",
+        manifest.name,
+        manifest.version.as_deref().unwrap_or_default()
+    );
+
+    // Step 2: Create the instance from the primary mod loader
+    let (loader, loader_version) = resolve_loader(&manifest.minecraft.mod_loaders)?;
+    let instance = instances::create_instance(
+        &manifest.name,
+        &manifest.minecraft.version,
+        loader,
+        loader_version,
+    )
+    .await?;
+
+    // Step 3: Download files and apply overrides, removing the half installed instance on failure
+    match install_pack(client, path, &manifest, &instance).await {
+        Ok(manual_downloads) => {
+            log::info!("Modpack {} installed as {}", manifest.name, instance.name);
+            Ok(CurseForgeImport { instance, manual_downloads })
+        }
+        Err(e) => {
+            log::error!("Modpack install failed, removing {}: {}", instance.name, e);
+            if let Err(e) = instances::delete_instance(&instance).await {
+                log::warn!("Failed to remove instance {}: {}", instance.id, e);
+            }
+            Err(e)
+        }
+    }
+}
+
+fn read_manifest(path: &Path) -> Result<CurseForgeManifest> {
+    let file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+    let mut entry = archive
+        .by_name(MANIFEST_FILE)
+        .map_err(|_| anyhow::anyhow!("Not a CurseForge modpack: {} is missing", MANIFEST_FILE))?;
+
+    let mut content = String::new();
+    entry.read_to_string(&mut content)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+fn resolve_loader(loaders: &[ModLoader]) -> Result<(VersionType, Option<String>)> {
+    let Some(loader) = loaders.iter().find(|l| l.primary).or(loaders.first()) else {
+        return Ok((VersionType::Vanilla, None));
+    };
+
+    let (name, version) = loader
+        .id
+        .split_once('-')
+        .ok_or_else(|| anyhow::anyhow!("Invalid mod loader id: {}", loader.id))?;
+    let loader_type = match name {
+        "neoforge" => VersionType::NeoForge,
+        "fabric" => VersionType::Fabric,
+        "quilt" => VersionType::Quilt,
+        _ => return Err(anyhow::anyhow!("Unsupported mod loader: {}", loader.id)),
+    };
+
+    Ok((loader_type, Some(version.to_string())))
+}
+
+async fn install_pack(
+    client: &CurseForgeClient,
+    path: &Path,
+    manifest: &CurseForgeManifest,
+    instance: &Instance,
+) -> Result<Vec<ManualDownload>> {
+    let game_dir = instance.game_dir();
+    let manual_downloads = download_files(client, &manifest.files, &game_dir).await?;
+
+    let pack_path = path.to_path_buf();
+    let overrides = manifest.overrides.clone().unwrap_or_else(|| "overrides".to_string());
+    tokio::task::spawn_blocking(move || extract_overrides(&pack_path, &overrides, &game_dir)).await??;
+
+    Ok(manual_downloads)
+}
+
+/// Download every required file, returning the ones that have to be downloaded by hand
+async fn download_files(
+    client: &CurseForgeClient,
+    files: &[ManifestFile],
+    game_dir: &Path,
+) -> Result<Vec<ManualDownload>> {
+    let required: Vec<&ManifestFile> = files.iter().filter(|f| f.required).collect();
+    if required.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Step 1: Resolve file and project info in bulk
+    let file_ids: Vec<u64> = required.iter().map(|f| f.file_id).collect();
+    let cf_files = client.get_files(&file_ids).await?;
+    let mut mod_ids: Vec<u64> = required
+        .iter()
+        .map(|f| f.project_id)
+        .chain(cf_files.iter().map(|f| f.mod_id))
+        .collect();
+    mod_ids.sort();
+    mod_ids.dedup();
+    let mods: HashMap<u64, Mod> = client
+        .get_mods(&mod_ids)
+        .await?
+        .into_iter()
+        .map(|m| (m.id, m))
+        .collect();
+
+    // Files the API doesn't know (deleted or hidden) have to be found on the website
+    let mut manual_downloads = Vec::new();
+    for missing in required.iter().filter(|f| !cf_files.iter().any(|c| c.id == f.file_id)) {
+        log::warn!(
+            "CurseForge file {} of project {} not found",
+            missing.file_id,
+            missing.project_id
+        );
+        let project = mods.get(&missing.project_id);
+        manual_downloads.push(ManualDownload {
+            project_name: project_name(missing.project_id, project),
+            file_name: format!("File {}", missing.file_id),
+            url: file_page(missing.project_id, project, missing.file_id),
+            target: game_dir.join(target_dir(project)),
+        });
+    }
+
+    // Step 2: Download what we're allowed to
+    for file in &cf_files {
+        let project = mods.get(&file.mod_id);
+        let folder = game_dir.join(target_dir(project));
+
+        let Some(url) = &file.download_url else {
+            manual_downloads.push(ManualDownload {
+                project_name: project_name(file.mod_id, project),
+                file_name: file.file_name.clone(),
+                url: file_page(file.mod_id, project, file.id),
+                target: folder,
+            });
+            continue;
+        };
+
+        log::info!("Downloading {}", file.file_name);
+        let out_path = folder.join(safe_relative_path(&file.file_name)?);
+        match file.sha1() {
+            Some(sha1) => {
+                let hash = FileHash::Sha1(sha1);
+                download_file_verified(client.http(), url, &out_path, hash, Some(file.file_length)).await?
+            }
+            None => download_file(client.http(), url, &out_path).await?,
+        }
+    }
+
+    if !manual_downloads.is_empty() {
+        log::warn!("{} files have to be downloaded manually", manual_downloads.len());
+    }
+    Ok(manual_downloads)
+}
+
+fn target_dir(project: Option<&Mod>) -> &'static str {
+    match project.and_then(|p| p.class_id) {
+        Some(CLASS_RESOURCE_PACKS) => "resourcepacks",
+        Some(CLASS_SHADERS) => "shaderpacks",
+        _ => "mods",
+    }
+}
+
+fn project_name(project_id: u64, project: Option<&Mod>) -> String {
+    project
+        .map(|p| p.name.clone())
+        .unwrap_or_else(|| project_id.to_string())
+}
+
+/// Download page of a file on curseforge.com
+fn file_page(project_id: u64, project: Option<&Mod>, file_id: u64) -> String {
+    let page = project
+        .and_then(|p| p.links.as_ref())
+        .and_then(|l| l.website_url.clone())
+        .or_else(|| {
+            project.map(|p| {
+                let category = match p.class_id {
+                    Some(CLASS_RESOURCE_PACKS) => "texture-packs",
+                    Some(CLASS_SHADERS) => "shaders",
+                    _ => "mc-mods",
+                };
+                format!("https://www.curseforge.com/minecraft/{}/{}", category, p.slug)
+            })
+        })
+        .unwrap_or_else(|| format!("https://www.curseforge.com/projects/{}", project_id));
+
+    format!("{}/files/{}", page.trim_end_matches('/'), file_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scripts::test_server::{self, Response};
+    use serde_json::json;
+
+    const JAR: &[u8] = b"not really a jar";
+
+    fn manifest_file(project_id: u64, file_id: u64) -> ManifestFile {
+        ManifestFile {
+            project_id,
+            file_id,
+            required: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn download_files_sorts_out_manual_and_missing_files() {
+        let cdn = test_server::serve(|request| match request.path.as_str() {
+            "/sodium.jar" => Response::bytes(JAR),
+            _ => Response::status(404),
+        })
+        .await;
+        let jar_url = format!("{}/sodium.jar", cdn.url);
+        let api = test_server::serve(move |request| match request.path.as_str() {
+            // File 40 was deleted from CurseForge
+            "/v1/mods/files" => Response::json(
+                200,
+                json!({ "data": [
+                    { "id": 10, "modId": 1, "fileName": "sodium.jar", "downloadUrl": jar_url, "fileLength": JAR.len() },
+                    { "id": 20, "modId": 2, "fileName": "faithful.zip", "downloadUrl": null, "fileLength": 5 },
+                    { "id": 30, "modId": 3, "fileName": "complementary.zip", "downloadUrl": null, "fileLength": 5 },
+                ] }),
+            ),
+            "/v1/mods" => Response::json(
+                200,
+                json!({ "data": [
+                    { "id": 1, "name": "Sodium", "slug": "sodium", "classId": 6 },
+                    { "id": 2, "name": "Faithful", "slug": "faithful", "classId": CLASS_RESOURCE_PACKS },
+                    { "id": 3, "name": "Complementary", "slug": "complementary", "classId": CLASS_SHADERS },
+                    { "id": 4, "name": "Gone", "slug": "gone", "classId": 6 },
+                ] }),
+            ),
+            _ => Response::status(404),
+        })
+        .await;
+
+        let client = CurseForgeClient::new(&api.url, "key").unwrap();
+        let game_dir = std::env::temp_dir().join(format!("ezlauncher-cf-test-{}", std::process::id()));
+        let files = [
+            manifest_file(1, 10),
+            manifest_file(2, 20),
+            manifest_file(3, 30),
+            manifest_file(4, 40),
+            ManifestFile {
+                project_id: 5,
+                file_id: 50,
+                required: false,
+            },
+        ];
+        let manual = download_files(&client, &files, &game_dir).await.unwrap();
+
+        assert_eq!(std::fs::read(game_dir.join("mods/sodium.jar")).unwrap(), JAR);
+        let _ = std::fs::remove_dir_all(&game_dir);
+
+        let summary: Vec<(&str, &str, &str, PathBuf)> = manual
+            .iter()
+            .map(|m| {
+                let folder = m.target.strip_prefix(&game_dir).unwrap().to_path_buf();
+                (m.project_name.as_str(), m.file_name.as_str(), m.url.as_str(), folder)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (
+                    "Gone",
+                    "File 40",
+                    "https://www.curseforge.com/minecraft/mc-mods/gone/files/40",
+                    PathBuf::from("mods")
+                ),
+                (
+                    "Faithful",
+                    "faithful.zip",
+                    "https://www.curseforge.com/minecraft/texture-packs/faithful/files/20",
+                    PathBuf::from("resourcepacks")
+                ),
+                (
+                    "Complementary",
+                    "complementary.zip",
+                    "https://www.curseforge.com/minecraft/shaders/complementary/files/30",
+                    PathBuf::from("shaderpacks")
+                ),
+            ]
+        );
+
+        // Optional files are never asked for
+        let requested = api.requests()[0].json();
+        assert_eq!(requested["fileIds"], json!([10, 20, 30, 40]));
+    }
+}
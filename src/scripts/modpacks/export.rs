@@ -0,0 +1,219 @@
+use super::mrpack::{INDEX_FILE, MrpackFile, MrpackIndex};
+use crate::scripts::game::VersionType;
+use crate::scripts::game::utils::hex;
+use crate::scripts::instances::Instance;
+use crate::scripts::modrinth::ModrinthClient;
+use anyhow::Result;
+use sha1::Sha1;
+use sha2::{Digest, Sha512};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+
+/// Folders and files that are never worth shipping in a pack
+const EXCLUDED_ENTRIES: &[&str] = &["mods", "logs", "crash-reports", "screenshots", "natives"];
+/// Selected by default when exporting
+pub const DEFAULT_INCLUDES: &[&str] = &[
+    "config",
+    "defaultconfigs",
+    "kubejs",
+    "resourcepacks",
+    "shaderpacks",
+    "options.txt",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportOptions {
+    pub name: String,
+    pub version: String,
+    pub summary: Option<String>,
+    /// Entries of the game directory (besides `mods/`) to put into `overrides/`
+    pub includes: Vec<String>,
+}
+
+/// Top level entries of the game directory the user can pick from
+pub async fn exportable_entries(instance: &Instance) -> Result<Vec<String>> {
+    let mut entries = Vec::new();
+    let game_dir = instance.game_dir();
+    if !game_dir.exists() {
+        return Ok(entries);
+    }
+
+    let mut dir = tokio::fs::read_dir(game_dir).await?;
+    while let Some(entry) = dir.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !EXCLUDED_ENTRIES.contains(&name.as_str()) {
+            entries.push(name);
+        }
+    }
+    entries.sort();
+    Ok(entries)
+}
+
+struct HashedMod {
+    path: PathBuf,
+    /// Path inside the pack, e.g. `mods/sodium.jar`
+    pack_path: String,
+    sha1: String,
+    sha512: String,
+    size: u64,
+}
+
+/// Export `instance` as a `.mrpack` at `out_path`.
+/// Mods Modrinth knows are referenced by URL, everything else is bundled in `overrides/`.
+pub async fn export_mrpack(
+    client: &ModrinthClient,
+    instance: &Instance,
+    options: &ExportOptions,
+    out_path: &Path,
+) -> Result<()> {
+    // Step 1: Hash every enabled mod jar
+    let mods_dir = instance.game_dir().join("mods");
+    let mods = tokio::task::spawn_blocking(move || hash_mods(&mods_dir)).await??;
+    log::info!("Exporting {} with {} mods", instance.name, mods.len());
+
+    // Step 2: Look the hashes up on Modrinth
+    let hashes: Vec<String> = mods.iter().map(|m| m.sha512.clone()).collect();
+    let versions = if hashes.is_empty() {
+        HashMap::new()
+    } else {
+        client.get_versions_by_hashes(&hashes, "sha512").await?
+    };
+
+    let mut files = Vec::new();
+    let mut unresolved = Vec::new();
+    for hashed in mods {
+        let url = versions.get(&hashed.sha512).and_then(|version| {
+            version
+                .files
+                .iter()
+                .find(|f| f.hashes.sha512 == hashed.sha512)
+                .map(|f| f.url.clone())
+        });
+
+        match url {
+            Some(url) => files.push(MrpackFile {
+                path: hashed.pack_path,
+                hashes: HashMap::from([
+                    ("sha1".to_string(), hashed.sha1),
+                    ("sha512".to_string(), hashed.sha512),
+                ]),
+                env: None,
+                downloads: vec![url],
+                file_size: Some(hashed.size),
+            }),
+            None => {
+                log::info!("{} is not on Modrinth, bundling it in overrides", hashed.pack_path);
+                unresolved.push(hashed);
+            }
+        }
+    }
+
+    // Step 3: Write the index and overrides
+    let index = MrpackIndex {
+        format_version: 1,
+        game: "minecraft".to_string(),
+        version_id: options.version.clone(),
+        name: options.name.clone(),
+        summary: options.summary.clone(),
+        files,
+        dependencies: dependencies(instance),
+    };
+
+    let game_dir = instance.game_dir();
+    let includes = options.includes.clone();
+    let out_path = out_path.to_path_buf();
+    tokio::task::spawn_blocking(move || write_pack(&out_path, &index, &unresolved, &game_dir, &includes))
+        .await??;
+
+    log::info!("Exported {}", options.name);
+    Ok(())
+}
+
+fn dependencies(instance: &Instance) -> HashMap<String, String> {
+    let mut deps = HashMap::from([("minecraft".to_string(), instance.game_version.clone())]);
+    let key = match instance.loader {
+        VersionType::Vanilla => None,
+        VersionType::NeoForge => Some("neoforge"),
+        VersionType::Fabric => Some("fabric-loader"),
+        VersionType::Quilt => Some("quilt-loader"),
+    };
+    if let (Some(key), Some(version)) = (key, &instance.loader_version) {
+        deps.insert(key.to_string(), version.clone());
+    }
+    deps
+}
+
+fn hash_mods(mods_dir: &Path) -> Result<Vec<HashedMod>> {
+    let mut mods = Vec::new();
+    if !mods_dir.exists() {
+        return Ok(mods);
+    }
+
+    for entry in std::fs::read_dir(mods_dir)? {
+        let path = entry?.path();
+        // Disabled mods (`.jar.disabled`) are left out
+        if !path.is_file() || path.extension().is_none_or(|e| e != "jar") {
+            continue;
+        }
+
+        let bytes = std::fs::read(&path)?;
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        mods.push(HashedMod {
+            pack_path: format!("mods/{}", file_name),
+            sha1: hex(&Sha1::digest(&bytes)),
+            sha512: hex(&Sha512::digest(&bytes)),
+            size: bytes.len() as u64,
+            path,
+        });
+    }
+    mods.sort_by(|a, b| a.pack_path.cmp(&b.pack_path));
+    Ok(mods)
+}
+
+fn write_pack(
+    out_path: &Path,
+    index: &MrpackIndex,
+    unresolved: &[HashedMod],
+    game_dir: &Path,
+    includes: &[String],
+) -> Result<()> {
+    if let Some(parent) = out_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(out_path)?);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file(INDEX_FILE, options)?;
+    zip.write_all(serde_json::to_string_pretty(index)?.as_bytes())?;
+
+    for hashed in unresolved {
+        zip.start_file(format!("overrides/{}", hashed.pack_path), options)?;
+        zip.write_all(&std::fs::read(&hashed.path)?)?;
+    }
+
+    for include in includes {
+        let root = game_dir.join(include);
+        if !root.exists() {
+            continue;
+        }
+        for entry in walkdir::WalkDir::new(&root) {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = entry.path().strip_prefix(game_dir)?;
+            let name = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            zip.start_file(format!("overrides/{}", name), options)?;
+            zip.write_all(&std::fs::read(entry.path())?)?;
+        }
+    }
+
+    zip.finish()?;
+    Ok(())
+}
@@ -0,0 +1,53 @@
+pub mod curseforge;
+pub mod export;
+pub mod mrpack;
+
+use anyhow::Result;
+use std::path::{Component, Path, PathBuf};
+
+pub use curseforge::{CurseForgeImport, ManualDownload, import_curseforge_zip};
+pub use export::{DEFAULT_INCLUDES, ExportOptions, export_mrpack, exportable_entries};
+pub use mrpack::{import_modrinth_version, import_mrpack};
+
+/// Copy everything under `prefix/` in the pack archive into the game directory
+pub(crate) fn extract_overrides(pack_path: &Path, prefix: &str, game_dir: &Path) -> Result<()> {
+    let file = std::fs::File::open(pack_path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let Some(name) = entry.enclosed_name() else {
+            log::warn!("Skipping unsafe path in modpack: {}", entry.name());
+            continue;
+        };
+        let Ok(relative) = name.strip_prefix(prefix) else {
+            continue;
+        };
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+
+        let out_path = game_dir.join(relative);
+        if entry.is_dir() {
+            std::fs::create_dir_all(&out_path)?;
+        } else {
+            if let Some(parent) = out_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut out_file = std::fs::File::create(&out_path)?;
+            std::io::copy(&mut entry, &mut out_file)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reject absolute paths and `..` so a pack can't write outside the game directory
+pub(crate) fn safe_relative_path(path: &str) -> Result<PathBuf> {
+    let path = Path::new(path);
+    if !path.as_os_str().is_empty() && path.components().all(|c| matches!(c, Component::Normal(_))) {
+        Ok(path.to_path_buf())
+    } else {
+        Err(anyhow::anyhow!("Unsafe file path in modpack: {}", path.display()))
+    }
+}
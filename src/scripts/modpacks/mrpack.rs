@@ -0,0 +1,214 @@
+use crate::scripts::game::utils::{FileHash, download_file_verified};
+use crate::scripts::game::{DATA_DIR, VersionType};
+use crate::scripts::instances::{self, Instance};
+use crate::scripts::modrinth::ModrinthClient;
+use super::{extract_overrides, safe_relative_path};
+use anyhow::Result;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+pub(crate) const INDEX_FILE: &str = "modrinth.index.json";
+/// How many pack files are downloaded at once
+const MAX_CONCURRENT_DOWNLOADS: usize = 8;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrpackIndex {
+    pub format_version: u32,
+    pub game: String,
+    pub version_id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    pub files: Vec<MrpackFile>,
+    pub dependencies: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrpackFile {
+    pub path: String,
+    pub hashes: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<MrpackEnv>,
+    pub downloads: Vec<String>,
+    pub file_size: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MrpackEnv {
+    pub client: String,
+    pub server: String,
+}
+
+impl MrpackFile {
+    fn client_supported(&self) -> bool {
+        self.env.as_ref().is_none_or(|env| env.client != "unsupported")
+    }
+}
+
+/// Create a new instance from a `.mrpack` file.
+/// `name` overrides the pack name from the index.
+pub async fn import_mrpack(path: &Path, name: Option<&str>) -> Result<Instance> {
+    // Step 1: Read the index
+    let pack_path = path.to_path_buf();
+    let index = tokio::task::spawn_blocking(move || read_index(&pack_path)).await??;
+    if index.game != "minecraft" {
+        return Err(anyhow::anyhow!("Unsupported modpack game: {}", index.game));
+    }
+    if index.format_version != 1 {
+        return Err(anyhow::anyhow!("Unsupported mrpack format version {}", index.format_version));
+    }
+    log::info!("Importing modpack {} {}", index.name, index.version_id);
+
+    // Step 2: Create the instance from the pack dependencies
+    let (game_version, loader, loader_version) = resolve_dependencies(&index.dependencies)?;
+    let instance = instances::create_instance(
+        name.unwrap_or(&index.name),
+        &game_version,
+        loader,
+        loader_version,
+    )
+    .await?;
+
+    // Step 3: Download files and apply overrides, removing the half installed instance on failure
+    if let Err(e) = install_pack(path, &index, &instance).await {
+        log::error!("Modpack install failed, removing {}: {}", instance.name, e);
+        if let Err(e) = instances::delete_instance(&instance).await {
+            log::warn!("Failed to remove instance {}: {}", instance.id, e);
+        }
+        return Err(e);
+    }
+
+    log::info!("Modpack {} installed as {}", index.name, instance.name);
+    Ok(instance)
+}
+
+/// Download a modpack version from Modrinth and import it
+pub async fn import_modrinth_version(client: &ModrinthClient, version_id: &str) -> Result<Instance> {
+    let version = client.get_version(version_id).await?;
+    let file = version
+        .files
+        .iter()
+        .find(|f| f.filename.ends_with(".mrpack"))
+        .ok_or_else(|| anyhow::anyhow!("Version {} has no .mrpack file", version.name))?;
+
+    // The file name comes from the API, keep it inside the downloads folder
+    let path = PathBuf::from(DATA_DIR)
+        .join("downloads")
+        .join(safe_relative_path(&file.filename)?);
+    log::info!("Downloading modpack {}...", file.filename);
+    download_file_verified(
+        client.http(),
+        &file.url,
+        &path,
+        FileHash::Sha512(&file.hashes.sha512),
+        Some(file.size),
+    )
+    .await?;
+
+    let result = import_mrpack(&path, None).await;
+    let _ = tokio::fs::remove_file(&path).await;
+    result
+}
+
+fn read_index(path: &Path) -> Result<MrpackIndex> {
+    let file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+    let mut entry = archive
+        .by_name(INDEX_FILE)
+        .map_err(|_| anyhow::anyhow!("Not a Modrinth modpack: {} is missing", INDEX_FILE))?;
+
+    let mut content = String::new();
+    entry.read_to_string(&mut content)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+fn resolve_dependencies(deps: &HashMap<String, String>) -> Result<(String, VersionType, Option<String>)> {
+    let game_version = deps
+        .get("minecraft")
+        .ok_or_else(|| anyhow::anyhow!("Modpack doesn't specify a Minecraft version"))?
+        .clone();
+
+    let loader = if let Some(version) = deps.get("neoforge") {
+        (VersionType::NeoForge, Some(version.clone()))
+    } else if let Some(version) = deps.get("fabric-loader") {
+        (VersionType::Fabric, Some(version.clone()))
+    } else if let Some(version) = deps.get("quilt-loader") {
+        (VersionType::Quilt, Some(version.clone()))
+    } else if deps.contains_key("forge") {
+        return Err(anyhow::anyhow!("Forge modpacks are not supported"));
+    } else {
+        (VersionType::Vanilla, None)
+    };
+
+    Ok((game_version, loader.0, loader.1))
+}
+
+async fn install_pack(path: &Path, index: &MrpackIndex, instance: &Instance) -> Result<()> {
+    let game_dir = instance.game_dir();
+    download_files(&index.files, &game_dir).await?;
+
+    // Client overrides are applied last so they win over the shared ones
+    let pack_path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        extract_overrides(&pack_path, "overrides", &game_dir)?;
+        extract_overrides(&pack_path, "client-overrides", &game_dir)
+    })
+    .await??;
+
+    Ok(())
+}
+
+async fn download_files(files: &[MrpackFile], game_dir: &Path) -> Result<()> {
+    let client = Client::builder().user_agent("ezLauncher/0.2.0").build()?;
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_DOWNLOADS));
+    let mut tasks = JoinSet::new();
+
+    for file in files.iter().filter(|f| f.client_supported()) {
+        let target = game_dir.join(safe_relative_path(&file.path)?);
+        let sha512 = file
+            .hashes
+            .get("sha512")
+            .ok_or_else(|| anyhow::anyhow!("{} has no SHA-512 hash", file.path))?
+            .clone();
+
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+        let file = file.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await?;
+            let mut last_error = anyhow::anyhow!("{} has no download URLs", file.path);
+            // Try the mirrors in order
+            for url in &file.downloads {
+                let hash = FileHash::Sha512(&sha512);
+                match download_file_verified(&client, url, &target, hash, file.file_size).await {
+                    Ok(()) => {
+                        log::info!("Downloaded {}", file.path);
+                        return Ok(());
+                    }
+                    Err(e) => {
+                        log::warn!("Failed to download {} from {}: {}", file.path, url, e);
+                        last_error = e;
+                    }
+                }
+            }
+            Err(last_error)
+        });
+    }
+
+    while let Some(result) = tasks.join_next().await {
+        if let Err(e) = result? {
+            tasks.abort_all();
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
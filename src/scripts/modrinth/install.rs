@@ -0,0 +1,383 @@
+use super::{ModrinthClient, ProjectType, Version};
+use crate::scripts::game::VersionType;
+use crate::scripts::game::utils::{FileHash, download_file_verified, hex};
+use crate::scripts::instances::Instance;
+use crate::scripts::modpacks::safe_relative_path;
+use anyhow::Result;
+use sha2::{Digest, Sha512};
+use std::collections::{HashSet, VecDeque};
+use std::path::Path;
+
+/// A file written into the instance by `install_project`
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstalledFile {
+    pub project_id: String,
+    pub version_number: String,
+    pub file_name: String,
+}
+
+/// Modrinth loader names whose mods run on an instance loader, none for vanilla.
+/// Quilt loads Fabric mods too.
+pub fn loader_names(loader: VersionType) -> &'static [&'static str] {
+    match loader {
+        VersionType::Vanilla => &[],
+        VersionType::NeoForge => &["neoforge"],
+        VersionType::Fabric => &["fabric"],
+        VersionType::Quilt => &["quilt", "fabric"],
+    }
+}
+
+enum Pending {
+    Project(String),
+    Version(String),
+}
+
+/// Install the newest version of a project compatible with the instance,
+/// pulling in its required dependencies. Projects already present are skipped.
+pub async fn install_project(
+    client: &ModrinthClient,
+    instance: &Instance,
+    project_id: &str,
+    project_type: ProjectType,
+) -> Result<Vec<InstalledFile>> {
+    let target_dir = instance.game_dir().join(project_type.folder());
+    install_into(client, instance, &target_dir, project_id, project_type).await
+}
+
+async fn install_into(
+    client: &ModrinthClient,
+    instance: &Instance,
+    target_dir: &Path,
+    project_id: &str,
+    project_type: ProjectType,
+) -> Result<Vec<InstalledFile>> {
+    // Step 1: Find out which projects are already installed
+    let mut installed = installed_projects(client, target_dir).await?;
+    if installed.contains(project_id) {
+        log::info!("{} is already installed", project_id);
+        return Ok(Vec::new());
+    }
+
+    // Step 2: Walk the dependency tree breadth first
+    let mut queue = VecDeque::from([Pending::Project(project_id.to_string())]);
+    let mut files = Vec::new();
+    while let Some(pending) = queue.pop_front() {
+        let version = match pending {
+            Pending::Version(version_id) => {
+                let version = client.get_version(&version_id).await?;
+                if is_compatible(&version, instance, project_type) {
+                    version
+                } else {
+                    log::warn!(
+                        "Pinned {} {} doesn't support {}, looking for another version",
+                        version.name,
+                        version.version_number,
+                        instance.version_label()
+                    );
+                    newest_compatible(client, instance, &version.project_id, project_type).await?
+                }
+            }
+            Pending::Project(id) => newest_compatible(client, instance, &id, project_type).await?,
+        };
+        if !installed.insert(version.project_id.clone()) {
+            continue;
+        }
+
+        let file = version
+            .primary_file()
+            .ok_or_else(|| anyhow::anyhow!("{} has no files", version.name))?;
+        log::info!("Installing {} {}", file.filename, version.version_number);
+        download_file_verified(
+            client.http(),
+            &file.url,
+            &target_dir.join(safe_relative_path(&file.filename)?),
+            FileHash::Sha512(&file.hashes.sha512),
+            Some(file.size),
+        )
+        .await?;
+
+        files.push(InstalledFile {
+            project_id: version.project_id.clone(),
+            version_number: version.version_number.clone(),
+            file_name: file.filename.clone(),
+        });
+
+        // Only mods declare dependencies we can install
+        if project_type != ProjectType::Mod {
+            continue;
+        }
+        for dep in version.dependencies.iter().filter(|d| d.dependency_type == "required") {
+            if dep.project_id.as_ref().is_some_and(|id| installed.contains(id)) {
+                continue;
+            }
+            match (&dep.version_id, &dep.project_id) {
+                (Some(version_id), _) => queue.push_back(Pending::Version(version_id.clone())),
+                (None, Some(project_id)) => queue.push_back(Pending::Project(project_id.clone())),
+                (None, None) => log::warn!("{} has a dependency without project or version", version.name),
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+fn loaders_for(instance: &Instance, project_type: ProjectType) -> &'static [&'static str] {
+    // Resource packs and shaders aren't tied to the mod loader
+    match project_type {
+        ProjectType::Mod => loader_names(instance.loader),
+        _ => &[],
+    }
+}
+
+fn is_compatible(version: &Version, instance: &Instance, project_type: ProjectType) -> bool {
+    let loaders = loaders_for(instance, project_type);
+    version.game_versions.contains(&instance.game_version)
+        && (loaders.is_empty() || version.loaders.iter().any(|l| loaders.contains(&l.as_str())))
+}
+
+/// Newest release compatible with the instance, or the newest beta/alpha if there's no release
+async fn newest_compatible(
+    client: &ModrinthClient,
+    instance: &Instance,
+    project_id: &str,
+    project_type: ProjectType,
+) -> Result<Version> {
+    let loaders = loaders_for(instance, project_type);
+    client
+        .get_project_versions(project_id, loaders, Some(&instance.game_version))
+        .await?
+        .into_iter()
+        .filter(|v| is_compatible(v, instance, project_type))
+        .max_by(|a, b| {
+            let stability = |v: &Version| match v.version_type.as_deref() {
+                Some("alpha") => 0,
+                Some("beta") => 1,
+                _ => 2,
+            };
+            stability(a)
+                .cmp(&stability(b))
+                .then_with(|| a.date_published.cmp(&b.date_published))
+        })
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "No version of {} is compatible with {}",
+                project_id,
+                instance.version_label()
+            )
+        })
+}
+
+/// Project ids of the files in `dir` Modrinth knows about
+async fn installed_projects(client: &ModrinthClient, dir: &Path) -> Result<HashSet<String>> {
+    let dir = dir.to_path_buf();
+    let hashes = tokio::task::spawn_blocking(move || -> Result<Vec<String>> {
+        let mut hashes = Vec::new();
+        if !dir.exists() {
+            return Ok(hashes);
+        }
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_file() {
+                hashes.push(hex(&Sha512::digest(std::fs::read(&path)?)));
+            }
+        }
+        Ok(hashes)
+    })
+    .await??;
+
+    if hashes.is_empty() {
+        return Ok(HashSet::new());
+    }
+    let versions = client.get_versions_by_hashes(&hashes, "sha512").await?;
+    Ok(versions.into_values().map(|v| v.project_id).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scripts::test_server::{self, Response};
+    use serde_json::{Value, json};
+
+    fn instance() -> Instance {
+        Instance {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            game_version: "1.21.1".to_string(),
+            loader: VersionType::Quilt,
+            loader_version: Some("0.27.1".to_string()),
+            java: Default::default(),
+            last_played: None,
+            playtime_secs: 0,
+            hooks: Default::default(),
+        }
+    }
+
+    fn jar(id: &str) -> Vec<u8> {
+        format!("jar of {}", id).into_bytes()
+    }
+
+    struct Stub<'a> {
+        id: &'a str,
+        project: &'a str,
+        game_version: &'a str,
+        loader: &'a str,
+        version_type: &'a str,
+        date: &'a str,
+        filename: &'a str,
+        dependencies: Value,
+    }
+
+    impl Stub<'_> {
+        fn json(&self, cdn: &str) -> Value {
+            let bytes = jar(self.id);
+            json!({
+                "id": self.id,
+                "project_id": self.project,
+                "name": self.id,
+                "version_number": self.id,
+                "version_type": self.version_type,
+                "date_published": self.date,
+                "game_versions": [self.game_version],
+                "loaders": [self.loader],
+                "files": [{
+                    "url": format!("{}/{}", cdn, self.id),
+                    "filename": self.filename,
+                    "hashes": { "sha1": "", "sha512": hex(&Sha512::digest(&bytes)) },
+                    "primary": true,
+                    "size": bytes.len(),
+                }],
+                "dependencies": self.dependencies,
+            })
+        }
+    }
+
+    fn stub<'a>(id: &'a str, project: &'a str, version_type: &'a str, date: &'a str) -> Stub<'a> {
+        Stub {
+            id,
+            project,
+            game_version: "1.21.1",
+            loader: "fabric",
+            version_type,
+            date,
+            filename: "mod.jar",
+            dependencies: json!([]),
+        }
+    }
+
+    /// Modrinth stand-in serving `versions` and their files, where every jar already present is `fabric-api`
+    async fn modrinth(versions: Vec<Stub<'static>>) -> test_server::TestServer {
+        let cdn = test_server::serve(|request| Response::bytes(&jar(request.path.trim_start_matches('/')))).await;
+        let versions: Vec<Value> = versions.iter().map(|v| v.json(&cdn.url)).collect();
+        test_server::serve(move |request| {
+            let path = request.path.split('?').next().unwrap_or_default();
+            if path == "/version_files" {
+                let hashes = request.json()["hashes"].as_array().cloned().unwrap_or_default();
+                let installed = json!({
+                    "id": "installed",
+                    "project_id": "fabric-api",
+                    "name": "Fabric API",
+                    "version_number": "1.0",
+                });
+                let known: serde_json::Map<String, Value> = hashes
+                    .iter()
+                    .filter_map(|h| Some((h.as_str()?.to_string(), installed.clone())))
+                    .collect();
+                return Response::json(200, known.into());
+            }
+            if let Some(id) = path.strip_prefix("/version/") {
+                return match versions.iter().find(|v| v["id"] == id) {
+                    Some(version) => Response::json(200, version.clone()),
+                    None => Response::status(404),
+                };
+            }
+            if let Some(project) = path.strip_prefix("/project/").and_then(|p| p.strip_suffix("/version")) {
+                let listed: Vec<&Value> = versions.iter().filter(|v| v["project_id"] == project).collect();
+                return Response::json(200, json!(listed));
+            }
+            Response::status(404)
+        })
+        .await
+    }
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("ezlauncher-modrinth-{}-{}", name, std::process::id()))
+    }
+
+    #[tokio::test]
+    async fn resolves_dependencies_for_the_instance() {
+        let server = modrinth(vec![
+            // The newest release doesn't run on Quilt or Fabric, the newest build is a beta
+            Stub {
+                loader: "neoforge",
+                filename: "sodium-neoforge.jar",
+                ..stub("sodium-neo", "sodium", "release", "2024-09-01T00:00:00Z")
+            },
+            Stub {
+                filename: "sodium-beta.jar",
+                ..stub("sodium-beta", "sodium", "beta", "2024-08-01T00:00:00Z")
+            },
+            Stub {
+                filename: "sodium.jar",
+                dependencies: json!([
+                    { "project_id": "fabric-api", "dependency_type": "required" },
+                    { "project_id": "lib", "version_id": "lib-old", "dependency_type": "required" },
+                    { "project_id": "extras", "dependency_type": "optional" },
+                ]),
+                ..stub("sodium-stable", "sodium", "release", "2024-07-01T00:00:00Z")
+            },
+            // The pinned dependency is for another game version
+            Stub {
+                game_version: "1.20.1",
+                filename: "lib-old.jar",
+                ..stub("lib-old", "lib", "release", "2023-01-01T00:00:00Z")
+            },
+            Stub {
+                loader: "quilt",
+                filename: "lib.jar",
+                ..stub("lib-new", "lib", "release", "2024-06-01T00:00:00Z")
+            },
+        ])
+        .await;
+        let dir = temp_dir("deps");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("fabric-api.jar"), b"fabric api").unwrap();
+
+        let client = ModrinthClient::new(&server.url).unwrap();
+        let installed = install_into(&client, &instance(), &dir, "sodium", ProjectType::Mod).await;
+        let on_disk = std::fs::read(dir.join("lib.jar"));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let names: Vec<String> = installed.unwrap().into_iter().map(|f| f.file_name).collect();
+        assert_eq!(names, ["sodium.jar", "lib.jar"]);
+        assert_eq!(on_disk.unwrap(), jar("lib-new"));
+
+        let listing = server
+            .requests()
+            .into_iter()
+            .find(|r| r.path.starts_with("/project/sodium/version"))
+            .unwrap();
+        assert!(
+            listing.path.contains("%5B%22quilt%22%2C%22fabric%22%5D"),
+            "{}",
+            listing.path
+        );
+    }
+
+    #[tokio::test]
+    async fn file_names_cant_escape_the_folder() {
+        let server = modrinth(vec![Stub {
+            filename: "../evil.jar",
+            ..stub("evil", "evil", "release", "2024-01-01T00:00:00Z")
+        }])
+        .await;
+        let dir = temp_dir("escape");
+
+        let client = ModrinthClient::new(&server.url).unwrap();
+        let result = install_into(&client, &instance(), &dir.join("mods"), "evil", ProjectType::Mod).await;
+        let escaped = dir.join("evil.jar").exists();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(result.is_err());
+        assert!(!escaped);
+    }
+}
@@ -0,0 +1,161 @@
+pub mod install;
+pub mod types;
+
+use anyhow::Result;
+use reqwest::Client;
+use serde_json::json;
+use std::collections::HashMap;
+pub use install::{InstalledFile, install_project, loader_names};
+pub use types::{
+    Dependency, Hashes, Project, ProjectType, SearchHit, SearchQuery, SearchResponse, Version,
+    VersionFile,
+};
+
+pub const MODRINTH_API: &str = "https://api.modrinth.com/v2";
+
+/// Thin client over the Modrinth v2 API
+#[derive(Debug, Clone)]
+pub struct ModrinthClient {
+    base_url: String,
+    http: Client,
+}
+
+impl ModrinthClient {
+    pub fn new(base_url: &str) -> Result<Self> {
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            http: Client::builder().user_agent("EzYDark/ezLauncher/0.2.0").build()?,
+        })
+    }
+
+    /// Client for the API base configured in the launcher settings
+    pub async fn from_settings() -> Result<Self> {
+        let settings = crate::scripts::settings::load_settings().await?;
+        Self::new(&settings.modrinth_api)
+    }
+
+    pub fn http(&self) -> &Client {
+        &self.http
+    }
+
+    pub async fn search(&self, query: &SearchQuery) -> Result<SearchResponse> {
+        // Facets are AND-ed lists of OR-ed conditions
+        let mut facets = vec![vec![format!("project_type:{}", query.project_type.api_value())]];
+        if let Some(game_version) = &query.game_version {
+            facets.push(vec![format!("versions:{}", game_version)]);
+        }
+        if !query.loaders.is_empty() {
+            facets.push(query.loaders.iter().map(|l| format!("categories:{}", l)).collect());
+        }
+
+        let response = self
+            .http
+            .get(format!("{}/search", self.base_url))
+            .query(&[
+                ("query", query.query.clone()),
+                ("facets", serde_json::to_string(&facets)?),
+                ("offset", query.offset.to_string()),
+                ("limit", query.limit.to_string()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(response)
+    }
+
+    pub async fn get_project(&self, id_or_slug: &str) -> Result<Project> {
+        let project = self
+            .http
+            .get(format!("{}/project/{}", self.base_url, id_or_slug))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(project)
+    }
+
+    /// Versions of a project, newest first, optionally filtered by loaders and game version
+    pub async fn get_project_versions(
+        &self,
+        id_or_slug: &str,
+        loaders: &[&str],
+        game_version: Option<&str>,
+    ) -> Result<Vec<Version>> {
+        let mut params = Vec::new();
+        if !loaders.is_empty() {
+            params.push(("loaders", serde_json::to_string(loaders)?));
+        }
+        if let Some(game_version) = game_version {
+            params.push(("game_versions", serde_json::to_string(&[game_version])?));
+        }
+
+        let versions = self
+            .http
+            .get(format!("{}/project/{}/version", self.base_url, id_or_slug))
+            .query(&params)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(versions)
+    }
+
+    pub async fn get_version(&self, version_id: &str) -> Result<Version> {
+        let version = self
+            .http
+            .get(format!("{}/version/{}", self.base_url, version_id))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(version)
+    }
+
+    /// Look up versions by file hash, keyed by the hash. Unknown hashes are simply missing.
+    pub async fn get_versions_by_hashes(
+        &self,
+        hashes: &[String],
+        algorithm: &str,
+    ) -> Result<HashMap<String, Version>> {
+        let versions = self
+            .http
+            .post(format!("{}/version_files", self.base_url))
+            .json(&json!({ "hashes": hashes, "algorithm": algorithm }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(versions)
+    }
+
+    /// Newest version for each hash matching the loaders and game versions, keyed by the hash
+    pub async fn get_latest_versions_by_hashes(
+        &self,
+        hashes: &[String],
+        algorithm: &str,
+        loaders: &[&str],
+        game_versions: &[&str],
+    ) -> Result<HashMap<String, Version>> {
+        let versions = self
+            .http
+            .post(format!("{}/version_files/update", self.base_url))
+            .json(&json!({
+                "hashes": hashes,
+                "algorithm": algorithm,
+                "loaders": loaders,
+                "game_versions": game_versions,
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(versions)
+    }
+}
@@ -0,0 +1,122 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Version {
+    pub id: String,
+    pub project_id: String,
+    pub name: String,
+    pub version_number: String,
+    /// release, beta or alpha
+    pub version_type: Option<String>,
+    pub changelog: Option<String>,
+    pub date_published: Option<String>,
+    #[serde(default)]
+    pub game_versions: Vec<String>,
+    #[serde(default)]
+    pub loaders: Vec<String>,
+    #[serde(default)]
+    pub files: Vec<VersionFile>,
+    #[serde(default)]
+    pub dependencies: Vec<Dependency>,
+}
+
+impl Version {
+    /// The file marked as primary, or the first one if none is
+    pub fn primary_file(&self) -> Option<&VersionFile> {
+        self.files.iter().find(|f| f.primary).or(self.files.first())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct VersionFile {
+    pub url: String,
+    pub filename: String,
+    pub hashes: Hashes,
+    #[serde(default)]
+    pub primary: bool,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Hashes {
+    pub sha1: String,
+    pub sha512: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Dependency {
+    pub version_id: Option<String>,
+    pub project_id: Option<String>,
+    pub file_name: Option<String>,
+    /// required, optional, incompatible or embedded
+    pub dependency_type: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Project {
+    pub id: String,
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub project_type: String,
+    pub icon_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SearchResponse {
+    pub hits: Vec<SearchHit>,
+    pub offset: u32,
+    pub limit: u32,
+    pub total_hits: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SearchHit {
+    pub project_id: String,
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub author: String,
+    pub project_type: String,
+    pub downloads: u64,
+    pub icon_url: Option<String>,
+}
+
+/// Kinds of projects the browse page can install
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProjectType {
+    Mod,
+    ResourcePack,
+    Shader,
+}
+
+impl ProjectType {
+    /// Value of the `project_type` search facet
+    pub fn api_value(&self) -> &'static str {
+        match self {
+            ProjectType::Mod => "mod",
+            ProjectType::ResourcePack => "resourcepack",
+            ProjectType::Shader => "shader",
+        }
+    }
+
+    /// Folder of the game directory the files go into
+    pub fn folder(&self) -> &'static str {
+        match self {
+            ProjectType::Mod => "mods",
+            ProjectType::ResourcePack => "resourcepacks",
+            ProjectType::Shader => "shaderpacks",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchQuery {
+    pub query: String,
+    pub project_type: ProjectType,
+    pub game_version: Option<String>,
+    /// Any of these loaders will do
+    pub loaders: Vec<String>,
+    pub offset: u32,
+    pub limit: u32,
+}
@@ -0,0 +1,219 @@
+use super::types::{DependencyKind, MetadataFormat, ModInfo};
+use super::{PLATFORM_IDS, list_mods, versions};
+use crate::scripts::game::VersionType;
+use crate::scripts::instances::Instance;
+use anyhow::Result;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    /// The game is not going to start
+    Error,
+    /// Likely a problem, but the game may still load
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModIssue {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl ModIssue {
+    fn error(message: String) -> Self {
+        Self { severity: Severity::Error, message }
+    }
+
+    fn warning(message: String) -> Self {
+        Self { severity: Severity::Warning, message }
+    }
+}
+
+/// A mod id that is present in the instance and where it comes from
+struct Provider<'a> {
+    version: Option<&'a str>,
+    source: &'a str,
+}
+
+pub async fn analyze_mods(instance: &Instance) -> Result<Vec<ModIssue>> {
+    let mods = list_mods(instance).await?;
+    Ok(analyze(instance, &mods))
+}
+
+/// Check the enabled mods for missing dependencies, duplicates,
+/// incompatibilities and game/loader version mismatches
+pub fn analyze(instance: &Instance, mods: &[ModInfo]) -> Vec<ModIssue> {
+    let mut issues = Vec::new();
+    let enabled: Vec<&ModInfo> = mods.iter().filter(|m| m.enabled).collect();
+
+    // Step 1: Collect every id that is present, bundled and provided ones included
+    let mut providers: HashMap<&str, Vec<Provider>> = HashMap::new();
+    for info in &enabled {
+        if let Some(id) = &info.id {
+            providers.entry(id).or_default().push(Provider {
+                version: info.version.as_deref(),
+                source: &info.file_name,
+            });
+        }
+        for id in &info.provides {
+            providers.entry(id).or_default().push(Provider {
+                version: info.version.as_deref(),
+                source: &info.file_name,
+            });
+        }
+        for bundled in &info.bundled {
+            providers.entry(&bundled.id).or_default().push(Provider {
+                version: bundled.version.as_deref(),
+                source: &info.file_name,
+            });
+        }
+    }
+
+    // Step 2: The same mod id twice (as separate jars) fails to load
+    let mut by_id: HashMap<&str, Vec<&str>> = HashMap::new();
+    for info in &enabled {
+        if let Some(id) = &info.id {
+            by_id.entry(id).or_default().push(&info.file_name);
+        }
+    }
+    let mut duplicates: Vec<_> = by_id.into_iter().filter(|(_, files)| files.len() > 1).collect();
+    duplicates.sort();
+    for (id, files) in duplicates {
+        issues.push(ModIssue::error(format!(
+            "{} is installed more than once: {}",
+            id,
+            files.join(", ")
+        )));
+    }
+
+    // Step 3: Check every declared dependency
+    for info in &enabled {
+        if let Some(issue) = loader_mismatch(instance, info) {
+            issues.push(issue);
+            // Its dependencies are for another loader, checking them would only add noise
+            continue;
+        }
+
+        let maven = matches!(info.format, Some(MetadataFormat::NeoForge | MetadataFormat::Forge));
+        for dep in &info.dependencies {
+            if dep.kind == DependencyKind::Optional || dep.id == "java" {
+                continue;
+            }
+
+            // Game and loader versions come from the instance, not from a jar
+            if PLATFORM_IDS.contains(&dep.id.as_str()) {
+                let (Some(range), Some(version)) = (&dep.versions, platform_version(instance, &dep.id)) else {
+                    continue;
+                };
+                if dep.kind == DependencyKind::Required
+                    && versions::matches(range, &version, maven) == Some(false)
+                {
+                    issues.push(ModIssue::error(format!(
+                        "{} requires {} {}, but the instance uses {}",
+                        info.name,
+                        platform_name(&dep.id),
+                        range,
+                        version
+                    )));
+                }
+                continue;
+            }
+
+            let present = providers.get(dep.id.as_str()).map(Vec::as_slice).unwrap_or_default();
+            let in_range: Vec<&Provider> = present
+                .iter()
+                .filter(|p| match (&dep.versions, p.version) {
+                    (Some(range), Some(version)) => versions::matches(range, version, maven) != Some(false),
+                    _ => true,
+                })
+                .collect();
+
+            match dep.kind {
+                DependencyKind::Required if present.is_empty() => {
+                    issues.push(ModIssue::error(format!(
+                        "{} requires {}{}, which is not installed",
+                        info.name,
+                        dep.id,
+                        range_suffix(&dep.versions)
+                    )));
+                }
+                DependencyKind::Required if in_range.is_empty() => {
+                    let found = present[0].version.unwrap_or("an unknown version");
+                    issues.push(ModIssue::error(format!(
+                        "{} requires {}{}, but {} is installed ({})",
+                        info.name,
+                        dep.id,
+                        range_suffix(&dep.versions),
+                        found,
+                        present[0].source
+                    )));
+                }
+                DependencyKind::Incompatible if !in_range.is_empty() => {
+                    issues.push(ModIssue::error(format!(
+                        "{} is incompatible with {}{} ({})",
+                        info.name,
+                        dep.id,
+                        range_suffix(&dep.versions),
+                        in_range[0].source
+                    )));
+                }
+                DependencyKind::Conflicting if !in_range.is_empty() => {
+                    issues.push(ModIssue::warning(format!(
+                        "{} conflicts with {}{} ({}), it may misbehave",
+                        info.name,
+                        dep.id,
+                        range_suffix(&dep.versions),
+                        in_range[0].source
+                    )));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    issues
+}
+
+/// The instance's version of a platform dependency id, if it applies to this instance
+fn platform_version(instance: &Instance, id: &str) -> Option<String> {
+    match (id, instance.loader) {
+        ("minecraft", _) => Some(instance.game_version.clone()),
+        ("fabricloader", VersionType::Fabric)
+        | ("quilt_loader", VersionType::Quilt)
+        | ("neoforge", VersionType::NeoForge) => instance.loader_version.clone(),
+        _ => None,
+    }
+}
+
+fn platform_name(id: &str) -> &str {
+    match id {
+        "minecraft" => "Minecraft",
+        "fabricloader" => "Fabric Loader",
+        "quilt_loader" => "Quilt Loader",
+        "neoforge" => "NeoForge",
+        other => other,
+    }
+}
+
+fn range_suffix(range: &Option<String>) -> String {
+    range.as_ref().map(|r| format!(" {}", r)).unwrap_or_default()
+}
+
+fn loader_mismatch(instance: &Instance, info: &ModInfo) -> Option<ModIssue> {
+    let format = info.format?;
+    let supported = match instance.loader {
+        VersionType::Vanilla => false,
+        VersionType::NeoForge => matches!(format, MetadataFormat::NeoForge | MetadataFormat::Forge),
+        VersionType::Fabric => format == MetadataFormat::Fabric,
+        // Quilt loads Fabric mods too
+        VersionType::Quilt => matches!(format, MetadataFormat::Quilt | MetadataFormat::Fabric),
+    };
+    if supported || format == MetadataFormat::McModInfo {
+        return None;
+    }
+
+    Some(ModIssue::warning(format!(
+        "{} is a {:?} mod and won't be loaded by {:?}",
+        info.name, format, instance.loader
+    )))
+}
@@ -0,0 +1,476 @@
+use super::types::{BundledMod, DependencyKind, MetadataFormat, ModDependency, ParsedMetadata};
+use anyhow::Result;
+use serde_json::Value;
+use std::io::Read;
+
+const JAR_VERSION: &str = "${file.jarVersion}";
+
+/// Metadata files in the order they are tried
+const METADATA_FILES: &[(&str, MetadataFormat)] = &[
+    ("fabric.mod.json", MetadataFormat::Fabric),
+    ("quilt.mod.json", MetadataFormat::Quilt),
+    ("META-INF/neoforge.mods.toml", MetadataFormat::NeoForge),
+    ("META-INF/mods.toml", MetadataFormat::Forge),
+    ("mcmod.info", MetadataFormat::McModInfo),
+];
+
+pub(crate) fn read_metadata<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+) -> Result<Option<(MetadataFormat, ParsedMetadata)>> {
+    for (file, format) in METADATA_FILES {
+        let Some(content) = read_entry_string(archive, file)? else {
+            continue;
+        };
+
+        let mut parsed = match format {
+            MetadataFormat::Fabric => parse_fabric(&parse_json(&content)?),
+            MetadataFormat::Quilt => parse_quilt(&parse_json(&content)?),
+            MetadataFormat::NeoForge | MetadataFormat::Forge => parse_mods_toml(&content)?,
+            MetadataFormat::McModInfo => parse_mcmod_info(&parse_json(&content)?),
+        };
+
+        // NeoForge lists Jar-in-Jar files separately from the mod metadata
+        if matches!(format, MetadataFormat::NeoForge | MetadataFormat::Forge) {
+            if let Some(jarjar) = read_entry_string(archive, "META-INF/jarjar/metadata.json")? {
+                let jarjar: Value = serde_json::from_str(&jarjar)?;
+                if let Some(jars) = jarjar.get("jars").and_then(Value::as_array) {
+                    parsed.nested_jars = jars.iter().filter_map(|j| string(j, "path")).collect();
+                }
+            }
+        }
+
+        // Forge style jars usually template the version from the jar manifest
+        let templated = |version: &Option<String>| version.as_deref() == Some(JAR_VERSION);
+        if templated(&parsed.version) || parsed.other_mods.iter().any(|m| templated(&m.version)) {
+            let jar_version = read_entry_string(archive, "META-INF/MANIFEST.MF")?
+                .and_then(|manifest| manifest_value(&manifest, "Implementation-Version"));
+            let versions = std::iter::once(&mut parsed.version).chain(parsed.other_mods.iter_mut().map(|m| &mut m.version));
+            for version in versions.filter(|v| templated(v)) {
+                *version = jar_version.clone();
+            }
+        }
+
+        return Ok(Some((*format, parsed)));
+    }
+
+    Ok(None)
+}
+
+pub(crate) fn read_entry_bytes<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+) -> Result<Option<Vec<u8>>> {
+    let mut entry = match archive.by_name(name.trim_start_matches('/')) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes)?;
+    Ok(Some(bytes))
+}
+
+fn read_entry_string<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+) -> Result<Option<String>> {
+    Ok(read_entry_bytes(archive, name)?.map(|bytes| String::from_utf8_lossy(&bytes).to_string()))
+}
+
+/// Parse metadata JSON the way the loaders do, which accept comments and raw newlines in strings
+fn parse_json(content: &str) -> Result<Value> {
+    match serde_json::from_str(content) {
+        Ok(value) => Ok(value),
+        Err(e) => serde_json::from_str(&lenient_json(content)).map_err(|_| e.into()),
+    }
+}
+
+/// Strip comments and escape control characters inside strings
+fn lenient_json(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            match c {
+                '\\' => {
+                    out.push(c);
+                    out.extend(chars.next());
+                }
+                '"' => {
+                    in_string = false;
+                    out.push(c);
+                }
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c.is_control() => {}
+                c => out.push(c),
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut last = ' ';
+                for c in chars.by_ref() {
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+                out.push(' ');
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn manifest_value(manifest: &str, key: &str) -> Option<String> {
+    manifest.lines().find_map(|line| {
+        let (k, v) = line.split_once(':')?;
+        (k.trim() == key).then(|| v.trim().to_string())
+    })
+}
+
+fn string(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+fn parse_fabric(json: &Value) -> ParsedMetadata {
+    let authors = json
+        .get("authors")
+        .and_then(Value::as_array)
+        .map(|authors| {
+            authors
+                .iter()
+                .filter_map(|a| a.as_str().map(str::to_string).or_else(|| string(a, "name")))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // `icon` is either a path or a map of size -> path, take the largest
+    let icon_path = match json.get("icon") {
+        Some(Value::String(path)) => Some(path.clone()),
+        Some(Value::Object(sizes)) => sizes
+            .iter()
+            .max_by_key(|(size, _)| size.parse::<u32>().unwrap_or_default())
+            .and_then(|(_, path)| path.as_str().map(str::to_string)),
+        _ => None,
+    };
+
+    let mut dependencies = Vec::new();
+    let sections = [
+        ("depends", DependencyKind::Required),
+        ("recommends", DependencyKind::Optional),
+        ("breaks", DependencyKind::Incompatible),
+        ("conflicts", DependencyKind::Conflicting),
+    ];
+    for (key, kind) in sections {
+        if let Some(deps) = json.get(key).and_then(Value::as_object) {
+            for (id, versions) in deps {
+                dependencies.push(ModDependency {
+                    id: id.clone(),
+                    versions: match versions {
+                        Value::String(v) => Some(v.clone()),
+                        Value::Array(v) => Some(
+                            v.iter().filter_map(Value::as_str).collect::<Vec<_>>().join(" || "),
+                        ),
+                        _ => None,
+                    },
+                    kind,
+                });
+            }
+        }
+    }
+
+    ParsedMetadata {
+        id: string(json, "id"),
+        name: string(json, "name"),
+        version: string(json, "version"),
+        authors,
+        description: string(json, "description"),
+        icon_path,
+        provides: string_list(json.get("provides")),
+        dependencies,
+        other_mods: Vec::new(),
+        nested_jars: json
+            .get("jars")
+            .and_then(Value::as_array)
+            .map(|jars| jars.iter().filter_map(|j| string(j, "file")).collect())
+            .unwrap_or_default(),
+    }
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|v| {
+            v.iter()
+                .filter_map(|item| item.as_str().map(str::to_string).or_else(|| string(item, "id")))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_quilt(json: &Value) -> ParsedMetadata {
+    let loader = json.get("quilt_loader").cloned().unwrap_or_default();
+    let metadata = loader.get("metadata").cloned().unwrap_or_default();
+
+    let authors = metadata
+        .get("contributors")
+        .and_then(Value::as_object)
+        .map(|c| c.keys().cloned().collect())
+        .unwrap_or_default();
+
+    let icon_path = match metadata.get("icon") {
+        Some(Value::String(path)) => Some(path.clone()),
+        Some(Value::Object(sizes)) => sizes
+            .iter()
+            .max_by_key(|(size, _)| size.parse::<u32>().unwrap_or_default())
+            .and_then(|(_, path)| path.as_str().map(str::to_string)),
+        _ => None,
+    };
+
+    let mut dependencies = Vec::new();
+    for (key, kind) in [("depends", DependencyKind::Required), ("breaks", DependencyKind::Incompatible)] {
+        let Some(deps) = loader.get(key).and_then(Value::as_array) else {
+            continue;
+        };
+        dependencies.extend(deps.iter().filter_map(|dep| match dep {
+            Value::String(id) => Some(ModDependency {
+                id: id.clone(),
+                versions: None,
+                kind,
+            }),
+            Value::Object(_) => Some(ModDependency {
+                id: string(dep, "id")?,
+                versions: match dep.get("versions") {
+                    Some(Value::String(v)) => Some(v.clone()),
+                    Some(Value::Array(v)) => Some(
+                        v.iter().filter_map(Value::as_str).collect::<Vec<_>>().join(" || "),
+                    ),
+                    _ => None,
+                },
+                kind: match dep.get("optional").and_then(Value::as_bool) {
+                    Some(true) if kind == DependencyKind::Required => DependencyKind::Optional,
+                    _ => kind,
+                },
+            }),
+            _ => None,
+        }));
+    }
+
+    ParsedMetadata {
+        id: string(&loader, "id"),
+        name: string(&metadata, "name"),
+        version: string(&loader, "version"),
+        authors,
+        description: string(&metadata, "description"),
+        icon_path,
+        provides: string_list(loader.get("provides")),
+        dependencies,
+        other_mods: Vec::new(),
+        nested_jars: string_list(loader.get("jars")),
+    }
+}
+
+fn parse_mods_toml(content: &str) -> Result<ParsedMetadata> {
+    let toml: toml::Value = toml::from_str(content)?;
+    let str_of = |value: &toml::Value, key: &str| value.get(key).and_then(|v| v.as_str()).map(str::to_string);
+
+    // A jar can declare several mods, the first one is the main one
+    let entries = toml.get("mods").and_then(|m| m.as_array()).cloned().unwrap_or_default();
+    let Some(main) = entries.first() else {
+        return Ok(ParsedMetadata::default());
+    };
+    let ids: Vec<String> = entries.iter().filter_map(|m| str_of(m, "modId")).collect();
+
+    let provides: Vec<String> = entries
+        .iter()
+        .filter_map(|m| m.get("provides")?.as_array())
+        .flatten()
+        .filter_map(|p| p.as_str().map(str::to_string))
+        .collect();
+
+    // Dependencies of every mod in the jar, except on each other
+    let mut dependencies: Vec<ModDependency> = Vec::new();
+    let declared = ids.iter().filter_map(|id| toml.get("dependencies")?.get(id)?.as_array());
+    for dep in declared.flatten() {
+        // NeoForge uses `type`, Forge the older `mandatory` flag
+        let kind = match dep.get("type").and_then(|t| t.as_str()).map(str::to_lowercase) {
+            Some(kind) if kind == "required" => DependencyKind::Required,
+            Some(kind) if kind == "incompatible" => DependencyKind::Incompatible,
+            Some(kind) if kind == "discouraged" => DependencyKind::Conflicting,
+            Some(_) => DependencyKind::Optional,
+            None => match dep.get("mandatory").and_then(|m| m.as_bool()) {
+                Some(false) => DependencyKind::Optional,
+                _ => DependencyKind::Required,
+            },
+        };
+        let Some(id) = str_of(dep, "modId") else {
+            continue;
+        };
+        if ids.contains(&id) || provides.contains(&id) {
+            continue;
+        }
+        let dependency = ModDependency {
+            id,
+            versions: str_of(dep, "versionRange"),
+            kind,
+        };
+        if !dependencies.contains(&dependency) {
+            dependencies.push(dependency);
+        }
+    }
+
+    Ok(ParsedMetadata {
+        id: str_of(main, "modId"),
+        name: str_of(main, "displayName"),
+        version: str_of(main, "version"),
+        authors: str_of(main, "authors")
+            .map(|a| a.split(',').map(|a| a.trim().to_string()).collect())
+            .unwrap_or_default(),
+        description: str_of(main, "description").map(|d| d.trim().to_string()),
+        icon_path: str_of(main, "logoFile").or_else(|| str_of(&toml, "logoFile")),
+        provides,
+        dependencies,
+        other_mods: entries
+            .iter()
+            .skip(1)
+            .filter_map(|m| {
+                Some(BundledMod {
+                    id: str_of(m, "modId")?,
+                    version: str_of(m, "version"),
+                })
+            })
+            .collect(),
+        nested_jars: Vec::new(),
+    })
+}
+
+fn parse_mcmod_info(json: &Value) -> ParsedMetadata {
+    // Either a bare list or `{ "modListVersion": 2, "modList": [...] }`
+    let list = json.as_array().or_else(|| json.get("modList").and_then(Value::as_array));
+    let Some(main) = list.and_then(|l| l.first()) else {
+        return ParsedMetadata::default();
+    };
+
+    let list_of = |key: &str| -> Vec<String> {
+        main.get(key)
+            .and_then(Value::as_array)
+            .map(|v| v.iter().filter_map(Value::as_str).map(str::to_string).collect())
+            .unwrap_or_default()
+    };
+
+    ParsedMetadata {
+        id: string(main, "modid"),
+        name: string(main, "name"),
+        version: string(main, "version"),
+        authors: list_of("authorList"),
+        description: string(main, "description"),
+        icon_path: string(main, "logoFile").filter(|l| !l.is_empty()),
+        provides: Vec::new(),
+        dependencies: list_of("requiredMods")
+            .into_iter()
+            .chain(list_of("dependencies"))
+            .map(|id| ModDependency {
+                id,
+                versions: None,
+                kind: DependencyKind::Required,
+            })
+            .collect(),
+        other_mods: Vec::new(),
+        nested_jars: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fabric_metadata_with_comments_and_raw_newlines_parses() {
+        let content = "{\n  // the mod id\n  \"id\": \"sodium\", /* block */\n  \"description\": \"Fast\n\tand \\\"good\\\" // not a comment\",\n  \"version\": \"0.6.0\"\n}";
+        let parsed = parse_fabric(&parse_json(content).unwrap());
+        assert_eq!(parsed.id.as_deref(), Some("sodium"));
+        assert_eq!(parsed.version.as_deref(), Some("0.6.0"));
+        assert_eq!(parsed.description.as_deref(), Some("Fast\n\tand \"good\" // not a comment"));
+    }
+
+    #[test]
+    fn every_mod_of_a_mods_toml_is_read() {
+        let content = r#"
+            modLoader = "javafml"
+            loaderVersion = "[4,)"
+
+            [[mods]]
+            modId = "create"
+            version = "6.0.0"
+            displayName = "Create"
+
+            [[mods]]
+            modId = "flywheel"
+            version = "1.0.0"
+            provides = ["ponder"]
+
+            [[dependencies.create]]
+            modId = "flywheel"
+            type = "required"
+
+            [[dependencies.create]]
+            modId = "neoforge"
+            type = "required"
+            versionRange = "[21.1,)"
+
+            [[dependencies.flywheel]]
+            modId = "neoforge"
+            type = "required"
+            versionRange = "[21.1,)"
+
+            [[dependencies.flywheel]]
+            modId = "sodium"
+            type = "incompatible"
+        "#;
+        let parsed = parse_mods_toml(content).unwrap();
+
+        assert_eq!(parsed.id.as_deref(), Some("create"));
+        assert_eq!(
+            parsed.other_mods,
+            [BundledMod {
+                id: "flywheel".to_string(),
+                version: Some("1.0.0".to_string()),
+            }]
+        );
+        assert_eq!(parsed.provides, ["ponder"]);
+        let dependencies: Vec<(&str, DependencyKind)> =
+            parsed.dependencies.iter().map(|d| (d.id.as_str(), d.kind)).collect();
+        assert_eq!(
+            dependencies,
+            [("neoforge", DependencyKind::Required), ("sodium", DependencyKind::Incompatible)]
+        );
+    }
+
+    #[test]
+    fn fabric_conflicts_only_warn() {
+        let content = r#"{"id": "a", "breaks": {"b": "*"}, "conflicts": {"c": ">=1.0"}}"#;
+        let parsed = parse_fabric(&parse_json(content).unwrap());
+        let dependencies: Vec<(&str, DependencyKind)> =
+            parsed.dependencies.iter().map(|d| (d.id.as_str(), d.kind)).collect();
+        assert_eq!(dependencies, [("b", DependencyKind::Incompatible), ("c", DependencyKind::Conflicting)]);
+    }
+
+    #[test]
+    fn broken_json_is_still_an_error() {
+        assert!(parse_json("{ \"id\": ").is_err());
+    }
+}
@@ -0,0 +1,190 @@
+pub mod analysis;
+pub mod metadata;
+pub mod types;
+pub mod updates;
+pub mod versions;
+
+use crate::scripts::instances::Instance;
+use anyhow::Result;
+use base64::Engine;
+use std::path::{Path, PathBuf};
+pub use analysis::{ModIssue, Severity, analyze_mods};
+pub use types::{BundledMod, DependencyKind, MetadataFormat, ModDependency, ModInfo};
+pub use updates::{ModUpdate, Rollback, apply_update, check_updates, load_rollbacks, rollback};
+
+pub const DISABLED_SUFFIX: &str = ".disabled";
+
+/// Dependency ids the game or loader itself provides, not worth listing as mods
+pub const PLATFORM_IDS: &[&str] = &[
+    "minecraft",
+    "java",
+    "fabricloader",
+    "quilt_loader",
+    "neoforge",
+    "forge",
+];
+
+pub fn mods_dir(instance: &Instance) -> PathBuf {
+    instance.game_dir().join("mods")
+}
+
+/// Every jar in the instance's `mods/` folder, disabled ones included
+pub async fn list_mods(instance: &Instance) -> Result<Vec<ModInfo>> {
+    let dir = mods_dir(instance);
+    tokio::task::spawn_blocking(move || {
+        let mut mods = Vec::new();
+        if !dir.exists() {
+            return Ok(mods);
+        }
+
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let enabled = file_name.ends_with(".jar");
+            if !path.is_file() || !(enabled || file_name.ends_with(&format!(".jar{}", DISABLED_SUFFIX))) {
+                continue;
+            }
+
+            match read_mod(&path) {
+                Ok(info) => mods.push(info),
+                Err(e) => {
+                    log::warn!("Failed to read {}: {}", file_name, e);
+                    mods.push(unreadable_mod(&path, e.to_string()));
+                }
+            }
+        }
+
+        mods.sort_by_key(|m| m.name.to_lowercase());
+        Ok(mods)
+    })
+    .await?
+}
+
+pub fn read_mod(path: &Path) -> Result<ModInfo> {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let enabled = !file_name.ends_with(DISABLED_SUFFIX);
+
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(path)?)?;
+    let (format, parsed) = match metadata::read_metadata(&mut archive)? {
+        Some((format, parsed)) => (Some(format), parsed),
+        None => (None, Default::default()),
+    };
+
+    let mut bundled = parsed.other_mods;
+    read_bundled(&mut archive, &parsed.nested_jars, 0, &mut bundled);
+
+    let icon = match &parsed.icon_path {
+        Some(icon_path) => metadata::read_entry_bytes(&mut archive, icon_path)?.map(|bytes| {
+            format!(
+                "data:image/png;base64,{}",
+                base64::engine::general_purpose::STANDARD.encode(bytes)
+            )
+        }),
+        None => None,
+    };
+
+    Ok(ModInfo {
+        path: path.to_path_buf(),
+        name: parsed
+            .name
+            .clone()
+            .or_else(|| parsed.id.clone())
+            .unwrap_or_else(|| file_name.trim_end_matches(DISABLED_SUFFIX).to_string()),
+        file_name,
+        enabled,
+        format,
+        id: parsed.id,
+        version: parsed.version,
+        authors: parsed.authors,
+        description: parsed.description,
+        icon,
+        provides: parsed.provides,
+        dependencies: parsed.dependencies,
+        bundled,
+        metadata_error: None,
+    })
+}
+
+/// Entry for a jar whose metadata can't be read, so it still shows up and can be toggled or deleted
+fn unreadable_mod(path: &Path, error: String) -> ModInfo {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    ModInfo {
+        path: path.to_path_buf(),
+        name: file_name.trim_end_matches(DISABLED_SUFFIX).to_string(),
+        enabled: !file_name.ends_with(DISABLED_SUFFIX),
+        file_name,
+        format: None,
+        id: None,
+        version: None,
+        authors: Vec::new(),
+        description: None,
+        icon: None,
+        provides: Vec::new(),
+        dependencies: Vec::new(),
+        bundled: Vec::new(),
+        metadata_error: Some(error),
+    }
+}
+
+/// Nested jars can nest jars themselves, but not deeper than this
+const MAX_NESTING: usize = 3;
+
+fn read_bundled<R: std::io::Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    paths: &[String],
+    depth: usize,
+    bundled: &mut Vec<BundledMod>,
+) {
+    if depth >= MAX_NESTING {
+        return;
+    }
+    for path in paths {
+        let nested = metadata::read_entry_bytes(archive, path)
+            .ok()
+            .flatten()
+            .and_then(|bytes| zip::ZipArchive::new(std::io::Cursor::new(bytes)).ok());
+        let Some(mut nested) = nested else {
+            log::debug!("Can't read nested jar {}", path);
+            continue;
+        };
+        let Ok(Some((_, parsed))) = metadata::read_metadata(&mut nested) else {
+            continue;
+        };
+
+        if let Some(id) = &parsed.id {
+            bundled.push(BundledMod {
+                id: id.clone(),
+                version: parsed.version.clone(),
+            });
+        }
+        bundled.extend(parsed.provides.iter().map(|id| BundledMod {
+            id: id.clone(),
+            version: parsed.version.clone(),
+        }));
+        bundled.extend(parsed.other_mods);
+        read_bundled(&mut nested, &parsed.nested_jars, depth + 1, bundled);
+    }
+}
+
+/// Enable or disable a mod by renaming it to/from `<name>.jar.disabled`
+pub async fn set_mod_enabled(info: &ModInfo, enabled: bool) -> Result<PathBuf> {
+    if info.enabled == enabled {
+        return Ok(info.path.clone());
+    }
+
+    let new_name = if enabled {
+        info.file_name.trim_end_matches(DISABLED_SUFFIX).to_string()
+    } else {
+        format!("{}{}", info.file_name, DISABLED_SUFFIX)
+    };
+    let new_path = info.path.with_file_name(new_name);
+    tokio::fs::rename(&info.path, &new_path).await?;
+    log::info!("{} {}", if enabled { "Enabled" } else { "Disabled" }, info.name);
+    Ok(new_path)
+}
+
+pub async fn delete_mod(info: &ModInfo) -> Result<()> {
+    tokio::fs::remove_file(&info.path).await?;
+    log::info!("Deleted mod {}", info.file_name);
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use std::path::PathBuf;
+
+/// Which metadata file the mod info was read from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MetadataFormat {
+    Fabric,
+    Quilt,
+    NeoForge,
+    Forge,
+    /// Legacy Forge `mcmod.info`
+    McModInfo,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DependencyKind {
+    Required,
+    Optional,
+    /// `breaks` or `incompatible`: must not be installed (in that version range)
+    Incompatible,
+    /// Fabric `conflicts` or NeoForge `discouraged`: the loader only warns about it
+    Conflicting,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModDependency {
+    pub id: String,
+    /// Version requirement as written by the mod, e.g. `>=0.15` or `[21.1,)`
+    pub versions: Option<String>,
+    pub kind: DependencyKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModInfo {
+    pub path: PathBuf,
+    pub file_name: String,
+    pub enabled: bool,
+    pub format: Option<MetadataFormat>,
+    pub id: Option<String>,
+    pub name: String,
+    pub version: Option<String>,
+    pub authors: Vec<String>,
+    pub description: Option<String>,
+    /// PNG icon as a data URL
+    pub icon: Option<String>,
+    /// Other ids this mod can stand in for (Fabric/Quilt `provides`)
+    pub provides: Vec<String>,
+    pub dependencies: Vec<ModDependency>,
+    /// Mods shipped inside the jar (Fabric/Quilt `jars`, NeoForge Jar-in-Jar)
+    pub bundled: Vec<BundledMod>,
+    /// Why the jar's metadata couldn't be read, the rest is guessed from the file name
+    pub metadata_error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BundledMod {
+    pub id: String,
+    pub version: Option<String>,
+}
+
+/// Fields parsed from a metadata file, before the jar-level info is added
+#[derive(Debug, Default)]
+pub(crate) struct ParsedMetadata {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub authors: Vec<String>,
+    pub description: Option<String>,
+    pub icon_path: Option<String>,
+    pub provides: Vec<String>,
+    pub dependencies: Vec<ModDependency>,
+    /// The other mods of a multi-mod `mods.toml`
+    pub other_mods: Vec<BundledMod>,
+    /// Paths of nested jars inside the mod jar
+    pub nested_jars: Vec<String>,
+}
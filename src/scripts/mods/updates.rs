@@ -0,0 +1,179 @@
+use super::mods_dir;
+use crate::scripts::game::utils::{FileHash, download_file_verified, hex};
+use crate::scripts::instances::Instance;
+use crate::scripts::modrinth::{ModrinthClient, Version, loader_names};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use std::path::PathBuf;
+
+/// Previous jars of updated mods live here so an update can be undone
+const BACKUP_DIR: &str = "mod_backups";
+const ROLLBACK_FILE: &str = "rollback.json";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModUpdate {
+    pub path: PathBuf,
+    pub file_name: String,
+    /// Installed version, if Modrinth still knows it
+    pub current: Option<Version>,
+    pub latest: Version,
+}
+
+/// An update that can be rolled back
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rollback {
+    /// Jar written by the update
+    pub current_file: String,
+    /// Jar it replaced, kept in the backup folder
+    pub previous_file: String,
+}
+
+fn backup_dir(instance: &Instance) -> PathBuf {
+    instance.dir().join(BACKUP_DIR)
+}
+
+/// Check every enabled jar in `mods/` for a newer Modrinth version
+/// compatible with the instance's loader and game version
+pub async fn check_updates(client: &ModrinthClient, instance: &Instance) -> Result<Vec<ModUpdate>> {
+    // Step 1: Hash the jars
+    let dir = mods_dir(instance);
+    let jars = tokio::task::spawn_blocking(move || -> Result<Vec<(PathBuf, String)>> {
+        let mut jars = Vec::new();
+        if !dir.exists() {
+            return Ok(jars);
+        }
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|e| e == "jar") {
+                let hash = hex(&Sha512::digest(std::fs::read(&path)?));
+                jars.push((path, hash));
+            }
+        }
+        Ok(jars)
+    })
+    .await??;
+    if jars.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Step 2: Ask Modrinth for the current and the newest versions
+    let hashes: Vec<String> = jars.iter().map(|(_, hash)| hash.clone()).collect();
+    let mut current = client.get_versions_by_hashes(&hashes, "sha512").await?;
+    let mut latest = client
+        .get_latest_versions_by_hashes(
+            &hashes,
+            "sha512",
+            loader_names(instance.loader),
+            &[&instance.game_version],
+        )
+        .await?;
+
+    // Step 3: Keep the ones where the newest version is a different one
+    let mut updates = Vec::new();
+    for (path, hash) in jars {
+        let Some(latest) = latest.remove(&hash) else {
+            continue;
+        };
+        let current = current.remove(&hash);
+        if current.as_ref().is_some_and(|c| c.id == latest.id) {
+            continue;
+        }
+        // The update endpoint can answer with the very file we already have
+        if latest.files.iter().any(|f| f.hashes.sha512 == hash) {
+            continue;
+        }
+
+        updates.push(ModUpdate {
+            file_name: path.file_name().unwrap_or_default().to_string_lossy().to_string(),
+            path,
+            current,
+            latest,
+        });
+    }
+
+    updates.sort_by_key(|u| u.file_name.to_lowercase());
+    Ok(updates)
+}
+
+/// Download the new jar and move the old one into the backup folder
+pub async fn apply_update(client: &ModrinthClient, instance: &Instance, update: &ModUpdate) -> Result<()> {
+    let file = update
+        .latest
+        .primary_file()
+        .ok_or_else(|| anyhow::anyhow!("{} has no files", update.latest.name))?;
+
+    // Step 1: Download next to the old jar under a temporary name
+    let new_path = mods_dir(instance).join(&file.filename);
+    let tmp_path = mods_dir(instance).join(format!("{}.download", file.filename));
+    download_file_verified(
+        client.http(),
+        &file.url,
+        &tmp_path,
+        FileHash::Sha512(&file.hashes.sha512),
+        Some(file.size),
+    )
+    .await?;
+
+    // Step 2: Back up the old jar, then move the new one in place
+    let backups = backup_dir(instance);
+    tokio::fs::create_dir_all(&backups).await?;
+    tokio::fs::rename(&update.path, backups.join(&update.file_name)).await?;
+    tokio::fs::rename(&tmp_path, &new_path).await?;
+
+    // Step 3: Remember how to undo it
+    let mut rollbacks = load_rollbacks(instance).await?;
+    // An older backup of the same file is replaced by this one
+    for stale in rollbacks.iter().filter(|r| r.current_file == update.file_name) {
+        let _ = tokio::fs::remove_file(backups.join(&stale.previous_file)).await;
+    }
+    rollbacks.retain(|r| r.current_file != update.file_name && r.current_file != file.filename);
+    rollbacks.push(Rollback {
+        current_file: file.filename.clone(),
+        previous_file: update.file_name.clone(),
+    });
+    save_rollbacks(instance, &rollbacks).await?;
+
+    log::info!("Updated {} to {}", update.file_name, file.filename);
+    Ok(())
+}
+
+pub async fn load_rollbacks(instance: &Instance) -> Result<Vec<Rollback>> {
+    let path = backup_dir(instance).join(ROLLBACK_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(serde_json::from_str(&tokio::fs::read_to_string(path).await?)?)
+}
+
+async fn save_rollbacks(instance: &Instance, rollbacks: &[Rollback]) -> Result<()> {
+    tokio::fs::create_dir_all(backup_dir(instance)).await?;
+    tokio::fs::write(
+        backup_dir(instance).join(ROLLBACK_FILE),
+        serde_json::to_string_pretty(rollbacks)?,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Put the previous jar back and remove the updated one
+pub async fn rollback(instance: &Instance, rollback: &Rollback) -> Result<()> {
+    let mods = mods_dir(instance);
+    let backup = backup_dir(instance).join(&rollback.previous_file);
+    if !backup.exists() {
+        return Err(anyhow::anyhow!("Backup of {} is missing", rollback.previous_file));
+    }
+
+    let current = mods.join(&rollback.current_file);
+    if current.exists() {
+        tokio::fs::remove_file(&current).await?;
+    }
+    tokio::fs::rename(&backup, mods.join(&rollback.previous_file)).await?;
+
+    let mut rollbacks = load_rollbacks(instance).await?;
+    rollbacks.retain(|r| r != rollback);
+    save_rollbacks(instance, &rollbacks).await?;
+
+    log::info!("Rolled back {} to {}", rollback.current_file, rollback.previous_file);
+    Ok(())
+}
@@ -0,0 +1,282 @@
+use std::cmp::Ordering;
+
+/// Whether `version` satisfies `range`, `None` when either can't be understood.
+/// `maven` selects Forge/NeoForge range syntax (`[1.0,2.0)`), otherwise the
+/// Fabric/Quilt style predicates are used (`>=1.0 <2.0 || 3.x`).
+pub fn matches(range: &str, version: &str, maven: bool) -> Option<bool> {
+    let range = range.trim();
+    if range.contains("${") || parse(version).is_none() {
+        return None;
+    }
+    if maven {
+        matches_maven(range, version)
+    } else {
+        matches_predicates(range, version)
+    }
+}
+
+/// A version split into its numeric-ish core and an optional pre-release tag.
+/// Build metadata after `+` is ignored.
+#[derive(Debug)]
+struct Parsed<'a> {
+    core: Vec<&'a str>,
+    pre: Option<&'a str>,
+}
+
+fn parse(version: &str) -> Option<Parsed<'_>> {
+    let version = version.trim().trim_start_matches('v');
+    let version = version.split('+').next().unwrap_or_default();
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+    // Versions like `mc1.21-2.0` have no meaningful order
+    if !core.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(Parsed {
+        core: core.split('.').collect(),
+        pre,
+    })
+}
+
+fn compare(a: &str, b: &str) -> Option<Ordering> {
+    let (a, b) = (parse(a)?, parse(b)?);
+    for i in 0..a.core.len().max(b.core.len()) {
+        let x = a.core.get(i).copied().unwrap_or("0");
+        let y = b.core.get(i).copied().unwrap_or("0");
+        let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            _ => x.cmp(y),
+        };
+        if ordering != Ordering::Equal {
+            return Some(ordering);
+        }
+    }
+    // A pre-release comes before the release itself
+    Some(match (a.pre, b.pre) {
+        (None, None) => Ordering::Equal,
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(x), Some(y)) => compare_pre(x, y),
+    })
+}
+
+/// Semver precedence of pre-release tags: identifier by identifier, numeric ones numerically
+/// and before alphanumeric ones, fewer identifiers first. An empty tag comes before any other.
+fn compare_pre(a: &str, b: &str) -> Ordering {
+    let mut a = a.split('.').filter(|id| !id.is_empty());
+    let mut b = b.split('.').filter(|id| !id.is_empty());
+    loop {
+        let ordering = match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => match (x.parse::<u64>(), y.parse::<u64>()) {
+                (Ok(x), Ok(y)) => x.cmp(&y),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => x.cmp(y),
+            },
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+fn matches_predicates(range: &str, version: &str) -> Option<bool> {
+    // Alternatives are OR-ed, the predicates within one are AND-ed
+    let results: Vec<Option<bool>> = range
+        .split("||")
+        .map(|alternative| {
+            let predicates: Vec<Option<bool>> = alternative
+                .split_whitespace()
+                .map(|predicate| matches_predicate(predicate, version))
+                .collect();
+            if predicates.contains(&Some(false)) {
+                Some(false)
+            } else if predicates.contains(&None) {
+                None
+            } else {
+                Some(true)
+            }
+        })
+        .collect();
+
+    if results.contains(&Some(true)) {
+        Some(true)
+    } else if results.contains(&None) {
+        None
+    } else {
+        Some(false)
+    }
+}
+
+fn matches_predicate(predicate: &str, version: &str) -> Option<bool> {
+    if predicate == "*" || predicate.is_empty() {
+        return Some(true);
+    }
+
+    for (op, check) in [
+        (">=", Ordering::is_ge as fn(Ordering) -> bool),
+        ("<=", Ordering::is_le),
+        (">", Ordering::is_gt),
+        ("<", Ordering::is_lt),
+    ] {
+        if let Some(target) = predicate.strip_prefix(op) {
+            return compare(version, target).map(check);
+        }
+    }
+
+    if let Some(target) = predicate.strip_prefix('~') {
+        // Same major and minor, at least `target`
+        let parsed = parse(target)?;
+        let minor = parsed.core.get(1).unwrap_or(&"0").parse::<u64>().ok()?;
+        // The trailing `-` keeps pre-releases of the next version out, like Fabric does
+        let upper = format!("{}.{}-", parsed.core.first()?, minor + 1);
+        return Some(compare(version, target)?.is_ge() && compare(version, &upper)?.is_lt());
+    }
+    if let Some(target) = predicate.strip_prefix('^') {
+        // Same major, at least `target`
+        let parsed = parse(target)?;
+        let upper = format!("{}-", parsed.core.first()?.parse::<u64>().ok()? + 1);
+        return Some(compare(version, target)?.is_ge() && compare(version, &upper)?.is_lt());
+    }
+
+    let target = predicate.strip_prefix('=').unwrap_or(predicate);
+    if target.ends_with(".x") || target.ends_with(".*") || target.ends_with(".X") {
+        // Wildcard: every listed component has to match
+        let prefix = &target[..target.len() - 2];
+        let version = parse(version)?;
+        return Some(
+            prefix
+                .split('.')
+                .enumerate()
+                .all(|(i, part)| version.core.get(i).is_some_and(|v| v == &part)),
+        );
+    }
+    compare(version, target).map(Ordering::is_eq)
+}
+
+fn matches_maven(range: &str, version: &str) -> Option<bool> {
+    // A bare version is only a recommendation in Maven
+    if !range.starts_with(['[', '(']) {
+        return Some(true);
+    }
+
+    let mut rest = range;
+    let mut known = true;
+    while !rest.is_empty() {
+        let open = rest.chars().next()?;
+        let close_at = rest.find([']', ')'])?;
+        let close = rest[close_at..].chars().next()?;
+        let inner = &rest[1..close_at];
+
+        let result = match inner.split_once(',') {
+            // `[1.0]` is an exact match
+            None => compare(version, inner.trim()).map(Ordering::is_eq),
+            Some((low, high)) => {
+                let low_ok = match low.trim() {
+                    "" => Some(true),
+                    low if open == '[' => compare(version, low).map(Ordering::is_ge),
+                    low => compare(version, low).map(Ordering::is_gt),
+                };
+                let high_ok = match high.trim() {
+                    "" => Some(true),
+                    high if close == ']' => compare(version, high).map(Ordering::is_le),
+                    high => compare(version, high).map(Ordering::is_lt),
+                };
+                match (low_ok, high_ok) {
+                    (Some(low), Some(high)) => Some(low && high),
+                    _ => None,
+                }
+            }
+        };
+        match result {
+            Some(true) => return Some(true),
+            Some(false) => {}
+            None => known = false,
+        }
+
+        rest = rest[close_at + 1..].trim_start_matches([',', ' ']);
+    }
+
+    if known { Some(false) } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fabric_ranges() {
+        let cases = [
+            ("*", "1.0.0", Some(true)),
+            ("1.2.3", "1.2.3", Some(true)),
+            ("=1.2.3", "1.2.4", Some(false)),
+            ("~1.2.3", "1.2.9", Some(true)),
+            ("~1.2.3", "1.2.2", Some(false)),
+            ("~1.2.3", "1.3.0", Some(false)),
+            ("~1.2.3", "1.3.0-alpha.1", Some(false)),
+            ("^1.2", "1.9.0", Some(true)),
+            ("^1.2", "1.1.9", Some(false)),
+            ("^1.2", "2.0.0", Some(false)),
+            ("^1.2", "2.0.0-beta.1", Some(false)),
+            ("1.21.x", "1.21.4", Some(true)),
+            ("1.21.x", "1.21", Some(true)),
+            ("1.21.x", "1.20.6", Some(false)),
+            ("1.x", "1.21.1", Some(true)),
+            ("1.21.*", "1.22", Some(false)),
+            (">=1.0 <2.0", "1.5", Some(true)),
+            (">=1.0 <2.0", "2.0", Some(false)),
+            (">=1.0 <2.0", "0.9", Some(false)),
+            ("<1.0 || >=2.0", "2.1", Some(true)),
+            ("<1.0 || >=2.0", "1.5", Some(false)),
+            ("1.20.1 || 1.21.x", "1.21.1", Some(true)),
+            (">=0.16.0", "0.16.0-beta.2", Some(false)),
+            (">=0.16.0-beta.1", "0.16.0-beta.2", Some(true)),
+            (">=0.16.0-beta.3", "0.16.0-beta.2", Some(false)),
+            (">=0.16.0-beta.9", "0.16.0-beta.10", Some(true)),
+            ("<0.16.0-beta.10", "0.16.0-beta.9", Some(true)),
+            (">=0.16.0-beta.10", "0.16.0-rc.1", Some(true)),
+            (">=0.16.0-beta", "0.16.0-beta.1", Some(true)),
+            (">=0.16.0-alpha.beta", "0.16.0-alpha.1", Some(false)),
+            ("<=1.0", "1.0+build.5", Some(true)),
+            (">=1.0", "mc1.21-2.0", None),
+            (">=${version}", "1.0", None),
+        ];
+        for (range, version, expected) in cases {
+            assert_eq!(matches(range, version, false), expected, "{} against {}", version, range);
+        }
+    }
+
+    #[test]
+    fn maven_ranges() {
+        let cases = [
+            ("[1.0,2.0)", "1.0", Some(true)),
+            ("[1.0,2.0)", "1.9.9", Some(true)),
+            ("[1.0,2.0)", "2.0", Some(false)),
+            ("(1.0,2.0]", "1.0", Some(false)),
+            ("(1.0,2.0]", "2.0", Some(true)),
+            ("(,1.20.1]", "1.20.1", Some(true)),
+            ("(,1.20.1]", "1.20.2", Some(false)),
+            ("[21.1,)", "21.1.65", Some(true)),
+            ("[21.1,)", "21.0.167", Some(false)),
+            ("[21.1.0,)", "21.1.0-beta", Some(false)),
+            ("[21.1.0-beta.9,)", "21.1.0-beta.10", Some(true)),
+            ("[21.1.0-beta.10,)", "21.1.0-beta.9", Some(false)),
+            ("(,21.1.0-beta.10)", "21.1.0-beta.9", Some(true)),
+            ("[1.20.1]", "1.20.1", Some(true)),
+            ("[1.20.1]", "1.20.2", Some(false)),
+            ("[1.0,1.5),[2.0,)", "2.1", Some(true)),
+            ("[1.0,1.5),[2.0,)", "1.7", Some(false)),
+            // A bare version is only a recommendation
+            ("1.20.1", "1.21", Some(true)),
+            ("[1.0,)", "mc1.21-2.0", None),
+        ];
+        for (range, version, expected) in cases {
+            assert_eq!(matches(range, version, true), expected, "{} against {}", version, range);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::scripts::game::utils::parse_release;
+
+/// `(first game version, resource pack format, data pack format)`, newest first.
+/// A game version uses the formats of the newest entry that isn't newer than it.
+const PACK_FORMATS: &[(&str, u32, u32)] = &[
+    ("1.21.7", 64, 81),
+    ("1.21.6", 63, 80),
+    ("1.21.5", 55, 71),
+    ("1.21.4", 46, 61),
+    ("1.21.2", 42, 57),
+    ("1.21", 34, 48),
+    ("1.20.5", 32, 41),
+    ("1.20.3", 22, 26),
+    ("1.20.2", 18, 18),
+    ("1.20", 15, 15),
+    ("1.19.4", 13, 12),
+    ("1.19.3", 12, 10),
+    ("1.19", 9, 10),
+    ("1.18.2", 8, 9),
+    ("1.18", 8, 8),
+    ("1.17", 7, 7),
+    ("1.16.2", 6, 6),
+];
+
+/// Newest release the table is known to cover, later ones may already use a new format
+const NEWEST_KNOWN: &str = "1.21.8";
+
+/// Resource pack format of a release, `None` for snapshots and versions outside the table
+pub fn resource_pack_format(game_version: &str) -> Option<u32> {
+    lookup(game_version).map(|(resource, _)| resource)
+}
+
+pub fn data_pack_format(game_version: &str) -> Option<u32> {
+    lookup(game_version).map(|(_, data)| data)
+}
+
+fn lookup(game_version: &str) -> Option<(u32, u32)> {
+    let version = parse_release(game_version)?;
+    if version > parse_release(NEWEST_KNOWN)? {
+        return None;
+    }
+    PACK_FORMATS
+        .iter()
+        .find(|(first, _, _)| parse_release(first).is_some_and(|first| first <= version))
+        .map(|(_, resource, data)| (*resource, *data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_only_come_from_the_table() {
+        assert_eq!(resource_pack_format("1.21.1"), Some(34));
+        assert_eq!(resource_pack_format("1.21.8"), Some(64));
+        assert_eq!(data_pack_format("1.20"), Some(15));
+        assert_eq!(resource_pack_format("1.21.9"), None);
+        assert_eq!(resource_pack_format("1.16.1"), None);
+        assert_eq!(resource_pack_format("24w14a"), None);
+    }
+}
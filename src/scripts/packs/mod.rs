@@ -0,0 +1,258 @@
+pub mod formats;
+pub mod options;
+pub mod types;
+
+use crate::scripts::instances::Instance;
+use anyhow::Result;
+use base64::Engine;
+use serde_json::Value;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+pub use types::{PackInfo, PackKind};
+use types::PackMeta;
+
+/// Prefix Minecraft uses for user packs in `options.txt`
+const FILE_PREFIX: &str = "file/";
+
+pub fn packs_dir(instance: &Instance, kind: &PackKind) -> PathBuf {
+    let game_dir = instance.game_dir();
+    match kind {
+        PackKind::ResourcePack => game_dir.join("resourcepacks"),
+        PackKind::ShaderPack => game_dir.join("shaderpacks"),
+        PackKind::DataPack(world) => game_dir.join("saves").join(world).join("datapacks"),
+    }
+}
+
+/// Folder names of the instance's worlds
+pub async fn list_worlds(instance: &Instance) -> Result<Vec<String>> {
+    let saves = instance.game_dir().join("saves");
+    let mut worlds = Vec::new();
+    if !saves.exists() {
+        return Ok(worlds);
+    }
+
+    let mut entries = tokio::fs::read_dir(saves).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.path().join("level.dat").exists() {
+            worlds.push(entry.file_name().to_string_lossy().to_string());
+        }
+    }
+    worlds.sort();
+    Ok(worlds)
+}
+
+pub async fn list_packs(instance: &Instance, kind: &PackKind) -> Result<Vec<PackInfo>> {
+    let enabled = match kind {
+        PackKind::ResourcePack => enabled_resource_packs(instance).await?,
+        _ => Vec::new(),
+    };
+
+    let dir = packs_dir(instance, kind);
+    let kind = kind.clone();
+    let expected_format = match &kind {
+        PackKind::ResourcePack => formats::resource_pack_format(&instance.game_version),
+        PackKind::DataPack(_) => formats::data_pack_format(&instance.game_version),
+        PackKind::ShaderPack => None,
+    };
+
+    tokio::task::spawn_blocking(move || {
+        let mut packs = Vec::new();
+        if !dir.exists() {
+            return Ok(packs);
+        }
+
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            if path.is_file() && path.extension().is_none_or(|e| e != "zip") {
+                continue;
+            }
+
+            let (meta, icon) = match read_pack(&path, &kind) {
+                Ok(read) => read,
+                Err(e) => {
+                    log::warn!("Failed to read pack {}: {}", file_name, e);
+                    (PackMeta::default(), None)
+                }
+            };
+            let compatible = match (meta.supported, meta.pack_format, expected_format) {
+                (Some((min, max)), _, Some(expected)) => Some((min..=max).contains(&expected)),
+                (None, Some(format), Some(expected)) => Some(format == expected),
+                _ => None,
+            };
+
+            packs.push(PackInfo {
+                enabled: enabled.contains(&file_name),
+                path,
+                file_name,
+                kind: kind.clone(),
+                description: meta.description,
+                pack_format: meta.pack_format,
+                compatible,
+                icon,
+            });
+        }
+
+        packs.sort_by_key(|p| p.file_name.to_lowercase());
+        Ok(packs)
+    })
+    .await?
+}
+
+/// Read `pack.mcmeta` and `pack.png` from a zipped or extracted pack
+fn read_pack(path: &Path, kind: &PackKind) -> Result<(PackMeta, Option<String>)> {
+    // Shader packs have neither
+    if *kind == PackKind::ShaderPack {
+        return Ok((PackMeta::default(), None));
+    }
+
+    let (mcmeta, png) = if path.is_dir() {
+        (
+            std::fs::read(path.join("pack.mcmeta")).ok(),
+            std::fs::read(path.join("pack.png")).ok(),
+        )
+    } else {
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(path)?)?;
+        let mut read = |name: &str| -> Option<Vec<u8>> {
+            let mut entry = archive.by_name(name).ok()?;
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes).ok()?;
+            Some(bytes)
+        };
+        (read("pack.mcmeta"), read("pack.png"))
+    };
+
+    let meta = match mcmeta {
+        Some(bytes) => parse_mcmeta(&serde_json::from_slice(&bytes)?),
+        None => PackMeta::default(),
+    };
+    let icon = png.map(|bytes| {
+        format!(
+            "data:image/png;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(bytes)
+        )
+    });
+    Ok((meta, icon))
+}
+
+fn parse_mcmeta(json: &Value) -> PackMeta {
+    let Some(pack) = json.get("pack") else {
+        return PackMeta::default();
+    };
+
+    // `supported_formats` is a number, `[min, max]` or `{ "min_inclusive", "max_inclusive" }`
+    let supported = match pack.get("supported_formats") {
+        Some(Value::Number(n)) => n.as_u64().map(|n| (n as u32, n as u32)),
+        Some(Value::Array(range)) => match (range.first(), range.get(1)) {
+            (Some(min), Some(max)) => min.as_u64().zip(max.as_u64()).map(|(min, max)| (min as u32, max as u32)),
+            _ => None,
+        },
+        Some(range @ Value::Object(_)) => {
+            let min = range.get("min_inclusive").and_then(Value::as_u64);
+            let max = range.get("max_inclusive").and_then(Value::as_u64);
+            min.zip(max).map(|(min, max)| (min as u32, max as u32))
+        }
+        _ => None,
+    };
+
+    PackMeta {
+        description: pack.get("description").map(component_text).filter(|d| !d.is_empty()),
+        pack_format: pack.get("pack_format").and_then(Value::as_u64).map(|f| f as u32),
+        supported,
+    }
+}
+
+/// Plain text of a chat component (string, `{ "text", "extra" }` or a list of those)
+fn component_text(component: &Value) -> String {
+    match component {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts.iter().map(component_text).collect(),
+        Value::Object(_) => {
+            let mut text = component
+                .get("text")
+                .or_else(|| component.get("translate"))
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            if let Some(extra) = component.get("extra") {
+                text.push_str(&component_text(extra));
+            }
+            text
+        }
+        _ => String::new(),
+    }
+}
+
+/// File names of the enabled resource packs, highest priority first
+pub async fn enabled_resource_packs(instance: &Instance) -> Result<Vec<String>> {
+    let packs = options::read_resource_packs(&instance.game_dir()).await?;
+    Ok(packs
+        .iter()
+        .rev()
+        .filter_map(|p| p.strip_prefix(FILE_PREFIX))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Enabled packs go on top of the stack, like in the in-game menu
+pub async fn set_resource_pack_enabled(instance: &Instance, file_name: &str, enabled: bool) -> Result<()> {
+    let game_dir = instance.game_dir();
+    let entry = format!("{}{}", FILE_PREFIX, file_name);
+    let mut packs = options::read_resource_packs(&game_dir).await?;
+    packs.retain(|p| *p != entry);
+    if enabled {
+        if packs.is_empty() {
+            packs.push("vanilla".to_string());
+        }
+        packs.push(entry);
+    }
+    options::write_resource_packs(&game_dir, &packs).await
+}
+
+/// Move an enabled pack one step up (higher priority) or down
+pub async fn move_resource_pack(instance: &Instance, file_name: &str, up: bool) -> Result<()> {
+    let game_dir = instance.game_dir();
+    let entry = format!("{}{}", FILE_PREFIX, file_name);
+    let mut packs = options::read_resource_packs(&game_dir).await?;
+    let Some(index) = packs.iter().position(|p| *p == entry) else {
+        return Ok(());
+    };
+
+    // `options.txt` lists the lowest priority first, and the built-in packs stay at the bottom
+    let target = if up { index + 1 } else { index.wrapping_sub(1) };
+    if target >= packs.len() || !packs[target].starts_with(FILE_PREFIX) {
+        return Ok(());
+    }
+    packs.swap(index, target);
+    options::write_resource_packs(&game_dir, &packs).await
+}
+
+/// Copy a pack file (e.g. picked in the UI) into the instance
+pub async fn install_pack_file(
+    instance: &Instance,
+    kind: &PackKind,
+    file_name: &str,
+    bytes: &[u8],
+) -> Result<()> {
+    let file_name = Path::new(file_name)
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid file name: {}", file_name))?;
+    let dir = packs_dir(instance, kind);
+    tokio::fs::create_dir_all(&dir).await?;
+    tokio::fs::write(dir.join(file_name), bytes).await?;
+    log::info!("Installed {:?} {}", kind, file_name.to_string_lossy());
+    Ok(())
+}
+
+pub async fn delete_pack(instance: &Instance, pack: &PackInfo) -> Result<()> {
+    if pack.path.is_dir() {
+        tokio::fs::remove_dir_all(&pack.path).await?;
+    } else {
+        tokio::fs::remove_file(&pack.path).await?;
+    }
+    if pack.enabled {
+        set_resource_pack_enabled(instance, &pack.file_name, false).await?;
+    }
+    log::info!("Deleted {}", pack.file_name);
+    Ok(())
+}
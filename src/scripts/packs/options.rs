@@ -0,0 +1,52 @@
+use anyhow::Result;
+use std::path::Path;
+
+const RESOURCE_PACKS_KEY: &str = "resourcePacks";
+
+/// Entries of `resourcePacks` in `options.txt`, lowest priority first (e.g. `vanilla`, `file/Faithful.zip`)
+pub async fn read_resource_packs(game_dir: &Path) -> Result<Vec<String>> {
+    let path = game_dir.join("options.txt");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = tokio::fs::read_to_string(path).await?;
+    let value = content
+        .lines()
+        .find_map(|line| line.strip_prefix(RESOURCE_PACKS_KEY)?.strip_prefix(':'));
+    match value {
+        Some(value) => Ok(serde_json::from_str(value.trim()).unwrap_or_default()),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Replace the `resourcePacks` list, leaving every other option untouched
+pub async fn write_resource_packs(game_dir: &Path, packs: &[String]) -> Result<()> {
+    let path = game_dir.join("options.txt");
+    let content = if path.exists() {
+        tokio::fs::read_to_string(&path).await?
+    } else {
+        String::new()
+    };
+
+    let new_line = format!("{}:{}", RESOURCE_PACKS_KEY, serde_json::to_string(packs)?);
+    let mut replaced = false;
+    let mut lines: Vec<String> = content
+        .lines()
+        .map(|line| {
+            if line.starts_with(&format!("{}:", RESOURCE_PACKS_KEY)) {
+                replaced = true;
+                new_line.clone()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    if !replaced {
+        lines.push(new_line);
+    }
+
+    tokio::fs::create_dir_all(game_dir).await?;
+    tokio::fs::write(path, lines.join("\n") + "\n").await?;
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PackKind {
+    ResourcePack,
+    ShaderPack,
+    /// Data pack of the given world (folder name under `saves/`)
+    DataPack(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackInfo {
+    pub path: PathBuf,
+    pub file_name: String,
+    pub kind: PackKind,
+    pub description: Option<String>,
+    pub pack_format: Option<u32>,
+    /// Whether `pack_format` fits the instance's game version, `None` if unknown
+    pub compatible: Option<bool>,
+    /// `pack.png` as a data URL
+    pub icon: Option<String>,
+    /// Resource packs only: listed in `options.txt`
+    pub enabled: bool,
+}
+
+/// Contents of `pack.mcmeta` we care about
+#[derive(Debug, Default)]
+pub(crate) struct PackMeta {
+    pub description: Option<String>,
+    pub pack_format: Option<u32>,
+    /// Inclusive range from `supported_formats`
+    pub supported: Option<(u32, u32)>,
+}
@@ -0,0 +1,110 @@
+use crate::scripts::instances::Instance;
+use anyhow::Result;
+use std::path::Path;
+use std::process::Stdio;
+
+/// Variables describing the instance, passed to every hook
+pub fn hook_env(instance: &Instance, java_path: &Path) -> Vec<(String, String)> {
+    let absolute = |path: &Path| {
+        std::fs::canonicalize(path)
+            .unwrap_or_else(|_| path.to_path_buf())
+            .to_string_lossy()
+            .trim_start_matches(r"\\?\")
+            .to_string()
+    };
+
+    vec![
+        ("INST_ID".to_string(), instance.id.clone()),
+        ("INST_NAME".to_string(), instance.name.clone()),
+        ("INST_DIR".to_string(), absolute(&instance.dir())),
+        ("INST_MC_DIR".to_string(), absolute(&instance.game_dir())),
+        ("INST_MC_VER".to_string(), instance.game_version.clone()),
+        ("INST_LOADER".to_string(), format!("{:?}", instance.loader)),
+        (
+            "INST_LOADER_VER".to_string(),
+            instance.loader_version.clone().unwrap_or_default(),
+        ),
+        ("INST_JAVA".to_string(), absolute(java_path)),
+    ]
+}
+
+/// Run a hook command through the system shell in the game directory, returns its exit code
+pub async fn run_hook(
+    name: &str,
+    command: &str,
+    instance: &Instance,
+    env: &[(String, String)],
+) -> Result<Option<i32>> {
+    log::info!("Running {} hook: {}", name, command);
+
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    } else {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    };
+
+    tokio::fs::create_dir_all(instance.game_dir()).await?;
+    let output = cmd
+        .current_dir(instance.game_dir())
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .stdin(Stdio::null())
+        .output()
+        .await?;
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        log::info!("[{}] {}", name, line);
+    }
+    for line in String::from_utf8_lossy(&output.stderr).lines() {
+        log::warn!("[{}] {}", name, line);
+    }
+
+    Ok(output.status.code())
+}
+
+/// Run the pre-launch hook if set, a failure aborts the launch
+pub async fn pre_launch(instance: &Instance, java_path: &Path) -> Result<()> {
+    let Some(command) = instance
+        .hooks
+        .pre_launch
+        .as_deref()
+        .filter(|c| !c.trim().is_empty())
+    else {
+        return Ok(());
+    };
+
+    let env = hook_env(instance, java_path);
+    match run_hook("pre-launch", command, instance, &env).await? {
+        Some(0) => Ok(()),
+        code => Err(anyhow::anyhow!(
+            "Pre-launch hook failed with exit code {}, launch aborted",
+            code.map_or("none".to_string(), |c| c.to_string())
+        )),
+    }
+}
+
+/// Run the post-exit hook if set, with the game's exit code in `EXIT_CODE`
+pub async fn post_exit(instance: &Instance, java_path: &Path, exit_code: Option<i32>) {
+    let Some(command) = instance
+        .hooks
+        .post_exit
+        .as_deref()
+        .filter(|c| !c.trim().is_empty())
+    else {
+        return;
+    };
+
+    let mut env = hook_env(instance, java_path);
+    env.push((
+        "EXIT_CODE".to_string(),
+        exit_code.map(|c| c.to_string()).unwrap_or_default(),
+    ));
+    match run_hook("post-exit", command, instance, &env).await {
+        Ok(Some(0)) => {}
+        Ok(code) => log::warn!("Post-exit hook exited with code {:?}", code),
+        Err(e) => log::error!("Failed to run post-exit hook: {}", e),
+    }
+}
@@ -0,0 +1,234 @@
+use log::Level;
+
+/// A `<log4j:Event>` from the game's XML log layout
+#[derive(Debug, Clone, PartialEq)]
+pub struct Log4jEvent {
+    pub level: Level,
+    /// Unix timestamp (milliseconds)
+    pub timestamp: Option<u64>,
+    pub thread: Option<String>,
+    pub logger: Option<String>,
+    pub message: String,
+    pub throwable: Option<String>,
+}
+
+/// What a line of game output turned into
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedLine {
+    Event(Log4jEvent),
+    /// Not part of an XML event (old versions, mods printing directly, JVM messages)
+    Plain(String),
+}
+
+/// Collects the lines of an event until it's closed, one per output stream
+#[derive(Debug, Default)]
+pub struct Log4jParser {
+    buffer: Option<String>,
+}
+
+impl Log4jParser {
+    /// Feed a line, returns something once a whole event or a plain line has been read
+    pub fn feed(&mut self, line: &str) -> Option<ParsedLine> {
+        if let Some(buffer) = &mut self.buffer {
+            buffer.push('\n');
+            buffer.push_str(line);
+            if !line.contains("</log4j:Event>") {
+                return None;
+            }
+            let xml = self.buffer.take().unwrap_or_default();
+            return Some(parse_event(&xml).map_or(ParsedLine::Plain(xml), ParsedLine::Event));
+        }
+
+        if line.trim_start().starts_with("<log4j:Event") {
+            if line.contains("</log4j:Event>") {
+                let parsed = parse_event(line)
+                    .map_or_else(|| ParsedLine::Plain(line.to_string()), ParsedLine::Event);
+                return Some(parsed);
+            }
+            self.buffer = Some(line.to_string());
+            return None;
+        }
+
+        Some(ParsedLine::Plain(line.to_string()))
+    }
+
+    /// Whatever is left of an unfinished event when the stream closes
+    pub fn finish(&mut self) -> Option<ParsedLine> {
+        self.buffer.take().map(ParsedLine::Plain)
+    }
+}
+
+fn parse_event(xml: &str) -> Option<Log4jEvent> {
+    let start = xml.find("<log4j:Event")?;
+    let tag_end = start + xml[start..].find('>')?;
+    let tag = &xml[start..tag_end];
+
+    let level = attribute(tag, "level").map_or(Level::Info, |l| parse_level(&l));
+
+    Some(Log4jEvent {
+        level,
+        timestamp: attribute(tag, "timestamp").and_then(|t| t.parse().ok()),
+        thread: attribute(tag, "thread"),
+        logger: attribute(tag, "logger"),
+        message: element(xml, "log4j:Message").unwrap_or_default(),
+        throwable: element(xml, "log4j:Throwable"),
+    })
+}
+
+/// Log4j levels mapped onto the ones the launcher logs with
+pub fn parse_level(level: &str) -> Level {
+    match level.to_ascii_uppercase().as_str() {
+        "FATAL" | "ERROR" => Level::Error,
+        "WARN" | "WARNING" => Level::Warn,
+        "DEBUG" => Level::Debug,
+        "TRACE" => Level::Trace,
+        _ => Level::Info,
+    }
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let needle = format!(" {}=\"", name);
+    let start = tag.find(&needle)? + needle.len();
+    let end = start + tag[start..].find('"')?;
+    Some(unescape(&tag[start..end]))
+}
+
+/// Text of `<name>...</name>`, CDATA or escaped
+fn element(xml: &str, name: &str) -> Option<String> {
+    let open = format!("<{}>", name);
+    let close = format!("</{}>", name);
+    let start = xml.find(&open)? + open.len();
+    let end = start + xml[start..].find(&close)?;
+    let content = xml[start..end].trim();
+
+    match content
+        .strip_prefix("<![CDATA[")
+        .and_then(|c| c.strip_suffix("]]>"))
+    {
+        Some(cdata) => Some(cdata.trim_end().to_string()),
+        None => Some(unescape(content)),
+    }
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#10;", "\n")
+        .replace("&#13;", "\r")
+        .replace("&amp;", "&")
+}
+
+/// Best guess at the level of a plain line like `[12:00:00] [Render thread/WARN]: ...`
+pub fn guess_level(line: &str) -> Option<Level> {
+    if !line.starts_with('[') {
+        return None;
+    }
+    let start = line.find('/')? + 1;
+    let end = start + line[start..].find(']')?;
+    match &line[start..end] {
+        "FATAL" | "ERROR" | "WARN" | "INFO" | "DEBUG" | "TRACE" => {
+            Some(parse_level(&line[start..end]))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed_all(parser: &mut Log4jParser, lines: &[&str]) -> Vec<ParsedLine> {
+        lines.iter().filter_map(|line| parser.feed(line)).collect()
+    }
+
+    #[test]
+    fn multi_line_events_parse() {
+        let mut parser = Log4jParser::default();
+        let parsed = feed_all(
+            &mut parser,
+            &[
+                r#"<log4j:Event logger="net.minecraft.client.Minecraft" timestamp="1718000000000" level="WARN" thread="Render thread">"#,
+                r#"  <log4j:Message><![CDATA[Missing sound for event: "minecraft:item.goat_horn" & more]]></log4j:Message>"#,
+                "</log4j:Event>",
+            ],
+        );
+        assert_eq!(
+            parsed,
+            [ParsedLine::Event(Log4jEvent {
+                level: Level::Warn,
+                timestamp: Some(1718000000000),
+                thread: Some("Render thread".to_string()),
+                logger: Some("net.minecraft.client.Minecraft".to_string()),
+                message: r#"Missing sound for event: "minecraft:item.goat_horn" & more"#.to_string(),
+                throwable: None,
+            })]
+        );
+    }
+
+    #[test]
+    fn cdata_throwables_keep_their_lines() {
+        let mut parser = Log4jParser::default();
+        let parsed = feed_all(
+            &mut parser,
+            &[
+                r#"<log4j:Event logger="Worker" timestamp="1" level="ERROR" thread="main">"#,
+                "  <log4j:Message><![CDATA[Failed to load &lt;mod&gt;]]></log4j:Message>",
+                "  <log4j:Throwable><![CDATA[java.lang.RuntimeException: boom",
+                "\tat a.b.C.run(C.java:1)",
+                "]]></log4j:Throwable>",
+                "</log4j:Event>",
+            ],
+        );
+        let [ParsedLine::Event(event)] = parsed.as_slice() else {
+            panic!("expected one event, got {:?}", parsed);
+        };
+        assert_eq!(event.level, Level::Error);
+        // CDATA is taken as is, escapes only apply outside of it
+        assert_eq!(event.message, "Failed to load &lt;mod&gt;");
+        assert_eq!(
+            event.throwable.as_deref(),
+            Some("java.lang.RuntimeException: boom\n\tat a.b.C.run(C.java:1)")
+        );
+    }
+
+    #[test]
+    fn events_split_across_reads_wait_for_the_end() {
+        let mut parser = Log4jParser::default();
+        assert_eq!(
+            parser.feed(r#"<log4j:Event logger="a" timestamp="2" level="INFO" thread="main">"#),
+            None
+        );
+        assert_eq!(parser.feed("  <log4j:Message>Loading &quot;world&quot;</log4j:Message>"), None);
+        let Some(ParsedLine::Event(event)) = parser.feed("</log4j:Event>") else {
+            panic!("the event should be complete");
+        };
+        assert_eq!(event.message, "Loading \"world\"");
+
+        // A stream closing mid-event gives back what was read
+        assert_eq!(parser.feed(r#"<log4j:Event logger="a" level="INFO">"#), None);
+        assert_eq!(
+            parser.finish(),
+            Some(ParsedLine::Plain(r#"<log4j:Event logger="a" level="INFO">"#.to_string()))
+        );
+        assert_eq!(parser.finish(), None);
+    }
+
+    #[test]
+    fn plain_lines_pass_through() {
+        let mut parser = Log4jParser::default();
+        let lines = [
+            "[12:00:00] [Render thread/WARN]: Old style log line",
+            "Picked up _JAVA_OPTIONS: -Dawt.useSystemAAFontSettings=on",
+            r#"<log4j:Event logger="a" timestamp="3" level="DEBUG" thread="main"><log4j:Message>inline</log4j:Message></log4j:Event>"#,
+        ];
+        let parsed = feed_all(&mut parser, &lines);
+        assert_eq!(parsed[0], ParsedLine::Plain(lines[0].to_string()));
+        assert_eq!(parsed[1], ParsedLine::Plain(lines[1].to_string()));
+        assert!(matches!(&parsed[2], ParsedLine::Event(e) if e.message == "inline" && e.level == Level::Debug));
+
+        assert_eq!(guess_level(lines[0]), Some(Level::Warn));
+        assert_eq!(guess_level(lines[1]), None);
+    }
+}
@@ -0,0 +1,414 @@
+pub mod hooks;
+pub mod log4j;
+pub mod types;
+
+use crate::scripts::crash;
+use crate::scripts::game::LaunchedGame;
+use crate::scripts::instances::{Instance, SessionEnd, SessionRecord, history, now_secs};
+use crate::state::{CRASHES, GAME_LOGS, GAMES};
+use log4j::{Log4jParser, ParsedLine};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Child;
+use tokio::sync::mpsc;
+pub use types::{GameLogEntry, GameProcess, GameState, OutputStream, StopRequest};
+
+/// Entries kept per instance, older ones are dropped
+const MAX_LOG_LINES: usize = 20_000;
+
+/// How often new output is moved into `GAME_LOGS`, every write there re-renders the consoles
+const LOG_FLUSH_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Log entries checked against the crash rules
+const CRASH_LOG_TAIL: usize = 500;
+
+/// Stop channels of the running games, by run. Not a signal since senders aren't comparable.
+static STOP_SENDERS: LazyLock<Mutex<HashMap<u64, mpsc::UnboundedSender<StopRequest>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_RUN: AtomicU64 = AtomicU64::new(1);
+
+/// Add a starting run of the instance (before installing), returns its run number.
+/// Finished runs of the instance are dropped along with their logs.
+pub fn mark_starting(instance: &Instance) -> u64 {
+    let run = NEXT_RUN.fetch_add(1, Ordering::Relaxed);
+    let finished: Vec<u64> = GAMES
+        .peek()
+        .values()
+        .filter(|g| g.instance_id == instance.id && !g.state.is_active())
+        .map(|g| g.run)
+        .collect();
+
+    let mut games = GAMES.write();
+    let mut logs = GAME_LOGS.write();
+    for old in finished {
+        games.remove(&old);
+        logs.remove(&old);
+    }
+    logs.insert(run, Vec::new());
+    CRASHES.write().remove(&instance.id);
+    games.insert(
+        run,
+        GameProcess {
+            run,
+            instance_id: instance.id.clone(),
+            instance_name: instance.name.clone(),
+            game_dir: instance.game_dir(),
+            pid: None,
+            started_at: now_secs(),
+            state: GameState::Starting,
+        },
+    );
+    run
+}
+
+/// Forget a run that never got to spawn the game
+pub fn mark_failed(run: u64) {
+    GAMES.write().remove(&run);
+    GAME_LOGS.write().remove(&run);
+}
+
+/// Whether the instance has a run starting or running
+pub fn is_active(instance_id: &str) -> bool {
+    !active_runs(instance_id).is_empty()
+}
+
+/// Starting or running runs of the instance, oldest first
+pub fn active_runs(instance_id: &str) -> Vec<u64> {
+    let mut runs: Vec<u64> = GAMES
+        .peek()
+        .values()
+        .filter(|g| g.instance_id == instance_id && g.state.is_active())
+        .map(|g| g.run)
+        .collect();
+    runs.sort();
+    runs
+}
+
+/// The newest run of the instance in `games`, finished or not
+pub fn latest_run(games: &HashMap<u64, GameProcess>, instance_id: &str) -> Option<GameProcess> {
+    games
+        .values()
+        .filter(|g| g.instance_id == instance_id)
+        .max_by_key(|g| g.run)
+        .cloned()
+}
+
+pub fn request_stop(run: u64, request: StopRequest) {
+    let senders = STOP_SENDERS.lock().unwrap();
+    match senders.get(&run) {
+        Some(sender) => {
+            let _ = sender.send(request);
+        }
+        None => log::warn!("Run {} is not running", run),
+    }
+}
+
+/// Stop every run of the instance
+pub fn request_stop_instance(instance_id: &str, request: StopRequest) {
+    for run in active_runs(instance_id) {
+        request_stop(run, request);
+    }
+}
+
+/// Follow the game until it exits: collect its output, keep `GAMES` up to date and run the post-exit hook
+pub async fn supervise(instance: &Instance, run: u64, launched: LaunchedGame) {
+    let LaunchedGame {
+        mut child,
+        java_path,
+        temp_dir,
+    } = launched;
+    let id = instance.id.clone();
+    let pid = child.id();
+    log::info!("{} started (PID {:?})", instance.name, pid);
+    // Playtime counts from here, installing isn't playing
+    let spawned_at = now_secs();
+    let mut started_at = spawned_at;
+    if let Some(game) = GAMES.write().get_mut(&run) {
+        game.pid = pid;
+        started_at = game.started_at;
+    }
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    STOP_SENDERS.lock().unwrap().insert(run, tx);
+
+    let mut stdout = child.stdout.take().map(PipeLines::new);
+    let mut stderr = child.stderr.take().map(PipeLines::new);
+    let mut stdout_parser = Log4jParser::default();
+    let mut stderr_parser = Log4jParser::default();
+    let mut stop_requested = false;
+    // Entries not in `GAME_LOGS` yet, written in batches
+    let mut pending = Vec::new();
+    let mut flush = tokio::time::interval(LOG_FLUSH_INTERVAL);
+    flush.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    // Everything runs in this task so signal writes stay on the Dioxus runtime
+    let status = loop {
+        tokio::select! {
+            line = next_line(&mut stdout) => match line {
+                Some(text) => push_line(run, OutputStream::Stdout, &mut stdout_parser, &text, &mut pending),
+                None => stdout = None,
+            },
+            line = next_line(&mut stderr) => match line {
+                Some(text) => push_line(run, OutputStream::Stderr, &mut stderr_parser, &text, &mut pending),
+                None => stderr = None,
+            },
+            _ = flush.tick() => flush_logs(run, &mut pending),
+            Some(request) = rx.recv() => {
+                stop_requested = true;
+                log::info!("{:?} requested for {}", request, instance.name);
+                if let Err(e) = stop(&mut child, request).await {
+                    log::error!("Failed to stop {}: {}", instance.name, e);
+                }
+            }
+            status = child.wait() => break status,
+        }
+    };
+
+    // Drain what's left in the pipes
+    while let Some(text) = next_line(&mut stdout).await {
+        push_line(run, OutputStream::Stdout, &mut stdout_parser, &text, &mut pending);
+    }
+    while let Some(text) = next_line(&mut stderr).await {
+        push_line(run, OutputStream::Stderr, &mut stderr_parser, &text, &mut pending);
+    }
+    for (stream, parser) in [
+        (OutputStream::Stdout, &mut stdout_parser),
+        (OutputStream::Stderr, &mut stderr_parser),
+    ] {
+        if let Some(parsed) = parser.finish() {
+            pending.push(to_entry(stream, parsed));
+        }
+    }
+    flush_logs(run, &mut pending);
+
+    STOP_SENDERS.lock().unwrap().remove(&run);
+    if let Some(dir) = temp_dir {
+        if let Err(e) = tokio::fs::remove_dir_all(&dir).await {
+            log::warn!("Failed to remove {}: {}", dir.display(), e);
+        }
+    }
+
+    let state = match status {
+        Ok(status) if status.success() || stop_requested => GameState::Exited(status.code()),
+        Ok(status) => GameState::Crashed(status.code()),
+        Err(e) => {
+            log::error!("Failed to wait for {}: {}", instance.name, e);
+            GameState::Crashed(None)
+        }
+    };
+    log::info!("{} finished: {:?}", instance.name, state);
+
+    if let GameState::Crashed(code) = state {
+        let log_tail = log_tail(run);
+        let analysis = crash::analyze_crash(instance, started_at, code, &log_tail).await;
+        for diagnosis in &analysis.diagnoses {
+            log::warn!(
+                "{} crash: {} ({})",
+                instance.name,
+                diagnosis.title,
+                diagnosis.evidence
+            );
+        }
+        CRASHES.write().insert(id.clone(), analysis);
+    }
+
+    let exit_code = match state {
+        GameState::Exited(code) | GameState::Crashed(code) => code,
+        _ => None,
+    };
+    let record = SessionRecord {
+        started_at: spawned_at,
+        ended_at: now_secs(),
+        exit_code,
+        end: match state {
+            GameState::Crashed(_) => SessionEnd::Crashed,
+            _ if stop_requested => SessionEnd::Stopped,
+            _ => SessionEnd::Exited,
+        },
+    };
+    if let Err(e) = history::record_session(instance, record).await {
+        log::error!("Failed to record the session of {}: {}", instance.name, e);
+    }
+    if let Some(game) = GAMES.write().get_mut(&run) {
+        game.state = state;
+    }
+
+    hooks::post_exit(instance, &java_path, exit_code).await;
+}
+
+/// Text of the last entries of the game log, throwables included
+fn log_tail(run: u64) -> String {
+    let logs = GAME_LOGS.peek();
+    let Some(entries) = logs.get(&run) else {
+        return String::new();
+    };
+
+    let mut text = String::new();
+    for entry in entries
+        .iter()
+        .skip(entries.len().saturating_sub(CRASH_LOG_TAIL))
+    {
+        text.push_str(&entry.message);
+        text.push('\n');
+        if let Some(throwable) = &entry.throwable {
+            text.push_str(throwable);
+            text.push('\n');
+        }
+    }
+    text
+}
+
+/// Lines of a game pipe, decoded lossily so a stray non-UTF-8 byte doesn't end the capture
+struct PipeLines<R> {
+    reader: BufReader<R>,
+    /// Partial line, kept across calls so `select!` can drop a read without losing bytes
+    buffer: Vec<u8>,
+}
+
+impl<R: AsyncRead + Unpin> PipeLines<R> {
+    fn new(pipe: R) -> Self {
+        Self {
+            reader: BufReader::new(pipe),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// `None` once the pipe is closed
+    async fn next_line(&mut self) -> std::io::Result<Option<String>> {
+        let read = self.reader.read_until(b'\n', &mut self.buffer).await?;
+        if read == 0 && self.buffer.is_empty() {
+            return Ok(None);
+        }
+        let mut line = std::mem::take(&mut self.buffer);
+        if line.last() == Some(&b'\n') {
+            line.pop();
+        }
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        Ok(Some(String::from_utf8_lossy(&line).into_owned()))
+    }
+}
+
+async fn next_line<R: AsyncRead + Unpin>(lines: &mut Option<PipeLines<R>>) -> Option<String> {
+    match lines {
+        Some(lines) => match lines.next_line().await {
+            Ok(line) => line,
+            Err(e) => {
+                log::warn!("Stopped reading the game output: {}", e);
+                None
+            }
+        },
+        // Closed pipe, never resolves so `select!` waits on the other branches
+        None => std::future::pending().await,
+    }
+}
+
+fn push_line(
+    run: u64,
+    stream: OutputStream,
+    parser: &mut Log4jParser,
+    text: &str,
+    pending: &mut Vec<GameLogEntry>,
+) {
+    // The first output means the JVM is up
+    if GAMES
+        .peek()
+        .get(&run)
+        .is_some_and(|g| g.state == GameState::Starting)
+    {
+        if let Some(game) = GAMES.write().get_mut(&run) {
+            game.state = GameState::Running;
+        }
+    }
+
+    if let Some(parsed) = parser.feed(text) {
+        pending.push(to_entry(stream, parsed));
+    }
+}
+
+fn to_entry(stream: OutputStream, parsed: ParsedLine) -> GameLogEntry {
+    match parsed {
+        ParsedLine::Event(event) => GameLogEntry {
+            stream,
+            level: event.level,
+            timestamp: event.timestamp,
+            thread: event.thread,
+            logger: event.logger,
+            message: event.message,
+            throwable: event.throwable,
+        },
+        ParsedLine::Plain(text) => {
+            let fallback = match stream {
+                OutputStream::Stdout => log::Level::Info,
+                OutputStream::Stderr => log::Level::Error,
+            };
+            GameLogEntry {
+                stream,
+                level: log4j::guess_level(&text).unwrap_or(fallback),
+                timestamp: None,
+                thread: None,
+                logger: None,
+                message: text,
+                throwable: None,
+            }
+        }
+    }
+}
+
+/// Move the pending entries into `GAME_LOGS` in one write
+fn flush_logs(run: u64, pending: &mut Vec<GameLogEntry>) {
+    if pending.is_empty() {
+        return;
+    }
+    let mut logs = GAME_LOGS.write();
+    let log = logs.entry(run).or_default();
+    log.append(pending);
+    if log.len() > MAX_LOG_LINES {
+        let overflow = log.len() - MAX_LOG_LINES;
+        log.drain(..overflow);
+    }
+}
+
+async fn stop(child: &mut Child, request: StopRequest) -> anyhow::Result<()> {
+    match (request, child.id()) {
+        (StopRequest::Stop, Some(pid)) => {
+            // SIGTERM / WM_CLOSE let the game save and close on its own
+            let status = if cfg!(target_os = "windows") {
+                tokio::process::Command::new("taskkill")
+                    .args(["/PID", &pid.to_string()])
+                    .status()
+                    .await?
+            } else {
+                tokio::process::Command::new("kill")
+                    .args(["-TERM", &pid.to_string()])
+                    .status()
+                    .await?
+            };
+            if !status.success() {
+                return Err(anyhow::anyhow!("Stop command failed: {}", status));
+            }
+        }
+        _ => child.start_kill()?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn invalid_utf8_doesnt_end_the_output() {
+        let output: &[u8] = b"[main/INFO]: caf\xe9\r\nsecond line\nno newline";
+        let mut lines = Some(PipeLines::new(output));
+        assert_eq!(next_line(&mut lines).await.as_deref(), Some("[main/INFO]: caf\u{fffd}"));
+        assert_eq!(next_line(&mut lines).await.as_deref(), Some("second line"));
+        assert_eq!(next_line(&mut lines).await.as_deref(), Some("no newline"));
+        assert_eq!(next_line(&mut lines).await, None);
+    }
+}
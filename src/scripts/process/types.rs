@@ -0,0 +1,61 @@
+use log::Level;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameState {
+    /// Installing and spawning, no process yet
+    Starting,
+    Running,
+    /// Exited normally or was stopped by the user
+    Exited(Option<i32>),
+    /// Exited with a failure code (or was killed by something else than us)
+    Crashed(Option<i32>),
+}
+
+impl GameState {
+    pub fn is_active(&self) -> bool {
+        matches!(self, GameState::Starting | GameState::Running)
+    }
+}
+
+/// A game run of an instance
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameProcess {
+    /// Counts up with every launch, an instance can have several runs going at once
+    pub run: u64,
+    pub instance_id: String,
+    pub instance_name: String,
+    pub game_dir: PathBuf,
+    pub pid: Option<u32>,
+    /// Unix timestamp (seconds)
+    pub started_at: u64,
+    pub state: GameState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// A game log record, parsed from a Log4j event when the game logs XML
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameLogEntry {
+    pub stream: OutputStream,
+    pub level: Level,
+    /// Unix timestamp (milliseconds)
+    pub timestamp: Option<u64>,
+    pub thread: Option<String>,
+    pub logger: Option<String>,
+    pub message: String,
+    pub throwable: Option<String>,
+}
+
+/// What the user asked the supervisor to do
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StopRequest {
+    /// Ask the game to close, like clicking the window's close button
+    Stop,
+    /// Kill the process right away
+    Kill,
+}
@@ -0,0 +1,81 @@
+pub mod shortcut;
+pub mod types;
+
+use crate::scripts::instances::Instance;
+use anyhow::Result;
+use quartz_nbt::io::{Flavor, read_nbt};
+use quartz_nbt::{NbtCompound, NbtList};
+use std::path::Path;
+pub use types::{LaunchArgs, SavedServer, SavedWorld};
+
+/// Servers from the instance's multiplayer list, in the order the game shows them
+pub async fn list_servers(instance: &Instance) -> Result<Vec<SavedServer>> {
+    let path = instance.game_dir().join("servers.dat");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let root = read_nbt_file(&path, Flavor::Uncompressed)?;
+        let servers = root
+            .get::<_, &NbtList>("servers")
+            .map(|list| {
+                list.iter_map::<&NbtCompound>()
+                    .filter_map(|server| server.ok())
+                    .filter_map(|server| {
+                        let address = server.get::<_, &str>("ip").ok()?.trim().to_string();
+                        let name = server
+                            .get::<_, &str>("name")
+                            .map_or_else(|_| address.clone(), str::to_string);
+                        (!address.is_empty()).then_some(SavedServer { name, address })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(servers)
+    })
+    .await?
+}
+
+/// Worlds in `saves/`, most recently played first
+pub async fn list_worlds(instance: &Instance) -> Result<Vec<SavedWorld>> {
+    let saves = instance.game_dir().join("saves");
+    if !saves.exists() {
+        return Ok(Vec::new());
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let mut worlds = Vec::new();
+        for entry in std::fs::read_dir(&saves)? {
+            let path = entry?.path();
+            let level = path.join("level.dat");
+            if !level.exists() {
+                continue;
+            }
+
+            let folder = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let data = read_nbt_file(&level, Flavor::GzCompressed)
+                .map_err(|e| log::warn!("Failed to read {}: {}", level.display(), e))
+                .ok();
+            let data = data.as_ref().and_then(|root| root.get::<_, &NbtCompound>("Data").ok());
+            worlds.push(SavedWorld {
+                name: data
+                    .and_then(|d| d.get::<_, &str>("LevelName").ok())
+                    .filter(|name| !name.is_empty())
+                    .map_or_else(|| folder.clone(), str::to_string),
+                last_played: data.and_then(|d| d.get::<_, i64>("LastPlayed").ok()),
+                folder,
+            });
+        }
+
+        worlds.sort_by_key(|w| std::cmp::Reverse(w.last_played));
+        Ok(worlds)
+    })
+    .await?
+}
+
+fn read_nbt_file(path: &Path, flavor: Flavor) -> Result<NbtCompound> {
+    let mut file = std::io::BufReader::new(std::fs::File::open(path)?);
+    let (root, _) = read_nbt(&mut file, flavor)?;
+    Ok(root)
+}
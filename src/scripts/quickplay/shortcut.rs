@@ -0,0 +1,113 @@
+use super::types::LaunchArgs;
+use crate::scripts::game::QuickPlay;
+use crate::scripts::instances::Instance;
+use crate::scripts::shell::{cmd_quote, path_str, sh_quote};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// Put a shortcut on the desktop that opens the launcher and starts the instance,
+/// straight into a server or world when `quick_play` is set
+pub async fn create_shortcut(instance: &Instance, quick_play: Option<QuickPlay>) -> Result<PathBuf> {
+    let exe = std::env::current_exe()?;
+    // The launcher keeps its data next to where it runs from
+    let cwd = std::env::current_dir()?;
+    let desktop = dirs::desktop_dir()
+        .or_else(dirs::home_dir)
+        .ok_or_else(|| anyhow::anyhow!("Couldn't find the desktop folder"))?;
+
+    let title = match &quick_play {
+        Some(QuickPlay::Multiplayer(address)) => format!("{} - {}", instance.name, address),
+        Some(QuickPlay::Singleplayer(world)) => format!("{} - {}", instance.name, world),
+        Some(QuickPlay::Realms(realm)) => format!("{} - Realm {}", instance.name, realm),
+        None => instance.name.clone(),
+    };
+    let args = LaunchArgs {
+        instance_id: instance.id.clone(),
+        quick_play,
+    }
+    .to_args();
+
+    let (file_name, content) = if cfg!(target_os = "windows") {
+        (format!("{}.bat", file_stem(&title)), batch_shortcut(&exe, &cwd, &args))
+    } else if cfg!(target_os = "macos") {
+        (format!("{}.command", file_stem(&title)), shell_shortcut(&exe, &cwd, &args))
+    } else {
+        (format!("{}.desktop", file_stem(&title)), desktop_entry(&title, &exe, &cwd, &args))
+    };
+
+    tokio::fs::create_dir_all(&desktop).await?;
+    let path = desktop.join(file_name);
+    tokio::fs::write(&path, content).await?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        tokio::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).await?;
+    }
+
+    log::info!("Shortcut created at {}", path.display());
+    Ok(path)
+}
+
+/// Title without the characters file systems don't allow
+fn file_stem(title: &str) -> String {
+    title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Quoted for `Exec=`, escaped once for the argument and once more for the string value
+fn desktop_quote(arg: &str) -> String {
+    let escaped = arg
+        .replace('\\', r"\\")
+        .replace('"', "\\\"")
+        .replace('`', "\\`")
+        .replace('$', "\\$");
+    format!("\"{}\"", escaped.replace('\\', r"\\").replace('%', "%%"))
+}
+
+fn desktop_entry(title: &str, exe: &Path, cwd: &Path, args: &[String]) -> String {
+    let exec = std::iter::once(path_str(exe))
+        .chain(args.iter().cloned())
+        .map(|arg| desktop_quote(&arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        "[Desktop Entry]\nType=Application\nName={}\nExec={}\nPath={}\nTerminal=false\nCategories=Game;\n",
+        title.replace('\n', " "),
+        exec,
+        path_str(cwd)
+    )
+}
+
+fn shell_shortcut(exe: &Path, cwd: &Path, args: &[String]) -> String {
+    let command = std::iter::once(path_str(exe))
+        .chain(args.iter().cloned())
+        .map(|arg| sh_quote(&arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        "#!/bin/sh\ncd {} || exit 1\nexec {}\n",
+        sh_quote(&path_str(cwd)),
+        command
+    )
+}
+
+fn batch_shortcut(exe: &Path, cwd: &Path, args: &[String]) -> String {
+    let command = std::iter::once(path_str(exe))
+        .chain(args.iter().cloned())
+        .map(|arg| cmd_quote(&arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    // `start` so the console window closes right away
+    format!(
+        "@echo off\r\ncd /d {}\r\nstart \"\" {}\r\n",
+        cmd_quote(&path_str(cwd)),
+        command
+    )
+}
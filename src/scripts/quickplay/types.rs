@@ -0,0 +1,87 @@
+use crate::scripts::game::QuickPlay;
+
+/// An entry of the in-game server list (`servers.dat`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavedServer {
+    pub name: String,
+    pub address: String,
+}
+
+/// A world in `saves/`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavedWorld {
+    /// Folder name, what Quick Play opens
+    pub folder: String,
+    /// Name shown in the world list
+    pub name: String,
+    /// Unix timestamp (milliseconds)
+    pub last_played: Option<i64>,
+}
+
+/// What the launcher was asked to start from the command line (or a desktop shortcut)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchArgs {
+    pub instance_id: String,
+    pub quick_play: Option<QuickPlay>,
+}
+
+impl LaunchArgs {
+    /// `--launch <id> [--server <address> | --world <folder> | --realm <id>]`
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Option<Self> {
+        let mut instance_id = None;
+        let mut quick_play = None;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--launch" => instance_id = args.next(),
+                "--server" => quick_play = args.next().map(QuickPlay::Multiplayer),
+                "--world" => quick_play = args.next().map(QuickPlay::Singleplayer),
+                "--realm" => quick_play = args.next().map(QuickPlay::Realms),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            instance_id: instance_id?,
+            quick_play,
+        })
+    }
+
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec!["--launch".to_string(), self.instance_id.clone()];
+        match &self.quick_play {
+            Some(QuickPlay::Multiplayer(address)) => {
+                args.extend(["--server".to_string(), address.clone()])
+            }
+            Some(QuickPlay::Singleplayer(world)) => {
+                args.extend(["--world".to_string(), world.clone()])
+            }
+            Some(QuickPlay::Realms(realm)) => args.extend(["--realm".to_string(), realm.clone()]),
+            None => {}
+        }
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn launch_args_round_trip() {
+        let targets = [
+            None,
+            Some(QuickPlay::Multiplayer("mc.example.com:25566".to_string())),
+            Some(QuickPlay::Singleplayer("New World".to_string())),
+            Some(QuickPlay::Realms("1234567".to_string())),
+        ];
+        for quick_play in targets {
+            let args = LaunchArgs {
+                instance_id: "survival".to_string(),
+                quick_play,
+            };
+            assert_eq!(LaunchArgs::parse(args.to_args()), Some(args));
+        }
+        assert_eq!(LaunchArgs::parse(["--realm".to_string(), "1".to_string()]), None);
+    }
+}
@@ -0,0 +1,29 @@
+pub mod types;
+
+use crate::scripts::game::DATA_DIR;
+use anyhow::Result;
+use std::path::PathBuf;
+pub use types::{JavaDefaults, LauncherSettings};
+
+const SETTINGS_FILE: &str = "settings.json";
+
+fn settings_path() -> PathBuf {
+    PathBuf::from(DATA_DIR).join(SETTINGS_FILE)
+}
+
+/// Saved settings, or the defaults if nothing was saved yet
+pub async fn load_settings() -> Result<LauncherSettings> {
+    let path = settings_path();
+    if !path.exists() {
+        return Ok(LauncherSettings::default());
+    }
+
+    let content = tokio::fs::read_to_string(path).await?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+pub async fn save_settings(settings: &LauncherSettings) -> Result<()> {
+    tokio::fs::create_dir_all(DATA_DIR).await?;
+    tokio::fs::write(settings_path(), serde_json::to_string_pretty(settings)?).await?;
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use crate::components::login::script::DEFAULT_CALLBACK_PORTS;
+use crate::scripts::curseforge::CURSEFORGE_API;
+use crate::scripts::modrinth::MODRINTH_API;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Launcher wide settings stored in `settings.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LauncherSettings {
+    /// Modrinth compatible API base, e.g. a local mock while testing
+    pub modrinth_api: String,
+    /// CurseForge compatible API base
+    pub curseforge_api: String,
+    /// Java defaults for instances that don't override them
+    pub java: JavaDefaults,
+    /// Local ports tried in order for the OAuth login callback
+    pub callback_ports: Vec<u16>,
+}
+
+impl Default for LauncherSettings {
+    fn default() -> Self {
+        Self {
+            modrinth_api: MODRINTH_API.to_string(),
+            curseforge_api: CURSEFORGE_API.to_string(),
+            java: JavaDefaults::default(),
+            callback_ports: DEFAULT_CALLBACK_PORTS.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JavaDefaults {
+    /// Custom Java executable, the bundled Java 21 is used when unset
+    pub path: Option<PathBuf>,
+    pub min_memory_mb: u32,
+    pub max_memory_mb: u32,
+    pub jvm_args: Vec<String>,
+    pub env: BTreeMap<String, String>,
+    /// Command the Java command is run through, e.g. `gamemoderun` or `mangohud --dlsym`
+    pub wrapper: Option<String>,
+}
+
+impl Default for JavaDefaults {
+    fn default() -> Self {
+        Self {
+            path: None,
+            min_memory_mb: 1024,
+            max_memory_mb: 4096,
+            jvm_args: Vec::new(),
+            env: BTreeMap::new(),
+            wrapper: None,
+        }
+    }
+}
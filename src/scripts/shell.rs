@@ -0,0 +1,70 @@
+use std::path::Path;
+
+/// A path as scripts show it, without the `\\?\` prefix `canonicalize` adds on Windows
+pub fn path_str(path: &Path) -> String {
+    path.to_string_lossy()
+        .trim_start_matches(r"\\?\")
+        .to_string()
+}
+
+/// One argument for a POSIX shell: single quoted, so nothing in it is expanded
+pub fn sh_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+/// One argument for a batch file: double quoted, `%` and `"` doubled
+pub fn cmd_quote(arg: &str) -> String {
+    format!("\"{}\"", cmd_escape(arg))
+}
+
+/// `cmd_quote` without the surrounding quotes, for pieces of one quoted argument
+pub fn cmd_escape(arg: &str) -> String {
+    arg.replace('%', "%%").replace('"', "\"\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARGS: &[&str] = &[
+        "",
+        "plain",
+        "with spaces",
+        "it's \"quoted\"",
+        "100% %PATH%",
+        "$HOME `whoami` $(id)",
+        r"C:\Program Files\Java\bin\java.exe",
+    ];
+
+    #[test]
+    fn batch_arguments_escape_percent_and_quotes() {
+        let quoted: Vec<String> = ARGS.iter().map(|a| cmd_quote(a)).collect();
+        assert_eq!(
+            quoted,
+            [
+                r#""""#,
+                r#""plain""#,
+                r#""with spaces""#,
+                r#""it's ""quoted""""#,
+                r#""100%% %%PATH%%""#,
+                r#""$HOME `whoami` $(id)""#,
+                r#""C:\Program Files\Java\bin\java.exe""#,
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn shell_arguments_come_back_unchanged() {
+        let script = format!(
+            "printf '%s\\n' {}",
+            ARGS.iter().map(|a| sh_quote(a)).collect::<Vec<_>>().join(" ")
+        );
+        let output = std::process::Command::new("sh")
+            .args(["-c", &script])
+            .output()
+            .unwrap();
+        let printed = String::from_utf8(output.stdout).unwrap();
+        assert_eq!(printed.lines().collect::<Vec<_>>(), ARGS);
+    }
+}
@@ -0,0 +1,13 @@
+pub mod types;
+pub mod render;
+pub mod textures;
+pub mod upload;
+
+pub use render::body_data_url;
+pub use textures::fetch_textures;
+pub use types::{PlayerTextures, SkinModel};
+pub use upload::{supports_upload, upload_skin, validate_skin};
+
+pub const ELY_BY_SKINSYSTEM: &str = "https://skinsystem.ely.by";
+/// Where Ely.by players change their skin
+pub const ELY_BY_SKINS_PAGE: &str = "https://ely.by/skins";
\ No newline at end of file
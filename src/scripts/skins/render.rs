@@ -0,0 +1,152 @@
+use super::types::{PlayerTextures, SkinModel};
+use anyhow::Result;
+use base64::Engine;
+use std::io::Cursor;
+
+/// Width and height of the rendered front view in skin pixels
+const BODY_WIDTH: u32 = 16;
+const BODY_HEIGHT: u32 = 32;
+
+struct Rgba {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Rgba {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; (width * height * 4) as usize],
+        }
+    }
+
+    fn get(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * self.width + x) * 4) as usize;
+        self.pixels[i..i + 4].try_into().unwrap()
+    }
+
+    /// Alpha-blend `color` over the pixel at (x, y)
+    fn blend(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        let i = ((y * self.width + x) * 4) as usize;
+        let alpha = color[3] as u32;
+        if alpha == 0 {
+            return;
+        }
+        for c in 0..3 {
+            let dst = self.pixels[i + c] as u32;
+            self.pixels[i + c] = ((color[c] as u32 * alpha + dst * (255 - alpha)) / 255) as u8;
+        }
+        let dst_alpha = self.pixels[i + 3] as u32;
+        self.pixels[i + 3] = (alpha + dst_alpha * (255 - alpha) / 255) as u8;
+    }
+
+    /// Copy a `w`x`h` region of `src` at (sx, sy) to (dx, dy), optionally mirrored horizontally
+    fn draw(&mut self, src: &Rgba, (sx, sy): (u32, u32), (w, h): (u32, u32), (dx, dy): (u32, u32), mirror: bool) {
+        for y in 0..h {
+            for x in 0..w {
+                let src_x = if mirror { sx + w - 1 - x } else { sx + x };
+                if src_x >= src.width || sy + y >= src.height {
+                    continue;
+                }
+                self.blend(dx + x, dy + y, src.get(src_x, sy + y));
+            }
+        }
+    }
+}
+
+fn decode_png(data: &[u8]) -> Result<Rgba> {
+    let mut decoder = png::Decoder::new(Cursor::new(data));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info()?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf)?;
+    let buf = &buf[..info.buffer_size()];
+
+    let pixels = match info.color_type {
+        png::ColorType::Rgba => buf.to_vec(),
+        png::ColorType::Rgb => buf.chunks(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha => buf.chunks(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        png::ColorType::Grayscale => buf.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        png::ColorType::Indexed => return Err(anyhow::anyhow!("Unexpected indexed PNG output")),
+    };
+
+    Ok(Rgba {
+        width: info.width,
+        height: info.height,
+        pixels,
+    })
+}
+
+fn encode_png(image: &Rgba) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, image.width, image.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&image.pixels)?;
+    }
+    Ok(out)
+}
+
+/// Render the front view of a skin (head, torso, arms and legs with their overlay layers) as a 16x32 PNG
+pub fn render_body(skin_png: &[u8], model: SkinModel) -> Result<Vec<u8>> {
+    let skin = decode_png(skin_png)?;
+    if skin.width != 64 || (skin.height != 64 && skin.height != 32) {
+        return Err(anyhow::anyhow!("Unsupported skin size {}x{}", skin.width, skin.height));
+    }
+    // Legacy 64x32 skins have no left limbs or second layer except the hat
+    let legacy = skin.height == 32;
+    let arm = if model == SkinModel::Slim { 3 } else { 4 };
+
+    let mut body = Rgba::new(BODY_WIDTH, BODY_HEIGHT);
+
+    // The player's right side is on the viewer's left
+    let right_arm_x = 4 - arm;
+    let left_arm_x = 12;
+
+    // Head + hat
+    body.draw(&skin, (8, 8), (8, 8), (4, 0), false);
+    body.draw(&skin, (40, 8), (8, 8), (4, 0), false);
+
+    // Torso
+    body.draw(&skin, (20, 20), (8, 12), (4, 8), false);
+
+    // Right arm and leg
+    body.draw(&skin, (44, 20), (arm, 12), (right_arm_x, 8), false);
+    body.draw(&skin, (4, 20), (4, 12), (4, 20), false);
+
+    if legacy {
+        // Left limbs are the right ones mirrored
+        body.draw(&skin, (44, 20), (arm, 12), (left_arm_x, 8), true);
+        body.draw(&skin, (4, 20), (4, 12), (8, 20), true);
+    } else {
+        body.draw(&skin, (36, 52), (arm, 12), (left_arm_x, 8), false);
+        body.draw(&skin, (20, 52), (4, 12), (8, 20), false);
+
+        // Second layer: jacket, sleeves and pants
+        body.draw(&skin, (20, 36), (8, 12), (4, 8), false);
+        body.draw(&skin, (44, 36), (arm, 12), (right_arm_x, 8), false);
+        body.draw(&skin, (52, 52), (arm, 12), (left_arm_x, 8), false);
+        body.draw(&skin, (4, 36), (4, 12), (4, 20), false);
+        body.draw(&skin, (4, 52), (4, 12), (8, 20), false);
+    }
+
+    encode_png(&body)
+}
+
+/// Render the cached skin of `textures` into a data URL the webview can show without network access
+pub async fn body_data_url(textures: &PlayerTextures) -> Result<Option<String>> {
+    let Some(skin_path) = &textures.skin else {
+        return Ok(None);
+    };
+
+    let data = tokio::fs::read(skin_path).await?;
+    let png = render_body(&data, textures.model)?;
+    Ok(Some(format!(
+        "data:image/png;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(png)
+    )))
+}
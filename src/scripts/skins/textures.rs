@@ -0,0 +1,86 @@
+use super::ELY_BY_SKINSYSTEM;
+use super::types::*;
+use crate::scripts::game::DATA_DIR;
+use crate::scripts::game::utils::download_file;
+use anyhow::Result;
+use reqwest::Client;
+use std::path::PathBuf;
+
+pub fn cache_dir(username: &str) -> PathBuf {
+    PathBuf::from(DATA_DIR).join("skins").join(username.to_lowercase())
+}
+
+/// Fetch the player's skin and cape from Ely.by and cache them locally.
+/// Falls back to the cached copy when the skinsystem can't be reached.
+pub async fn fetch_textures(username: &str) -> Result<PlayerTextures> {
+    match download_textures(username).await {
+        Ok(textures) => Ok(textures),
+        Err(e) => {
+            log::warn!("Failed to fetch textures for {}: {}", username, e);
+            load_cached(username)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("No cached textures for {}", username))
+        }
+    }
+}
+
+async fn download_textures(username: &str) -> Result<PlayerTextures> {
+    let client = Client::builder().user_agent("ezLauncher/0.2.0").build()?;
+    let response = client
+        .get(format!("{}/textures/{}", ELY_BY_SKINSYSTEM, username))
+        .send()
+        .await?;
+
+    // Ely.by answers 204 for accounts without any textures
+    let textures: TexturesResponse = if response.status() == reqwest::StatusCode::NO_CONTENT {
+        TexturesResponse { skin: None, cape: None }
+    } else {
+        response.error_for_status()?.json().await?
+    };
+
+    let dir = cache_dir(username);
+    tokio::fs::create_dir_all(&dir).await?;
+
+    let mut result = PlayerTextures::default();
+
+    if let Some(skin) = &textures.skin {
+        let path = dir.join("skin.png");
+        download_file(&client, &https(&skin.url), &path).await?;
+        result.skin = Some(path);
+
+        let model = skin.metadata.as_ref().and_then(|m| m.model.as_deref());
+        if model == Some("slim") {
+            result.model = SkinModel::Slim;
+        }
+    }
+
+    if let Some(cape) = &textures.cape {
+        let path = dir.join("cape.png");
+        download_file(&client, &https(&cape.url), &path).await?;
+        result.cape = Some(path);
+    } else if dir.join("cape.png").exists() {
+        // Cape was removed
+        tokio::fs::remove_file(dir.join("cape.png")).await?;
+    }
+
+    tokio::fs::write(dir.join("textures.json"), serde_json::to_string_pretty(&result)?).await?;
+    Ok(result)
+}
+
+pub async fn load_cached(username: &str) -> Result<Option<PlayerTextures>> {
+    let path = cache_dir(username).join("textures.json");
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = tokio::fs::read_to_string(path).await?;
+    Ok(Some(serde_json::from_str(&content)?))
+}
+
+/// Skinsystem still hands out plain http texture URLs
+fn https(url: &str) -> String {
+    match url.strip_prefix("http://") {
+        Some(rest) => format!("https://{}", rest),
+        None => url.to_string(),
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkinModel {
+    /// 4px wide arms ("Steve")
+    #[default]
+    Classic,
+    /// 3px wide arms ("Alex")
+    Slim,
+}
+
+impl SkinModel {
+    /// Value of the `model` field expected by the Yggdrasil texture API
+    pub fn api_value(&self) -> &'static str {
+        match self {
+            SkinModel::Classic => "",
+            SkinModel::Slim => "slim",
+        }
+    }
+}
+
+/// Locally cached textures of a player
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PlayerTextures {
+    pub model: SkinModel,
+    pub skin: Option<PathBuf>,
+    pub cape: Option<PathBuf>,
+}
+
+/// Response of the skinsystem `textures` endpoint
+#[derive(Debug, Deserialize)]
+pub struct TexturesResponse {
+    #[serde(rename = "SKIN")]
+    pub skin: Option<Texture>,
+    #[serde(rename = "CAPE")]
+    pub cape: Option<Texture>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Texture {
+    pub url: String,
+    pub metadata: Option<TextureMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TextureMetadata {
+    pub model: Option<String>,
+}
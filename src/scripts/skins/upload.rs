@@ -0,0 +1,89 @@
+use super::types::SkinModel;
+use anyhow::Result;
+use reqwest::Client;
+use reqwest::multipart::{Form, Part};
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Read the image dimensions from the PNG's IHDR chunk
+pub fn png_dimensions(data: &[u8]) -> Result<(u32, u32)> {
+    if data.len() < 24 || &data[0..8] != PNG_SIGNATURE || &data[12..16] != b"IHDR" {
+        return Err(anyhow::anyhow!("Not a PNG image"));
+    }
+
+    let width = u32::from_be_bytes(data[16..20].try_into()?);
+    let height = u32::from_be_bytes(data[20..24].try_into()?);
+    Ok((width, height))
+}
+
+/// Check that `data` is a skin the game can use with the given model
+pub fn validate_skin(data: &[u8], model: SkinModel) -> Result<()> {
+    match png_dimensions(data)? {
+        (64, 64) => Ok(()),
+        (64, 32) if model == SkinModel::Slim => Err(anyhow::anyhow!(
+            "Legacy 64x32 skins have no slim arms, use a 64x64 skin for the slim model"
+        )),
+        (64, 32) => Ok(()),
+        (width, height) => Err(anyhow::anyhow!(
+            "Skin must be 64x64 or 64x32 pixels, got {}x{}",
+            width,
+            height
+        )),
+    }
+}
+
+/// Whether the auth server takes skin uploads through the texture API.
+/// Ely.by doesn't, skins are changed on its website instead.
+pub fn supports_upload(api_root: &str) -> bool {
+    url::Url::parse(api_root)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .is_some_and(|host| host != "ely.by" && !host.ends_with(".ely.by"))
+}
+
+/// Upload a new skin through the Yggdrasil texture API of an authlib-injector server
+pub async fn upload_skin(
+    api_root: &str,
+    uuid: &str,
+    access_token: &str,
+    data: Vec<u8>,
+    model: SkinModel,
+) -> Result<()> {
+    if !supports_upload(api_root) {
+        return Err(anyhow::anyhow!(
+            "This auth server doesn't support uploading skins from the launcher"
+        ));
+    }
+    validate_skin(&data, model)?;
+
+    let form = Form::new().text("model", model.api_value()).part(
+        "file",
+        Part::bytes(data)
+            .file_name("skin.png")
+            .mime_str("image/png")?,
+    );
+
+    let client = Client::builder().user_agent("ezLauncher/0.2.0").build()?;
+    let response = client
+        .put(format!(
+            "{}/api/user/profile/{}/skin",
+            api_root.trim_end_matches('/'),
+            uuid.replace('-', "")
+        ))
+        .header("Authorization", format!("Bearer {}", access_token))
+        .multipart(form)
+        .send()
+        .await?;
+
+    match response.status().as_u16() {
+        200..=299 => Ok(()),
+        404 | 405 => Err(anyhow::anyhow!(
+            "This auth server doesn't support uploading skins from the launcher"
+        )),
+        _ => Err(anyhow::anyhow!(
+            "Skin upload failed: {} {}",
+            response.status(),
+            response.text().await.unwrap_or_default()
+        )),
+    }
+}
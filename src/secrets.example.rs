@@ -1,5 +1,7 @@
 // Get and set your Client ID and Client Secret from Ely.by OAuth page (https://account.ely.by/dev/applications)
-// and set the Redirect URI to REDIRECT_URI
-pub const CLIENT_ID: &str = "";
-pub const CLIENT_SECRET: &str = "";
-pub const REDIRECT_URI: &str = "http://localhost:23234/callback";
\ No newline at end of file
+// and allow the redirect URIs http://localhost:<port>/callback for every callback port in the settings
+// (login::script::DEFAULT_CALLBACK_PORTS unless changed)
+pub const AUTH_CLIENT_ID: &str = "";
+pub const AUTH_CLIENT_SECRET: &str = "";
+// CurseForge API key for modpack imports (https://console.curseforge.com)
+pub const CURSEFORGE_API_KEY: &str = "";
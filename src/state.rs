@@ -1,15 +1,48 @@
 use dioxus::prelude::*;
-use crate::{components::login::script::UserInfo, logger};
+use std::collections::HashMap;
+use crate::{
+    components::instance::script::BlockedLaunch,
+    components::login::{script::UserInfo, yggdrasil::Session},
+    logger,
+    scripts::{
+        crash::CrashAnalysis,
+        game::QuickPlay,
+        instances::Instance,
+        process::{GameLogEntry, GameProcess},
+    },
+};
 
 #[derive(Debug, Clone)]
 pub struct AuthState {
     pub token: Option<String>,
     pub user: Option<UserInfo>,
+    /// Set when logged in with username/password against an authlib-injector server
+    pub session: Option<Session>,
 }
 
 pub static AUTH: GlobalSignal<AuthState> = Signal::global(|| AuthState {
     token: None,
     user: None,
+    session: None,
 });
 
-pub static CONSOLE_LOG: GlobalSignal<Vec<logger::LogEntry>> = Signal::global(|| Vec::new());
\ No newline at end of file
+pub static CONSOLE_LOG: GlobalSignal<Vec<logger::LogEntry>> = Signal::global(|| Vec::new());
+
+/// Bumped whenever the player's skin changes so views re-render it
+pub static SKIN_REVISION: GlobalSignal<u32> = Signal::global(|| 0);
+
+/// Game runs by run number: the active ones and the latest finished one per instance
+pub static GAMES: GlobalSignal<HashMap<u64, GameProcess>> = Signal::global(|| HashMap::new());
+
+/// Output of the runs in `GAMES` by run number
+pub static GAME_LOGS: GlobalSignal<HashMap<u64, Vec<GameLogEntry>>> =
+    Signal::global(|| HashMap::new());
+
+/// Analysis of the latest crash by instance id, cleared when the instance starts again
+pub static CRASHES: GlobalSignal<HashMap<String, CrashAnalysis>> = Signal::global(|| HashMap::new());
+
+/// Launches stopped by the mod check by instance id, until launched anyway, dismissed or started again
+pub static BLOCKED_LAUNCHES: GlobalSignal<HashMap<String, BlockedLaunch>> = Signal::global(|| HashMap::new());
+
+/// A launch waiting for the user to log in, shows the login prompt
+pub static LAUNCH_AFTER_LOGIN: GlobalSignal<Option<(Instance, Option<QuickPlay>)>> = Signal::global(|| None);
\ No newline at end of file